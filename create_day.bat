@@ -1,5 +0,0 @@
-mkdir inputs\day_%1
-echo. > inputs\day_%1\input_example_1.txt
-echo. > inputs\day_%1\input.txt
-
-copy src\day_x.rs src\day_%1.rs
\ No newline at end of file
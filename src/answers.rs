@@ -0,0 +1,177 @@
+use std::{collections::HashMap, env, path::{Path, PathBuf}};
+
+use crate::error::{self, AocError};
+use crate::generic;
+use crate::inputs::InputResolver;
+use crate::solution::Answer;
+
+pub const ANSWERS_VAR: &str = "AOC_ANSWERS";
+pub const FILE_NAME: &str = "answers.toml";
+
+// Expected answers keyed by (day, part, input variant), read from a small subset of TOML:
+//
+//     [day_17.part_1]
+//     example_1 = "4,6,3,5,6,3,5,2,1,0"
+//     input = "7,0,3,1,2,6,3,7,1"
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(usize, usize, String), Answer>,
+}
+
+impl Answers {
+    pub fn parse(input_lines: &[String]) -> Result<Self, AocError> {
+        let mut answers: Answers = Answers::default();
+        let mut table: Option<(usize, usize)> = None;
+        for (i, line) in input_lines.iter().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with("#") {
+                continue;
+            }
+
+            if line.starts_with("[") {
+                table = Some(parse_table(line).map_err(|e| e.at_line(i + 1))?);
+                continue;
+            }
+
+            let (day, part) = table.ok_or(AocError::parse("expected a [day_N.part_P] table before any answers").at_line(i + 1))?;
+            let (variant, value) = line.split_once("=").ok_or(AocError::parse(format!("expected variant = answer, got {:?}", line)).at_line(i + 1))?;
+            let variant: &str = variant.trim();
+            if variant.is_empty() || !variant.chars().all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '-') {
+                return Err(AocError::parse(format!("bad variant name {:?}", variant)).at_line(i + 1));
+            }
+            if answers.get(day, part, variant).is_some() {
+                return Err(AocError::parse(format!("day {} part {} {:?} is given twice", day, part, variant)).at_line(i + 1));
+            }
+            answers.insert(day, part, variant, parse_value(value).map_err(|e| e.at_line(i + 1))?);
+        }
+
+        return Ok(answers);
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let input_lines: Vec<String> = generic::read_in_file(path.to_string_lossy().as_ref())?;
+        return Answers::parse(&input_lines).map_err(|e| match e {
+            AocError::Parse { line, column, message } => AocError::Parse { line, column, message: format!("{} in {}", message, path.display()) },
+            other => other,
+        });
+    }
+
+    pub fn find(resolver: &InputResolver) -> Option<PathBuf> {
+        // Answers belong with the inputs they were solved from, so the inputs folder wins over the repo's own file,
+        // which only has the example answers.
+        if let Ok(path) = env::var(ANSWERS_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        return [resolver.root().join(FILE_NAME), Path::new(env!("CARGO_MANIFEST_DIR")).join(FILE_NAME)]
+            .into_iter()
+            .find(|x| x.is_file());
+    }
+
+    pub fn from_resolver(resolver: &InputResolver) -> Result<Self, AocError> {
+        match Answers::find(resolver) {
+            Some(path) => return Answers::load(&path),
+            None => return Ok(Answers::default()),
+        }
+    }
+
+    pub fn get(&self, day: usize, part: usize, variant: &str) -> Option<&Answer> {
+        return self.answers.get(&(day, part, variant.to_string()));
+    }
+
+    pub fn insert(&mut self, day: usize, part: usize, variant: &str, answer: Answer) {
+        self.answers.insert((day, part, variant.to_string()), answer);
+    }
+
+    pub fn variants(&self, day: usize, part: usize) -> Vec<String> {
+        let mut variants: Vec<String> = self.answers.keys().filter(|x| x.0 == day && x.1 == part).map(|x| x.2.clone()).collect::<Vec<String>>();
+        variants.sort();
+        return variants;
+    }
+}
+
+fn parse_table(line: &str) -> Result<(usize, usize), AocError> {
+    let name: &str = line.strip_prefix("[").and_then(|x| x.strip_suffix("]")).ok_or(AocError::parse(format!("bad table {:?}", line)))?;
+    let (day, part) = name.split_once(".").ok_or(AocError::parse(format!("expected [day_N.part_P], got {:?}", line)))?;
+    let day: usize = error::parse_number::<usize>(day.trim().strip_prefix("day_").ok_or(AocError::parse(format!("expected day_N, got {:?}", day)))?)?;
+    let part: usize = error::parse_number::<usize>(part.trim().strip_prefix("part_").ok_or(AocError::parse(format!("expected part_P, got {:?}", part)))?)?;
+    if part != 1 && part != 2 {
+        return Err(AocError::parse(format!("part must be 1 or 2, got {}", part)));
+    }
+    return Ok((day, part));
+}
+
+fn parse_value(value: &str) -> Result<Answer, AocError> {
+    let value: &str = value.trim();
+    if let Some(text) = value.strip_prefix("\"") {
+        let end: usize = text.find("\"").ok_or(AocError::parse(format!("unterminated string {:?}", value)))?;
+        let rest: &str = text[(end + 1)..].trim();
+        if !rest.is_empty() && !rest.starts_with("#") {
+            return Err(AocError::parse(format!("unexpected {:?} after string", rest)));
+        }
+        return Ok(Answer::Text(text[..end].to_string()));
+    }
+
+    let number: &str = value.split("#").next().unwrap_or("").trim();
+    return Ok(Answer::Number(error::parse_number::<i64>(number.replace("_", "").as_str())?));
+}
+
+// Missing inputs are skipped, so the tests only check the inputs the person running them actually has.
+pub fn check<T: Into<Answer>>(day: usize, part: usize, variant: &str, solver: impl Fn(&str) -> Result<T, AocError>) {
+    let answers: Answers = Answers::from_resolver(&InputResolver::from_env()).unwrap();
+    let answer: Answer = match solver(variant) {
+        Ok(x) => x.into(),
+        Err(AocError::MissingInput { .. }) => {
+            println!("Skipping day {} part {} {:?}, no input", day, part, variant);
+            return;
+        },
+        Err(e) => panic!("day {} part {} {:?}: {}", day, part, variant, e),
+    };
+
+    println!("Answer = {}", answer);
+    match answers.get(day, part, variant) {
+        Some(expected) => assert!(answer == *expected, "day {} part {} {:?}: expected {}, got {}", day, part, variant, expected, answer),
+        None => println!("No answer recorded for day {} part {} {:?}", day, part, variant),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(value: &str) -> Vec<String> {
+        return value.lines().map(str::to_string).collect::<Vec<String>>();
+    }
+
+    #[test]
+    fn parse_answers() {
+        let answers: Answers = Answers::parse(&to_lines("# Mine\n[day_1.part_1]\nexample_1 = 11\ninput = 2_769_675 # first try\n\n[day_23.part_2]\ninput = \"co,de,ka,ta\"\n")).unwrap();
+        assert!(answers.get(1, 1, "example_1") == Some(&Answer::Number(11)));
+        assert!(answers.get(1, 1, "input") == Some(&Answer::Number(2769675)));
+        assert!(answers.get(23, 2, "input") == Some(&Answer::from("co,de,ka,ta")));
+        assert!(answers.get(1, 2, "input").is_none());
+        assert!(answers.variants(1, 1) == vec!["example_1".to_string(), "input".to_string()]);
+    }
+
+    #[test]
+    fn parse_bad_answers() {
+        assert!(Answers::parse(&to_lines("input = 3")).unwrap_err().to_string() == "line 1: expected a [day_N.part_P] table before any answers");
+        assert!(Answers::parse(&to_lines("[day_1.part_3]")).unwrap_err().to_string() == "line 1: part must be 1 or 2, got 3");
+        assert!(Answers::parse(&to_lines("[day_1.part_1]\ninput = x")).unwrap_err().to_string() == "line 2: bad number \"x\"");
+        assert!(Answers::parse(&to_lines("[day_1.part_1]\ninput = \"ab")).is_err());
+        assert!(Answers::parse(&to_lines("[day_1.part_1]\ninput = 1\ninput = 2")).unwrap_err().to_string().starts_with("line 3:"));
+    }
+
+    #[test]
+    fn repo_answers_are_examples_only() {
+        let answers: Answers = Answers::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join(FILE_NAME)).unwrap();
+        assert!((1..=25).all(|day| answers.get(day, 1, "input").is_none() && answers.get(day, 2, "input").is_none()));
+    }
+
+    #[test]
+    fn check_skips_missing_input() {
+        check(1, 1, "example_1", |_| -> Result<usize, AocError> {
+            return Err(AocError::MissingInput { year: 2024, day: 1, variant: "example_1".to_string(), tried: vec![] });
+        });
+    }
+}
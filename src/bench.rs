@@ -0,0 +1,376 @@
+use std::{fmt, fs, path::Path, time::{Duration, Instant}};
+
+use crate::error::AocError;
+use crate::generic;
+use crate::solution::Solution;
+
+// Changes smaller than this are timer noise, whatever the percentage.
+const MIN_REGRESSION: Duration = Duration::from_micros(50);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part_1"),
+            Phase::Part2 => write!(f, "part_2"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Phase {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "parse" => Ok(Phase::Parse),
+            "part_1" => Ok(Phase::Part1),
+            "part_2" => Ok(Phase::Part2),
+            _ => Err(AocError::parse(format!("bad phase {:?}", value))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub day: usize,
+    pub phase: Phase,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    pub fn from_samples(day: usize, phase: Phase, samples: &[Duration]) -> Self {
+        let mut sorted: Vec<Duration> = samples.to_vec();
+        sorted.sort();
+        return Timing { day, phase, min: sorted[0], median: sorted[sorted.len() / 2], max: sorted[sorted.len() - 1] };
+    }
+
+    pub fn regressed(&self, baseline: &Timing, threshold: f64) -> bool {
+        return self.median > baseline.median.mul_f64(1.0 + threshold) && self.median - baseline.median > MIN_REGRESSION;
+    }
+}
+
+// The input is copied before the timer starts, so the parse phase is only the day building its state from the lines
+// and the parts only the solving.
+pub fn bench_day(day: &mut dyn Solution, input_lines: &[String], iterations: usize) -> Result<Vec<Timing>, AocError> {
+    let mut samples: Vec<Vec<Duration>> = vec![Vec::new(); 3];
+    for _ in 0..iterations.max(1) {
+        let lines: Vec<String> = input_lines.to_vec();
+        let start_time = Instant::now();
+        day.parse(lines)?;
+        samples[0].push(start_time.elapsed());
+
+        let start_time = Instant::now();
+        day.part1()?;
+        samples[1].push(start_time.elapsed());
+
+        let start_time = Instant::now();
+        day.part2()?;
+        samples[2].push(start_time.elapsed());
+    }
+
+    return Ok([Phase::Parse, Phase::Part1, Phase::Part2].iter().zip(samples.iter())
+        .map(|(phase, x)| Timing::from_samples(day.day(), *phase, x))
+        .collect::<Vec<Timing>>());
+}
+
+pub fn to_json(timings: &[Timing], iterations: usize) -> String {
+    let rows: Vec<String> = timings.iter()
+        .map(|x| format!("    {{\"day\": {}, \"phase\": \"{}\", \"min_ns\": {}, \"median_ns\": {}, \"max_ns\": {}}}", x.day, x.phase, x.min.as_nanos(), x.median.as_nanos(), x.max.as_nanos()))
+        .collect::<Vec<String>>();
+    return format!("{{\n  \"iterations\": {},\n  \"timings\": [\n{}\n  ]\n}}\n", iterations, rows.join(",\n"));
+}
+
+pub fn from_json(value: &str) -> Result<Vec<Timing>, AocError> {
+    let json: Json = JsonParser { chars: value.chars().collect::<Vec<char>>(), index: 0 }.parse_document()?;
+    let rows: &Vec<Json> = match json.get("timings") {
+        Some(Json::Array(x)) => x,
+        _ => return Err(AocError::parse("expected a \"timings\" array")),
+    };
+
+    let mut timings: Vec<Timing> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let number = |key: &str| -> Result<u64, AocError> {
+            match row.get(key) {
+                Some(Json::Number(x)) if *x >= 0.0 => Ok(*x as u64),
+                _ => Err(AocError::parse(format!("timing {} has no {:?}", i + 1, key))),
+            }
+        };
+        let phase: Phase = match row.get("phase") {
+            Some(Json::String(x)) => Phase::try_from(x.as_str())?,
+            _ => return Err(AocError::parse(format!("timing {} has no \"phase\"", i + 1))),
+        };
+        timings.push(Timing {
+            day: number("day")? as usize,
+            phase,
+            min: Duration::from_nanos(number("min_ns")?),
+            median: Duration::from_nanos(number("median_ns")?),
+            max: Duration::from_nanos(number("max_ns")?),
+        });
+    }
+    return Ok(timings);
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<Timing>, AocError> {
+    let input_lines: Vec<String> = generic::read_in_file(path.to_string_lossy().as_ref())?;
+    return from_json(&input_lines.join("\n")).map_err(|e| AocError::parse(format!("{} in {}", e, path.display())));
+}
+
+pub fn save_baseline(path: &Path, timings: &[Timing], iterations: usize) -> Result<(), AocError> {
+    return fs::write(path, to_json(timings, iterations)).map_err(|e| AocError::Write { path: path.display().to_string(), source: e });
+}
+
+pub fn format_table(timings: &[Timing], baseline: &[Timing], threshold: f64) -> String {
+    let mut rows: Vec<Vec<String>> = vec![["Day", "Phase", "Min", "Median", "Max", "Baseline", "Change"].iter().map(|x| x.to_string()).collect::<Vec<String>>()];
+    for t in timings {
+        let previous: Option<&Timing> = baseline.iter().find(|x| x.day == t.day && x.phase == t.phase);
+        let change: String = match previous {
+            Some(p) if p.median.as_nanos() > 0 => {
+                let percent: f64 = (t.median.as_secs_f64() / p.median.as_secs_f64() - 1.0) * 100.0;
+                let flag: &str = if t.regressed(p, threshold) { "  REGRESSION" } else { "" };
+                format!("{:+.1}%{}", percent, flag)
+            },
+            _ => "-".to_string(),
+        };
+        rows.push(vec![
+            t.day.to_string(),
+            t.phase.to_string(),
+            format!("{:?}", t.min),
+            format!("{:?}", t.median),
+            format!("{:?}", t.max),
+            previous.map(|x| format!("{:?}", x.median)).unwrap_or("-".to_string()),
+            change,
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len()).map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0)).collect::<Vec<usize>>();
+    let mut lines: Vec<String> = rows.iter()
+        .map(|r| r.iter().zip(widths.iter()).map(|(x, w)| format!("{:<width$}", x, width = *w)).collect::<Vec<String>>().join("  ").trim_end().to_string())
+        .collect::<Vec<String>>();
+    lines.insert(1, widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("  "));
+    return lines.join("\n");
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(x) => return x.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => return None,
+        }
+    }
+}
+
+// Just enough JSON to read back the baselines this module writes.
+struct JsonParser {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl JsonParser {
+    fn error(&self, message: &str) -> AocError {
+        return AocError::parse(message.to_string()).at_column(self.index + 1);
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.index < self.chars.len() && self.chars[self.index].is_whitespace() {
+            self.index += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), AocError> {
+        self.skip_whitespace();
+        if self.chars.get(self.index) != Some(&c) {
+            return Err(self.error(format!("expected {:?}", c).as_str()));
+        }
+        self.index += 1;
+        return Ok(());
+    }
+
+    fn parse_document(&mut self) -> Result<Json, AocError> {
+        let value: Json = self.parse_value()?;
+        self.skip_whitespace();
+        if self.index != self.chars.len() {
+            return Err(self.error("unexpected trailing characters"));
+        }
+        return Ok(value);
+    }
+
+    fn parse_value(&mut self) -> Result<Json, AocError> {
+        self.skip_whitespace();
+        match self.chars.get(self.index) {
+            Some('{') => return self.parse_object(),
+            Some('[') => return self.parse_array(),
+            Some('"') => return Ok(Json::String(self.parse_string()?)),
+            Some('t') => return self.parse_word("true", Json::Bool(true)),
+            Some('f') => return self.parse_word("false", Json::Bool(false)),
+            Some('n') => return self.parse_word("null", Json::Null),
+            Some(c) if *c == '-' || c.is_ascii_digit() => return self.parse_number(),
+            _ => return Err(self.error("expected a value")),
+        }
+    }
+
+    fn parse_word(&mut self, word: &str, value: Json) -> Result<Json, AocError> {
+        if self.chars[self.index..].iter().take(word.len()).collect::<String>() != word {
+            return Err(self.error(format!("expected {}", word).as_str()));
+        }
+        self.index += word.len();
+        return Ok(value);
+    }
+
+    fn parse_number(&mut self) -> Result<Json, AocError> {
+        let start: usize = self.index;
+        while self.index < self.chars.len() && (self.chars[self.index].is_ascii_digit() || "+-.eE".contains(self.chars[self.index])) {
+            self.index += 1;
+        }
+        let text: String = self.chars[start..self.index].iter().collect::<String>();
+        return text.parse::<f64>().map(Json::Number).map_err(|_| self.error(format!("bad number {:?}", text).as_str()));
+    }
+
+    fn parse_string(&mut self) -> Result<String, AocError> {
+        self.expect('"')?;
+        let mut value: String = String::new();
+        loop {
+            match self.chars.get(self.index) {
+                None => return Err(self.error("unterminated string")),
+                Some('"') => {
+                    self.index += 1;
+                    return Ok(value);
+                },
+                Some('\\') => {
+                    match self.chars.get(self.index + 1) {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(c) if "\"\\/".contains(*c) => value.push(*c),
+                        _ => return Err(self.error("unsupported escape")),
+                    }
+                    self.index += 2;
+                },
+                Some(c) => {
+                    value.push(*c);
+                    self.index += 1;
+                },
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, AocError> {
+        self.expect('[')?;
+        let mut values: Vec<Json> = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.index) == Some(&']') {
+            self.index += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.chars.get(self.index) {
+                Some(',') => self.index += 1,
+                Some(']') => {
+                    self.index += 1;
+                    return Ok(Json::Array(values));
+                },
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, AocError> {
+        self.expect('{')?;
+        let mut values: Vec<(String, Json)> = Vec::new();
+        self.skip_whitespace();
+        if self.chars.get(self.index) == Some(&'}') {
+            self.index += 1;
+            return Ok(Json::Object(values));
+        }
+        loop {
+            self.skip_whitespace();
+            let key: String = self.parse_string()?;
+            self.expect(':')?;
+            values.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.chars.get(self.index) {
+                Some(',') => self.index += 1,
+                Some('}') => {
+                    self.index += 1;
+                    return Ok(Json::Object(values));
+                },
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+
+    fn timing(day: usize, phase: Phase, median_us: u64) -> Timing {
+        return Timing { day, phase, min: Duration::from_micros(median_us / 2), median: Duration::from_micros(median_us), max: Duration::from_micros(median_us * 2) };
+    }
+
+    #[test]
+    fn samples_and_regressions() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3].iter().map(|x| Duration::from_millis(*x)).collect::<Vec<Duration>>();
+        assert!(Timing::from_samples(1, Phase::Parse, &samples) == Timing { day: 1, phase: Phase::Parse, min: Duration::from_millis(1), median: Duration::from_millis(3), max: Duration::from_millis(5) });
+
+        assert!(timing(1, Phase::Part1, 1200).regressed(&timing(1, Phase::Part1, 1000), 0.1));
+        assert!(!timing(1, Phase::Part1, 1050).regressed(&timing(1, Phase::Part1, 1000), 0.1));
+        // Too small to be anything but noise.
+        assert!(!timing(1, Phase::Part1, 30).regressed(&timing(1, Phase::Part1, 10), 0.1));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let timings: Vec<Timing> = vec![timing(1, Phase::Parse, 10), timing(16, Phase::Part2, 123456)];
+        assert!(from_json(&to_json(&timings, 10)).unwrap() == timings);
+        assert!(from_json("{\"timings\": []}").unwrap().is_empty());
+        assert!(from_json("{\"timings\": [{\"day\": 1}]}").is_err());
+        assert!(from_json("{\"timings\": [").is_err());
+    }
+
+    #[test]
+    fn bench_and_compare() {
+        let input_lines: Vec<String> = ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let timings: Vec<Timing> = bench_day(solution::get_day(1).unwrap().as_mut(), &input_lines, 3).unwrap();
+        assert!(timings.iter().map(|x| x.phase).collect::<Vec<Phase>>() == vec![Phase::Parse, Phase::Part1, Phase::Part2]);
+        assert!(timings.iter().all(|x| x.min <= x.median && x.median <= x.max));
+
+        let baseline: Vec<Timing> = vec![Timing { median: Duration::from_nanos(1), ..timings[1].clone() }];
+        let mut slow: Vec<Timing> = timings.clone();
+        slow[1].median = Duration::from_millis(1);
+        let table: String = format_table(&slow, &baseline, 0.1);
+        assert!(table.lines().next().unwrap().starts_with("Day  Phase   Min"));
+        assert!(table.lines().nth(3).unwrap().ends_with("REGRESSION"));
+        assert!(table.lines().nth(2).unwrap().ends_with("-"));
+    }
+
+    #[test]
+    fn bad_input_fails_parse() {
+        // Bad input is caught by the parse phase, before either part runs.
+        let input_lines: Vec<String> = vec!["123".to_string(), "not a number".to_string()];
+        let mut day: Box<dyn Solution> = solution::get_day(22).unwrap();
+        assert!(matches!(day.parse(input_lines.clone()), Err(AocError::Parse { line: Some(2), .. })));
+        assert!(bench_day(day.as_mut(), &input_lines, 1).is_err());
+    }
+}
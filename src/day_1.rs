@@ -1,17 +1,41 @@
-const INPUTS_FOLDER: &str = "inputs/day_1";
+const DAY: usize = 1;
 
 use std::collections::HashMap;
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::solution::{Answer, Solution};
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut left_list: Vec<i32> = input_lines.iter().map(|x| x.split(" ").collect::<Vec<&str>>()[0].parse::<i32>().unwrap()).collect::<Vec<i32>>();
-    let mut right_list: Vec<i32> = input_lines.iter().map(|x| x.split(" ").collect::<Vec<&str>>().last().unwrap().parse::<i32>().unwrap()).collect::<Vec<i32>>();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let (left_list, right_list) = parse_lists(&input_lines)?;
+    return Ok(solve(&left_list, &right_list, part_2));
+}
+
+// The left and right columns, each sorted.
+fn parse_lists(input_lines: &[String]) -> Result<(Vec<i32>, Vec<i32>), AocError> {
+    let pairs: Vec<(i32, i32)> = error::parse_lines(input_lines, |x| parse_pair(x))?;
+    let mut left_list: Vec<i32> = pairs.iter().map(|x| x.0).collect::<Vec<i32>>();
+    let mut right_list: Vec<i32> = pairs.iter().map(|x| x.1).collect::<Vec<i32>>();
     left_list.sort();
     right_list.sort();
+    return Ok((left_list, right_list));
+}
 
+fn parse_pair(line: &str) -> Result<(i32, i32), AocError> {
+    let numbers: Vec<&str> = line.split_whitespace().collect::<Vec<&str>>();
+    if numbers.len() != 2 {
+        return Err(AocError::parse(format!("expected two numbers, got {:?}", line)));
+    }
+    return Ok((error::parse_number::<i32>(numbers[0])?, error::parse_number::<i32>(numbers[1])?));
+}
+
+fn solve(left_list: &[i32], right_list: &[i32], part_2: bool) -> usize {
     let answer: i32;
 
     if !part_2
@@ -21,7 +45,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     else {
         let mut right_counts: HashMap<i32, usize> = HashMap::new();
         for x in right_list {
-            let number = right_counts.entry(x).or_insert(0);
+            let number = right_counts.entry(*x).or_insert(0);
             *number += 1;
         }
 
@@ -37,41 +61,67 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     return answer as usize;
 }
 
+#[derive(Default)]
+pub struct Day1 {
+    left_list: Vec<i32>,
+    right_list: Vec<i32>,
+}
+
+impl Solution for Day1 {
+    fn day(&self) -> usize {
+        return 1;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        (self.left_list, self.right_list) = parse_lists(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(solve(&self.left_list, &self.right_list, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(solve(&self.left_list, &self.right_list, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
         // Do a quick test here
     }
 
+    #[test]
+    fn bad_input() {
+        let input_lines: Vec<String> = vec!["3   4".to_string(), "4".to_string(), "x   3".to_string()];
+        assert!(solve_lines(input_lines[..2].to_vec(), false).unwrap_err().to_string() == "line 2: expected two numbers, got \"4\"");
+        assert!(solve_lines(vec![input_lines[0].clone(), input_lines[2].clone()], false).unwrap_err().to_string() == "line 2: bad number \"x\"");
+        assert!(solve_lines(Vec::new(), false).unwrap() == 0);
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 11);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2769675);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 31);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 24643097);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,132 +1,35 @@
-const INPUTS_FOLDER: &str = "inputs/day_10";
+const DAY: usize = 10;
 
-use std::{collections::HashSet, fmt};
+use std::collections::HashSet;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::generic::{Grid, Position};
+use crate::inputs;
+use crate::solution::{Answer, Solution};
 
-#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
 }
 
-impl Direction {
-    fn iter() -> impl Iterator<Item = Direction> {
-        return [
-            Self::Up,
-            Self::Down,
-            Self::Left,
-            Self::Right,
-        ].iter().copied();
-    }
-}
-
-#[derive(Clone, PartialEq, Hash, Eq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-impl fmt::Debug for Position {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "(r: {}, c: {})", self.row, self.col)
-    }
-}
-
-impl Position {
-    fn walk(&self, steps: usize, direction: Direction) -> Self {
-        let mut new_position = self.clone();
-        match direction {
-            Direction::Up => new_position.row -= steps,
-            Direction::Down => new_position.row += steps,
-            Direction::Left => new_position.col -= steps,
-            Direction::Right => new_position.col += steps,
-        }
-        return new_position
-    }
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let trail_map: Grid<usize> = parse_trail_map(&input_lines)?;
+    return Ok(sum_scores(&trail_map, part_2));
 }
 
-struct TrailMap {
-    tiles: Vec<Vec<usize>>,
-}
-
-impl From<Vec<String>> for TrailMap {
-    fn from(value: Vec<String>) -> Self {
-        Self{tiles: value.iter().map(|x| x.chars().map(|y| y as usize - 48).collect::<Vec<usize>>()).collect::<Vec<Vec<usize>>>()}
-    }
-}
-
-impl TrailMap {
-    fn get_row_count(&self) -> usize {
-        return self.tiles.len();
-    }
-
-    fn get_col_count(&self) -> usize {
-        return self.tiles[0].len();
-    }
-
-    fn get_pos(&self, row: usize, col: usize) -> usize {
-        return self.tiles[row][col];
-    }
-
-    fn get_pos_from_pos(&self, p: &Position) -> usize {
-        return self.tiles[p.row][p.col];
-    }
-
-    fn get_neighbours(&self, p: Position) -> Vec<Position> {
-        let mut row: i32 = p.row as i32;
-        let mut col: i32 = p.col as i32;
-        let mut neighbours: Vec<Position> = Vec::new();
-
-        for d in Direction::iter() {
-            match d {
-                Direction::Up => {
-                    if p.row > 0 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Down => {
-                    if p.row < self.get_row_count() - 1 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Left => {
-                    if p.col > 0 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Right => {
-                    if p.col < self.get_col_count() - 1 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-            };
-        }
-        return neighbours;
-    }
+fn parse_trail_map(input_lines: &[String]) -> Result<Grid<usize>, AocError> {
+    // Anything that isn't a height can never be the next step of a trail.
+    return Ok(Grid::<char>::parse(input_lines)?.map(|x| x.to_digit(10).map(|y| y as usize).unwrap_or(usize::MAX)));
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut trail_map: TrailMap = TrailMap::from(input_lines);
-
-
-    let mut starting_positions: Vec<Position> = Vec::new();
-    for r in 0..trail_map.get_row_count() {
-        for c in 0..trail_map.get_col_count() {
-            if trail_map.get_pos(r, c) == 0 {
-                starting_positions.push(Position { row: r, col: c });
-            }
-        }
-    }
+fn sum_scores(trail_map: &Grid<usize>, part_2: bool) -> usize {
+    let starting_positions: Vec<Position> = trail_map.find_all(&0);
 
     let mut score_sum: usize = 0;
     let mut trail_heads_vec: Vec<Position>;
     let mut trail_heads: HashSet<&Position>;
     for s in starting_positions {
-        trail_heads_vec = get_score(&s, &trail_map);
+        trail_heads_vec = get_score(&s, trail_map);
         if !part_2 {
             trail_heads = HashSet::from_iter(trail_heads_vec.iter());
             score_sum += trail_heads.len();
@@ -139,18 +42,17 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     return score_sum;
 }
 
-fn get_score(p: &Position, trail_map: &TrailMap) -> Vec<Position> {
+fn get_score(p: &Position, trail_map: &Grid<usize>) -> Vec<Position> {
     let mut heads: Vec<Position> = Vec::new();
-    let current_value = trail_map.get_pos_from_pos(p);
+    let current_value = trail_map[*p];
     // println!("{}Getting score for {:?}", "\t".to_string().repeat(current_value), p);
     if current_value == 9 {
         // println!("{}Found a 9", "\t".to_string().repeat(current_value));
-        heads.push(p.clone());
+        heads.push(*p);
         return heads;
     }
-    let neighbours = trail_map.get_neighbours(p.clone());
-    for n in neighbours {
-        if trail_map.get_pos_from_pos(&n) == (current_value + 1) {
+    for n in trail_map.neighbours(*p) {
+        if trail_map[n] == (current_value + 1) {
             heads.append(&mut get_score(&n, &trail_map));
         }
     }
@@ -159,10 +61,35 @@ fn get_score(p: &Position, trail_map: &TrailMap) -> Vec<Position> {
     return heads;
 }
 
+#[derive(Default)]
+pub struct Day10 {
+    trail_map: Grid<usize>,
+}
+
+impl Solution for Day10 {
+    fn day(&self) -> usize {
+        return 10;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.trail_map = parse_trail_map(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_scores(&self.trail_map, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_scores(&self.trail_map, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -171,29 +98,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 36);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 798);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 81);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1816);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,13 +1,29 @@
-const INPUTS_FOLDER: &str = "inputs/day_11";
+const DAY: usize = 11;
 
 use std::collections::HashMap;
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::debug;
+use crate::solution::{Answer, Solution};
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut stones: Vec<usize> = input_lines[0].split(" ").map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return Ok(count_stones(&parse_stones(&input_lines)?, part_2));
+}
+
+// The stones are all on the first line.
+fn parse_stones(input_lines: &[String]) -> Result<Vec<usize>, AocError> {
+    let first_line: &String = input_lines.first().ok_or(AocError::parse("expected a line of stones").at_line(1))?;
+    return first_line.split_whitespace().map(|x| error::parse_number::<usize>(x).map_err(|e| e.at_line(1))).collect::<Result<Vec<usize>, AocError>>();
+}
+
+fn count_stones(stones: &[usize], part_2: bool) -> usize {
     let mut new_stones: Vec<usize> = Vec::new();
 
     let blink_count: usize;
@@ -79,8 +95,8 @@ fn blink_length(stone: usize, blink_depth: usize, previous_stones: &mut HashMap<
 
 fn blink_stone(stone: usize, blink_depth: usize, previous_stones: &mut HashMap<usize, Vec<usize>>, previous_stones_by_5: &mut HashMap<usize, Vec<usize>>, stone_lengths: &mut HashMap<usize, HashMap<usize, usize>>) -> Vec<usize> {
     if blink_depth == 0 {
-        println!("returning a stone, previous_stones.len = {}", previous_stones.len());
-        println!("returning a stone, previous_stones_by_5.len = {}", previous_stones_by_5.len());
+        debug!("returning a stone, previous_stones.len = {}", previous_stones.len());
+        debug!("returning a stone, previous_stones_by_5.len = {}", previous_stones_by_5.len());
         // println!("previous_stones = {:?}", previous_stones);
         return vec![stone];
     }
@@ -129,10 +145,35 @@ fn blink_stone(stone: usize, blink_depth: usize, previous_stones: &mut HashMap<u
     return new_stones;
 }
 
+#[derive(Default)]
+pub struct Day11 {
+    stones: Vec<usize>,
+}
+
+impl Solution for Day11 {
+    fn day(&self) -> usize {
+        return 11;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.stones = parse_stones(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_stones(&self.stones, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_stones(&self.stones, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -141,29 +182,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 55312);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 194782);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 30);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 233007586663131);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
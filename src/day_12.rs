@@ -1,11 +1,14 @@
-const INPUTS_FOLDER: &str = "inputs/day_12";
+const DAY: usize = 12;
 
 use std::collections::{HashMap, HashSet};
 
 use itertools::{sorted, Itertools};
 
-use crate::generic;
-use crate::generic::{Direction, Position};
+use crate::generic::{Direction, Grid, Position};
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{Answer, Solution};
 
 
 #[derive(Debug)]
@@ -63,12 +66,12 @@ impl Region {
                 last_col = *col;
             }
             current_row_set.insert((last_col + 1, Direction::Up));
-            vertical_bars_p2 += last_row_set.difference(&current_row_set).into_iter().collect::<Vec<&(usize, Direction)>>().len();
-            println!("\tRow {}, vertical_bars_p2 = {}, current_row = {:?}", row, vertical_bars_p2, current_row_set);
+            vertical_bars_p2 += last_row_set.difference(&current_row_set).collect::<Vec<&(usize, Direction)>>().len();
+            trace!("\tRow {}, vertical_bars_p2 = {}, current_row = {:?}", row, vertical_bars_p2, current_row_set);
             last_row_set = current_row_set;
         }
         vertical_bars_p2 += last_row_set.len();
-        println!("\tRow end, vertical_bars_p2 = {}, last_row_set = {:?}", vertical_bars_p2, last_row_set);
+        trace!("\tRow end, vertical_bars_p2 = {}, last_row_set = {:?}", vertical_bars_p2, last_row_set);
 
         let mut last_row: usize = 0;
         let mut last_col_set: HashSet<(usize, Direction)> = HashSet::new();
@@ -89,12 +92,12 @@ impl Region {
                 last_row = *row;
             }
             current_col_set.insert((last_row + 1, Direction::Left));
-            horizontal_bars_p2 += last_col_set.difference(&current_col_set).into_iter().collect::<Vec<&(usize, Direction)>>().len();
-            println!("\tCol {}, horizontal_bars_p2 = {}, current_col = {:?}", col, horizontal_bars_p2, current_col_set);
+            horizontal_bars_p2 += last_col_set.difference(&current_col_set).collect::<Vec<&(usize, Direction)>>().len();
+            trace!("\tCol {}, horizontal_bars_p2 = {}, current_col = {:?}", col, horizontal_bars_p2, current_col_set);
             last_col_set = current_col_set;
         }
         horizontal_bars_p2 += last_col_set.len();
-        println!("\tCol end, horizontal_bars_p2 = {}, last_col_set = {:?}", horizontal_bars_p2, last_col_set);
+        trace!("\tCol end, horizontal_bars_p2 = {}, last_col_set = {:?}", horizontal_bars_p2, last_col_set);
         // println!("\tRegion {} has {} vertical and {} horizontal", self.character, vertical_bars, horizontal_bars);
 
         if !part_2 {
@@ -105,91 +108,34 @@ impl Region {
     }
 }
 
-struct GardenMap {
-    tiles: Vec<Vec<char>>,
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
 }
 
-impl From<Vec<String>> for GardenMap {
-    fn from(value: Vec<String>) -> Self {
-        let tiles: Vec<Vec<char>> = value.iter().map(|x| x.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
-        return Self{ tiles: tiles };
-    }
-}
-
-impl GardenMap {
-    fn get_row_count(&self) -> usize {
-        return self.tiles.len();
-    }
-
-    fn get_col_count(&self) -> usize {
-        return self.tiles[0].len();
-    }
-
-    fn get_pos(&self, row: usize, col: usize) -> char {
-        return self.tiles[row][col];
-    }
-
-    fn get_pos_from_pos(&self, p: &Position) -> char {
-        return self.tiles[p.row][p.col];
-    }
-
-    fn get_neighbours(&self, p: Position) -> Vec<Position> {
-        let mut row: i32 = p.row as i32;
-        let mut col: i32 = p.col as i32;
-        let mut neighbours: Vec<Position> = Vec::new();
-
-        for d in Direction::iter() {
-            match d {
-                Direction::Up => {
-                    if p.row > 0 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Down => {
-                    if p.row < self.get_row_count() - 1 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Left => {
-                    if p.col > 0 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-                Direction::Right => {
-                    if p.col < self.get_col_count() - 1 {
-                        neighbours.push(p.walk(1, d));
-                    }
-                },
-            };
-        }
-        return neighbours;
-    }
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    debug!("Creating garden map");
+    let garden_map: Grid<char> = Grid::parse(&input_lines)?;
+    return Ok(fence_price(&garden_map, part_2));
 }
 
-
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    println!("Creating garden map");
-    let garden_map: GardenMap = GardenMap::from(input_lines);
-    
-    println!("make used");
-    let mut used: Vec<Vec<bool>> = vec![vec![false; garden_map.get_col_count()]; garden_map.get_row_count()];
-    println!("make regions");
+fn fence_price(garden_map: &Grid<char>, part_2: bool) -> usize {
+    debug!("make used");
+    let mut used: Grid<bool> = Grid::new(garden_map.row_count(), garden_map.col_count(), false);
+    debug!("make regions");
     let mut regions: Vec<Region> = Vec::new();
 
-    for r in 0..garden_map.get_row_count() {
-        for c in 0..garden_map.get_col_count() {
-            if !used[r][c] {
-                // println!("Doing row {}, col{}", r, c);
-                regions.push(Region::new(garden_map.get_pos(r, c), get_region(Position{ row: r, col: c }, garden_map.tiles[r][c], &garden_map, &mut used)));
-            }
+    for p in garden_map.positions() {
+        if !used[p] {
+            // println!("Doing row {}, col{}", p.row, p.col);
+            regions.push(Region::new(garden_map[p], get_region(p, garden_map[p], garden_map, &mut used)));
         }
     }
 
     let mut sum_product: usize = 0;
     for r in regions.iter() {
-        println!("region {} has area {}", r.character, r.get_area());
-        println!("region {} has perimeter {}", r.character, r.get_perimeter(part_2));
+        trace!("region {} has area {}", r.character, r.get_area());
+        trace!("region {} has perimeter {}", r.character, r.get_perimeter(part_2));
         // println!("\t{:?}", r);
         sum_product += r.get_area() * r.get_perimeter(part_2);
         // break;
@@ -199,30 +145,55 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     return sum_product;
 }
 
-fn get_region(p: Position, target_char: char, map: &GardenMap, used: &mut Vec<Vec<bool>>) -> Vec<Position> {
+fn get_region(p: Position, target_char: char, map: &Grid<char>, used: &mut Grid<bool>) -> Vec<Position> {
     let mut return_vec: Vec<Position> = Vec::new();
-    let neighbours: Vec<Position> = map.get_neighbours(p);
+    let neighbours: Vec<Position> = map.neighbours(p).collect::<Vec<Position>>();
     // println!("Doing point {:?}, neighbours = {:?}", p, neighbours);
     if neighbours.len() == 0 {
         return return_vec;
     }
-    used[p.row][p.col] = true;
+    used[p] = true;
     return_vec.push(p);
 
-    for n in neighbours.iter().filter(|x| map.get_pos_from_pos(x) == target_char) {
+    for n in neighbours.iter().filter(|x| map[**x] == target_char) {
         // println!("\ttargeting neighbour {:?}", n);
-        if !used[n.row][n.col] {
-            return_vec.append(&mut get_region(n.clone(), target_char, map, used));
+        if !used[*n] {
+            return_vec.append(&mut get_region(*n, target_char, map, used));
         }
     }
 
     return return_vec;
 }
 
+#[derive(Default)]
+pub struct Day12 {
+    garden_map: Grid<char>,
+}
+
+impl Solution for Day12 {
+    fn day(&self) -> usize {
+        return 12;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.garden_map = Grid::parse(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(fence_price(&self.garden_map, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(fence_price(&self.garden_map, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -230,57 +201,43 @@ mod tests {
         let s1: HashSet<usize> = HashSet::from_iter([0, 2, 4, 6]);
         let s2: HashSet<usize> = HashSet::from_iter([0, 2, 5]);
 
-        println!("DIfference len = {:?}", s2.difference(&s1).into_iter().collect::<Vec<&usize>>().len());
+        println!("DIfference len = {:?}", s2.difference(&s1).collect::<Vec<&usize>>().len());
         
     }
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1930);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1370100);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1206);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn example_2_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 80);
+        answers::check(DAY, 2, "example_2", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn example_2_3() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_3.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 236);
+        answers::check(DAY, 2, "example_3", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn example_2_4() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_4.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 368);
+        answers::check(DAY, 2, "example_4", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 818286);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
         // 814074 too low
     }
 }
\ No newline at end of file
@@ -1,9 +1,12 @@
-const INPUTS_FOLDER: &str = "inputs/day_13";
+const DAY: usize = 13;
 
 use std::collections::HashMap;
 
-use crate::generic;
 use crate::generic::Point64;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::trace;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug)]
 struct Button {
@@ -12,11 +15,17 @@ struct Button {
     cost: usize,
 }
 
+fn get_coordinate(value: &str, word_index: usize, prefix: &str) -> Result<i32, AocError> {
+    let word: &str = value.split(" ").nth(word_index).ok_or(AocError::parse(format!("too few fields in {:?}", value)))?;
+    let number: &str = word.trim_end_matches(",").strip_prefix(prefix).ok_or(AocError::parse(format!("expected {:?} in {:?}", prefix, word)))?;
+    return error::parse_number::<i32>(number);
+}
+
 impl Button {
-    fn new(value: String, cost: usize) -> Self {
-        let x: i32 = value.split(" ").collect::<Vec<_>>()[2][1..].trim_end_matches(",").parse::<i32>().unwrap();
-        let y: i32 = value.split(" ").collect::<Vec<_>>()[3][1..].parse::<i32>().unwrap();
-        return Self{x: x, y: y, cost: cost};
+    fn new(value: &str, cost: usize) -> Result<Self, AocError> {
+        let x: i32 = get_coordinate(value, 2, "X+")?;
+        let y: i32 = get_coordinate(value, 3, "Y+")?;
+        return Ok(Self{x, y, cost});
     }
 }
 
@@ -27,18 +36,24 @@ struct Machine {
 }
 
 impl Machine {
-    fn new(value: &[String], part_2: bool) -> Self {
+    fn new(value: &[String], first_line: usize) -> Result<Self, AocError> {
         // println!("Building Machine from {:?}", value);
-        let prize_x: i32 = value[2].split(" ").collect::<Vec<&str>>()[1][2..].trim_end_matches(",").parse::<i32>().unwrap();
-        let prize_y: i32 = value[2].split(" ").collect::<Vec<&str>>()[2][2..].parse::<i32>().unwrap();
-        if !part_2 {
-            return Self { buttons: vec![Button::new(value[0].clone(), 3), Button::new(value[1].clone(), 1)], prize: Point64::new(prize_x as i64, prize_y as i64) };
-        } else {
-            return Self { buttons: vec![Button::new(value[0].clone(), 3), Button::new(value[1].clone(), 1)], prize: Point64::new(prize_x as i64 + 10000000000000, prize_y as i64 + 10000000000000) };
+        if value.len() < 3 {
+            return Err(AocError::parse("expected two buttons and a prize").at_line(first_line));
         }
+        let buttons: Vec<Button> = vec![
+            Button::new(&value[0], 3).map_err(|e| e.at_line(first_line))?,
+            Button::new(&value[1], 1).map_err(|e| e.at_line(first_line + 1))?,
+        ];
+        let prize_x: i32 = get_coordinate(&value[2], 1, "X=").map_err(|e| e.at_line(first_line + 2))?;
+        let prize_y: i32 = get_coordinate(&value[2], 2, "Y=").map_err(|e| e.at_line(first_line + 2))?;
+        return Ok(Self { buttons, prize: Point64::new(prize_x as i64, prize_y as i64) });
     }
 
-    fn get_cost(&self) -> usize {
+    fn get_cost(&self, part_2: bool) -> usize {
+        // Part 2 moves the prize a long way out along both axes.
+        let prize: Point64 = if !part_2 { self.prize } else { self.prize.walk(10000000000000, 10000000000000) };
+
         // prize_x = a * button[0].x + b * button[1].x
         // prize_y = a * button[0].y + b * button[1].y
         //
@@ -50,12 +65,12 @@ impl Machine {
         //       ((button[0].x * button[1].y) - (button[0].y * button[1].x))
         //
 
-        let b: f64 = ((self.buttons[0].x as f64 * self.prize.y as f64) - (self.buttons[0].y as f64 * self.prize.x as f64))
+        let b: f64 = ((self.buttons[0].x as f64 * prize.y as f64) - (self.buttons[0].y as f64 * prize.x as f64))
                 / ((self.buttons[0].x as f64 * self.buttons[1].y as f64) - (self.buttons[0].y as f64 * self.buttons[1].x as f64));
-        let a: f64 = (self.prize.x as f64 - (b * self.buttons[1].x as f64)) / self.buttons[0].x as f64;
+        let a: f64 = (prize.x as f64 - (b * self.buttons[1].x as f64)) / self.buttons[0].x as f64;
 
         if a.fract() == 0.0 && b.fract() == 0.0 {
-            println!("Get cost 3 :: a = {}, b = {}", a, b);
+            trace!("Get cost 3 :: a = {}, b = {}", a, b);
             return (a as usize * self.buttons[0].cost) + (b as usize * self.buttons[1].cost);
         } else {
             return 0;
@@ -99,28 +114,65 @@ impl Machine {
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let machines: Vec<Machine> = parse_machines(&input_lines)?;
+    return Ok(total_cost(&machines, part_2));
+}
+
+fn parse_machines(input_lines: &[String]) -> Result<Vec<Machine>, AocError> {
     let mut i: usize = 0;
     let mut machines: Vec<Machine> = Vec::new();
     while i < input_lines.len() {
-        machines.push(Machine::new(&input_lines[i..(i+3)], part_2));
+        machines.push(Machine::new(&input_lines[i..(i+3).min(input_lines.len())], i + 1)?);
         i += 4;
     }
+    return Ok(machines);
+}
 
+fn total_cost(machines: &[Machine], part_2: bool) -> usize {
     let mut sum_cost: usize = 0;
     for (i, m) in machines.iter().enumerate() {
-        println!("{:?}", m);
-        sum_cost += m.get_cost();
+        trace!("{:?}", m);
+        sum_cost += m.get_cost(part_2);
     }
 
     return sum_cost;
 }
 
+#[derive(Default)]
+pub struct Day13 {
+    machines: Vec<Machine>,
+}
+
+impl Solution for Day13 {
+    fn day(&self) -> usize {
+        return 13;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.machines = parse_machines(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_cost(&self.machines, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_cost(&self.machines, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -129,30 +181,22 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 480);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 28753);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
         // 16517 too low
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 875318608908);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 102718967795500);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,42 +1,43 @@
-const INPUTS_FOLDER: &str = "inputs/day_14";
+const DAY: usize = 14;
 
 use std::collections::HashSet;
+use std::str::FromStr;
 
-use crate::generic::{self, append_to_file};
+use crate::generic::append_to_file;
 use crate::generic::{Position, Direction};
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::{debug, info, trace};
+use crate::solution::{Answer, Solution};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Robot {
     start: Position,
-    dx: i32,
-    dy: i32,
+    dx: isize,
+    dy: isize,
 }
 
-impl From<&String> for Robot {
-    fn from(value: &String) -> Self {
-        let position: Vec<&str> = value.split(" ").collect::<Vec<&str>>()[0][2..].split(",").collect::<Vec<&str>>();
-        let velocity: Vec<&str> = value.split(" ").collect::<Vec<&str>>()[1][2..].split(",").collect::<Vec<&str>>();
+fn get_pair(value: &str, prefix: &str) -> Result<(String, String), AocError> {
+    let (x, y) = value.strip_prefix(prefix).and_then(|x| x.split_once(",")).ok_or(AocError::parse(format!("expected {}x,y, got {:?}", prefix, value)))?;
+    return Ok((x.to_string(), y.to_string()));
+}
+
+impl FromStr for Robot {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = value.split_once(" ").ok_or(AocError::parse(format!("expected a position and velocity, got {:?}", value)))?;
+        let position: (String, String) = get_pair(position, "p=")?;
+        let velocity: (String, String) = get_pair(velocity, "v=")?;
 
-        return Self { start: Position{row: position[1].parse::<usize>().unwrap(), col: position[0].parse::<usize>().unwrap()},
-            dx: velocity[0].parse::<i32>().unwrap(), dy: velocity[1].parse::<i32>().unwrap() };
+        return Ok(Self { start: Position{row: error::parse_number::<usize>(&position.1)?, col: error::parse_number::<usize>(&position.0)?},
+            dx: error::parse_number::<isize>(&velocity.0)?, dy: error::parse_number::<isize>(&velocity.1)? });
     }
 }
 
 impl Robot {
     fn walk(&self, steps: usize, max_width: usize, max_height: usize) -> Position {
-        // println!("Robot = {:?}", self);
-        // println!("Robot walking from {:?}", self.start);
-        let mut new_row = (self.start.row as i32 + (steps as i32 * self.dy)) % max_height as i32;
-        let mut new_col = (self.start.col as i32 + (steps as i32 * self.dx)) % max_width as i32;
-        if new_row < 0 {
-            new_row += max_height as i32;
-        }
-        if new_col < 0 {
-            new_col += max_width as i32;
-        }
-        // println!("new_row = {}", new_row);
-        // println!("new_col = {}", new_col);
-        return Position { row: new_row as usize, col: new_col as usize };
+        return self.start.wrapping_offset((steps as isize * self.dy, steps as isize * self.dx), max_height, max_width);
     }
 
     fn walk_modify(&mut self, steps: usize, max_width: usize, max_height: usize) {
@@ -44,11 +45,32 @@ impl Robot {
     }
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usize) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut robots: Vec<Robot> = input_lines.iter().map(|x| Robot::from(x)).collect::<Vec<Robot>>();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    let (width, height) = room_size(variant);
+    return solve_lines(input_lines, part_2, width, height);
+}
 
-    
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool, width: usize, height: usize) -> Result<usize, AocError> {
+    let robots: Vec<Robot> = error::parse_lines(&input_lines, |x| x.parse::<Robot>())?;
+    return watch_robots(&robots, part_2, width, height);
+}
+
+// The room each input variant's robots walk around, as (variant prefix, width, height). The first matching prefix wins.
+// example_3 is a hand-made room with a tree in it, the puzzle's own examples are 11x7.
+const ROOM_SIZES: [(&str, usize, usize); 2] = [("example_3", 103, 101), ("example", 11, 7)];
+const INPUT_ROOM_SIZE: (usize, usize) = (101, 103);
+
+// The tree is looked for within TREE_SEARCH columns of the middle, and its top reaches TREE_TOP_ROWS - 1 columns either side.
+const TREE_SEARCH: usize = 10;
+const TREE_TOP_ROWS: usize = 5;
+
+fn room_size(variant: &str) -> (usize, usize) {
+    return ROOM_SIZES.iter().find(|x| variant.starts_with(x.0)).map(|x| (x.1, x.2)).unwrap_or(INPUT_ROOM_SIZE);
+}
+
+fn watch_robots(robots: &[Robot], part_2: bool, width: usize, height: usize) -> Result<usize, AocError> {
+    let mut robots: Vec<Robot> = robots.to_vec();
     let mid_width: usize = (width / 2);
     let mid_height: usize = (height / 2);
     let mut blank_map: Vec<Vec<char>> = vec![vec!['.'; width]; height];
@@ -61,11 +83,11 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
         let mut q_bot_left: usize = 0;
         let mut q_bot_right: usize = 0;
 
-        println!("Mid with = {}, mid_height = {}", mid_width, mid_height);
+        debug!("Mid with = {}, mid_height = {}", mid_width, mid_height);
 
         for p in robot_end_positions {
             blank_map[p.row][p.col] = 'X';
-            println!("\tPoint = {:?}", p);
+            trace!("\tPoint = {:?}", p);
             if p.row < mid_height && p.col < mid_width {
                 // println!("\t\tAdd to q_top_left");
                 q_top_left += 1;
@@ -81,26 +103,27 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
             }
         }
 
-        println!("q_top_left = {}", q_top_left);
-        println!("q_top_right = {}", q_top_right);
-        println!("q_bot_left = {}", q_bot_left);
-        println!("q_bot_right = {}", q_bot_right);
+        debug!("q_top_left = {}", q_top_left);
+        debug!("q_top_right = {}", q_top_right);
+        debug!("q_bot_left = {}", q_bot_left);
+        debug!("q_bot_right = {}", q_bot_right);
 
         for r in blank_map {
-            for c in r {
-                print!("{}", c);
-            }
-            println!("");
+            debug!("{}", r.iter().collect::<String>());
         }
 
-        return q_top_left * q_top_right * q_bot_left * q_bot_right;
+        return Ok(q_top_left * q_top_right * q_bot_left * q_bot_right);
     } else {
+        if mid_width < TREE_SEARCH + TREE_TOP_ROWS - 1 || height < TREE_TOP_ROWS {
+            return Err(AocError::parse(format!("a {}x{} room is too small to look for the tree in", width, height)));
+        }
+
         let mut i: usize = 0;
         let mut found_tree: bool = false;
         while (!found_tree) {
             i += 1;
             if i % 1000 == 0 {
-                println!("looking for tree after {} seconds", i);
+                debug!("looking for tree after {} seconds", i);
             }
             robots.iter_mut().for_each(|x| x.walk_modify(1, width, height));
             let all_points: HashSet<Position> = HashSet::from_iter(robots.iter().map(|x| x.start));
@@ -113,7 +136,7 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
             // let average_d_row: f32 = robots.iter().map(|x| (x.start.row as i32 - average_row).pow(2)).sum::<i32>() as f32 / (robots.len() as f32);
             // let average_d_col: f32 = robots.iter().map(|x| (x.start.col as i32 - average_col).pow(2)).sum::<i32>() as f32 / (robots.len() as f32);
             // let output_string: String = format!("{},{},{},{},{}", i, average_row, average_col, average_d_row, average_d_col);
-            // append_to_file(INPUTS_FOLDER.to_owned() + "/output.txt", output_string);
+            // append_to_file(inputs::InputResolver::from_env().day_folder(DAY).join("output.txt").display().to_string(), output_string);
 
             // if all_points.len() == robots.len() {
             //     // all robots have individual spots.
@@ -133,7 +156,7 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
             //     break;
             // }
             // println!("{:?}", all_points);
-            let mid_col: HashSet<&Position> = HashSet::from_iter(all_points.iter().filter(|x| x.col > mid_width-10 && x.col < mid_width + 10).collect::<Vec<&Position>>());
+            let mid_col: HashSet<&Position> = HashSet::from_iter(all_points.iter().filter(|x| x.col > mid_width - TREE_SEARCH && x.col < mid_width + TREE_SEARCH).collect::<Vec<&Position>>());
             for x in mid_col {
                 let tree_top: HashSet<Position> = make_tree_top(x);
                 // println!("\ttree top = {:?}", tree_top);
@@ -143,14 +166,11 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
                     }
 
                     for r in blank_map.iter() {
-                        for c in r {
-                            print!("{}", c);
-                        }
-                        println!("");
+                        debug!("{}", r.iter().collect::<String>());
                     }
 
                     found_tree = true;
-                    println!("found tree at seconds = {}", i);
+                    info!("found tree at seconds = {}", i);
                     break;
                 }
             }
@@ -160,7 +180,7 @@ fn solve_puzzle(input_filename: String, part_2: bool, width: usize, height: usiz
             }
         }
 
-        return i;
+        return Ok(i);
     }
 }
 
@@ -175,14 +195,14 @@ fn write_map(all_points: &HashSet<Position>, width: usize, height: usize) {
         for c in r {
             row_string.push(*c);
         }
-        append_to_file(INPUTS_FOLDER.to_owned() + "/output2.txt", row_string);
+        append_to_file(inputs::InputResolver::from_env().day_folder(DAY).join("output2.txt").display().to_string(), row_string);
     }
 }
 
 fn make_tree_top(start: &Position) -> HashSet<Position> {
     let mut return_hash: HashSet<Position> = HashSet::new();
     return_hash.insert(*start);
-    for i in 1..5 {
+    for i in 1..TREE_TOP_ROWS {
         return_hash.insert(Position { row: start.row + i, col: start.col - i });
         return_hash.insert(Position { row: start.row + i, col: start.col + i });
     }
@@ -190,12 +210,52 @@ fn make_tree_top(start: &Position) -> HashSet<Position> {
     return return_hash;
 }
 
+pub struct Day14 {
+    robots: Vec<Robot>,
+    width: usize,
+    height: usize,
+}
+
+impl Default for Day14 {
+    fn default() -> Self {
+        return Self { robots: Vec::new(), width: INPUT_ROOM_SIZE.0, height: INPUT_ROOM_SIZE.1 };
+    }
+}
+
+impl Solution for Day14 {
+    fn day(&self) -> usize {
+        return 14;
+    }
+
+    fn set_variant(&mut self, variant: &str) {
+        (self.width, self.height) = room_size(variant);
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        let robots: Vec<Robot> = error::parse_lines(&input_lines, |x| x.parse::<Robot>())?;
+        if let Some(robot) = robots.iter().find(|x| x.start.col >= self.width || x.start.row >= self.height) {
+            return Err(AocError::parse(format!("robot at {},{} is outside the {}x{} room", robot.start.col, robot.start.row, self.width, self.height)));
+        }
+        self.robots = robots;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(watch_robots(&self.robots, false, self.width, self.height)?));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(watch_robots(&self.robots, true, self.width, self.height)?));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
 
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -215,31 +275,37 @@ mod tests {
         println!("{:?}", h1.intersection(&h2).collect::<Vec<&usize>>().len());
     }
 
+    #[test]
+    fn room_sizes() {
+        assert!(room_size("example_1") == (11, 7));
+        assert!(room_size("example_3") == (103, 101));
+        assert!(room_size("input") == (101, 103));
+
+        let mut day: Day14 = Day14::default();
+        day.set_variant("example_1");
+        day.parse(vec!["p=2,4 v=2,-3".to_string()]).unwrap();
+        assert!(day.part1().is_ok());
+        assert!(day.part2().is_err());
+        assert!(day.parse(vec!["p=11,0 v=1,1".to_string()]).is_err());
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false, 11, 7);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 12);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false, 101, 103);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 224438715);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_3.txt", true, 103, 101);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 30);
+        answers::check(DAY, 2, "example_3", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true, 101, 103);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 7603);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,7 +1,10 @@
-const INPUTS_FOLDER: &str = "inputs/day_15";
+const DAY: usize = 15;
 
-use crate::generic;
-use crate::generic::{Direction, Position};
+use crate::generic::{Direction, Grid, Position};
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{self, Answer, Solution};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Tile {
@@ -13,86 +16,64 @@ enum Tile {
     BoxRight,
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '#' => Self::Wall,
-            'O' => Self::Box,
-            '@' => Self::Robot,
-            _ => Self::Empty,
+            '#' => Ok(Self::Wall),
+            'O' => Ok(Self::Box),
+            '@' => Ok(Self::Robot),
+            '.' => Ok(Self::Empty),
+            _ => Err(AocError::parse(format!("bad warehouse tile {:?}", value))),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 struct TileMap {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
     robot: Position,
 }
 
 impl TileMap {
 
-    fn new(value: &[String], part_2: bool) -> Self {
-        let mut tiles: Vec<Vec<Tile>> = Vec::new();
-        let mut robot_pos: Position = Position { row: 0, col: 0 };
-        let mut char_count: usize;
-        if part_2 {
-            char_count = 2;
-        } else {
-            char_count = 1;
+    fn new(value: &[String]) -> Result<Self, AocError> {
+        let mut tiles: Grid<Tile> = Grid::parse(value)?;
+        // Boxes are pushed one step past where they are, so the walls have to stop them before the edge of the map.
+        let edge: Option<Position> = tiles.iter()
+            .find(|(p, x)| **x != Tile::Wall && (p.row == 0 || p.col == 0 || p.row + 1 == tiles.row_count() || p.col + 1 == tiles.col_count()))
+            .map(|(p, _)| p);
+        if let Some(p) = edge {
+            return Err(AocError::parse("the warehouse must be walled in").at_column(p.col + 1).at_line(p.row + 1));
         }
-
-        for i in 0..value.len() {
-            let mut tile_row: Vec<Tile> = Vec::new();
-            for (j, c) in value[i].chars().enumerate() {
-                match Tile::from(c) {
-                    Tile::Wall => tile_row.append(&mut vec![Tile::Wall; char_count]),
-                    Tile::Empty => tile_row.append(&mut vec![Tile::Empty; char_count]),
-                    Tile::Robot => {
-                        robot_pos = Position{row: i, col: j * char_count};
-                        tile_row.append(&mut vec![Tile::Empty; char_count]);
-                    },
-                    Tile::BoxLeft | Tile::BoxRight => (),
-                    Tile::Box => {
-                        if !part_2 {
-                            tile_row.push(Tile::Box);
-                        } else {
-                            tile_row.push(Tile::BoxLeft);
-                            tile_row.push(Tile::BoxRight);
-                        }
-                    },
-                }
-            }
-            tiles.push(tile_row);
+        let robots: Vec<Position> = tiles.find_all(&Tile::Robot);
+        if robots.len() > 1 {
+            return Err(AocError::parse("more than one robot (@) on the map").at_column(robots[1].col + 1).at_line(robots[1].row + 1));
         }
-
-
-        return Self { tiles: tiles, robot: robot_pos, };
-    }
-
-    fn get_tile(&self, row: usize, col: usize) -> Tile {
-        return self.tiles[row][col];
-    }
-
-    fn set_tile(&mut self, row: usize, col: usize, value: Tile) {
-        self.tiles[row][col] = value;
+        let robot: Position = tiles.find(&Tile::Robot).ok_or(AocError::parse("no robot (@) on the map"))?;
+        tiles[robot] = Tile::Empty;
+        return Ok(Self { tiles, robot });
     }
 
-    fn get_tile_from_position(&self, p: Position) -> Tile {
-        return self.tiles[p.row][p.col];
-    }
-
-    fn set_tile_from_position(&mut self, p: Position, value: Tile) {
-        self.tiles[p.row][p.col] = value;
+    // Part 2's warehouse, where everything but the robot is twice as wide.
+    fn widen(&self) -> Result<Self, AocError> {
+        let tiles: Vec<Vec<Tile>> = self.tiles.rows()
+            .map(|row| row.iter().flat_map(|x| match x {
+                Tile::Box => [Tile::BoxLeft, Tile::BoxRight],
+                other => [*other, *other],
+            }).collect::<Vec<Tile>>())
+            .collect::<Vec<Vec<Tile>>>();
+        return Ok(Self { tiles: Grid::from_rows(tiles)?, robot: Position { row: self.robot.row, col: self.robot.col * 2 } });
     }
 
     fn swap_tiles(&mut self, p1: Position, p2: Position) {
-        println!("\tSwapping {:?} with {:?}", p1, p2);
-        println!("\tp1 = {:?}, p2 = {:?}", self.get_tile_from_position(p1), self.get_tile_from_position(p2));
-        let temp: Tile = self.get_tile_from_position(p2);
-        println!("\ttemp = {:?}", temp);
-        self.set_tile_from_position(p2, self.get_tile_from_position(p1));
-        self.set_tile_from_position(p1, temp);
+        trace!("\tSwapping {:?} with {:?}", p1, p2);
+        trace!("\tp1 = {:?}, p2 = {:?}", self.tiles[p1], self.tiles[p2]);
+        let temp: Tile = self.tiles[p2];
+        trace!("\ttemp = {:?}", temp);
+        self.tiles[p2] = self.tiles[p1];
+        self.tiles[p1] = temp;
     }
 
     fn move_robot(&mut self, direction: Direction) {
@@ -103,7 +84,7 @@ impl TileMap {
                 Tile::Wall => (),
                 Tile::Empty | Tile::Robot => self.robot = self.robot.walk(1, direction),
                 Tile::Box => {
-                    let path: Vec<Tile> = self.get_path_to_wall(self.robot.row, self.robot.col, &direction);
+                    let path: Vec<Tile> = self.get_path_to_wall(self.robot, direction);
                     if path.contains(&Tile::Empty) {
                         let distance_to_tile: usize = path.iter().position(|x| *x == Tile::Empty).unwrap();
                         let target_position: Position = self.robot.walk(distance_to_tile + 1, direction);
@@ -113,7 +94,7 @@ impl TileMap {
                 },
                 Tile::BoxLeft | Tile::BoxRight => {
                     if direction == Direction::Left || direction == Direction::Right {
-                        let path: Vec<Tile> = self.get_path_to_wall(self.robot.row, self.robot.col, &direction);
+                        let path: Vec<Tile> = self.get_path_to_wall(self.robot, direction);
                         if path.contains(&Tile::Empty) {
                             let distance_to_tile: usize = path.iter().position(|x| *x == Tile::Empty).unwrap();
                             let mut target_position: Position = self.robot.walk(distance_to_tile + 1, direction);
@@ -137,7 +118,7 @@ impl TileMap {
 
     fn push_box(&mut self, position: Position, d: Direction) {
         let mut push_positions: Vec<Position> = vec![position];
-        match self.get_tile_from_position(position) {
+        match self.tiles[position] {
             Tile::BoxLeft => {
                 push_positions.push(position.walk(1, Direction::Right));
             },
@@ -145,28 +126,28 @@ impl TileMap {
                 
                 push_positions.push(position.walk(1, Direction::Left));
             },
-            _ => panic!("Bad tile input"),
+            _ => unreachable!("only the halves of a wide box are pushed"),
         }
 
         let mut possible_to_push: Vec<bool> = Vec::new();
         for p in push_positions {
             let next_position: Position = p.walk(1, d);
-            match self.get_tile_from_position(next_position) {
+            match self.tiles[next_position] {
                 Tile::Empty => self.swap_tiles(p, next_position),
                 Tile::BoxLeft | Tile::BoxRight => {
                     self.push_box(next_position, d);
                     self.swap_tiles(p, next_position);
                 },
-                _ => panic!("Bad tile type"),
+                _ => unreachable!("check_push has already ruled out walls, and the wide map has no whole boxes"),
             }
         }
     }
 
     fn check_push(&self, position: Position, d: Direction) -> bool {
-        println!("\tCheck push on p = {:?}", position);
+        trace!("\tCheck push on p = {:?}", position);
         let mut push_positions: Vec<Position> = vec![position];
-        println!("{:?}", self.get_tile_from_position(position));
-        match self.get_tile_from_position(position) {
+        trace!("{:?}", self.tiles[position]);
+        match self.tiles[position] {
             Tile::BoxLeft => {
                 push_positions.push(position.walk(1, Direction::Right));
             },
@@ -174,17 +155,17 @@ impl TileMap {
                 
                 push_positions.push(position.walk(1, Direction::Left));
             },
-            _ => panic!("Bad tile input"),
+            _ => unreachable!("only the halves of a wide box are pushed"),
         }
         
         let mut possible_to_push: Vec<bool> = Vec::new();
         for p in push_positions {
             let next_position: Position = p.walk(1, d);
-            match self.get_tile_from_position(next_position) {
+            match self.tiles[next_position] {
                 Tile::Wall => return false,
                 Tile::BoxLeft | Tile::BoxRight => possible_to_push.push(self.check_push(next_position, d)),
                 Tile::Empty => possible_to_push.push(true),
-                _ => panic!("Bad tile type"),
+                _ => unreachable!("the wide map has no whole boxes, and the robot is not kept on it"),
             }
         }
 
@@ -192,46 +173,22 @@ impl TileMap {
     }
 
     fn get_neighbour(&self, p: Position, d: Direction) -> Option<(Position, Tile)> {
-        let mut new_row: i32 = p.row as i32;
-        let mut new_col: i32 = p.col as i32;
-        match d {
-            Direction::Up => new_row -= 1,
-            Direction::Down => new_row += 1,
-            Direction::Left => new_col -= 1,
-            Direction::Right => new_col += 1,
-        }
-
-        if new_row < 0 || new_col < 0 {
-            return None;
-        } else {
-            return Some((Position{row: new_row as usize, col: new_col as usize}, self.get_tile(new_row as usize, new_col as usize)));
-        }
+        let neighbour: Position = self.tiles.offset(p, d.delta())?;
+        return Some((neighbour, self.tiles[neighbour]));
     }
 
-    fn get_path_to_wall(&self, row: usize, col: usize, direction: &Direction) -> Vec<Tile> {
-        let mut path: Vec<Tile>;
-        path = match direction {
-            Direction::Up => self.tiles[..row].iter().map(|x| x[col].clone()).collect::<Vec<Tile>>(),
-            Direction::Down => self.tiles[(row+1)..].iter().map(|x| x[col].clone()).collect::<Vec<Tile>>(),
-            Direction::Left => self.tiles[row][..col].to_vec(),
-            Direction::Right => self.tiles[row][(col+1)..].to_vec(),
-        };
-
-        if *direction == Direction::Up || *direction == Direction::Left {
-            path.reverse();
-        }
-
-        return path[..path.iter().position(|x| *x == Tile::Wall).unwrap()].iter().copied().collect::<Vec<Tile>>();
+    fn get_path_to_wall(&self, p: Position, direction: Direction) -> Vec<Tile> {
+        return self.tiles.line(p, direction.delta()).skip(1).map(|x| *x.1).take_while(|x| *x != Tile::Wall).collect::<Vec<Tile>>();
     }
 
     fn print_map(&self) {
-        for r in 0..self.tiles.len() {
+        for (r, row) in self.tiles.rows().enumerate() {
             let mut row_string: String = String::new();
-            for c in 0..self.tiles[0].len() {
+            for (c, tile) in row.iter().enumerate() {
                 if r == self.robot.row && c == self.robot.col {
                     row_string.push('@');
                 } else {
-                    match self.get_tile(r, c) {
+                    match tile {
                         Tile::Wall => row_string.push('#'),
                         Tile::Box => row_string.push('O'),
                         Tile::Robot | Tile::Empty => row_string.push('.'),
@@ -240,98 +197,132 @@ impl TileMap {
                     }
                 }
             }
-            println!("{}", row_string);
+            debug!("{}", row_string);
         }
     }
 
     fn get_gps(&self) -> usize {
-        let mut gps_sum: usize = 0;
-        for r in 0..self.tiles.len() {
-            for c in 0..self.tiles[0].len() {
-                if self.get_tile(r, c) == Tile::Box || self.get_tile(r, c) == Tile::BoxLeft {
-                    gps_sum += (100*r) + c;
-                }
-            }
-        }
-
-        return gps_sum;
+        return self.tiles.iter()
+            .filter(|(_, x)| **x == Tile::Box || **x == Tile::BoxLeft)
+            .map(|(p, _)| (100 * p.row) + p.col)
+            .sum::<usize>();
     }
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let blank_line_index: usize = input_lines.iter().position(|x| x == "").unwrap();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let (tile_map, directions) = parse_warehouse(&input_lines)?;
+    return gps_after_moves(&tile_map, &directions, part_2);
+}
+
+fn parse_warehouse(input_lines: &[String]) -> Result<(TileMap, Vec<Direction>), AocError> {
+    let blank_line_index: usize = input_lines.iter().position(|x| x.is_empty()).ok_or(AocError::parse("expected a blank line between the map and the moves"))?;
 
-    let mut tile_map: TileMap = TileMap::new(&input_lines[..blank_line_index], part_2);
+    let tile_map: TileMap = TileMap::new(&input_lines[..blank_line_index])?;
 
     let mut directions: Vec<Direction> = Vec::new();
     for i in (blank_line_index + 1)..input_lines.len() {
-        directions.append(&mut input_lines[i].chars().map(|x| Direction::from(x)).collect::<Vec<Direction>>());
+        for (j, c) in input_lines[i].chars().enumerate() {
+            directions.push(Direction::try_from(c).map_err(|e| e.at_column(j + 1).at_line(i + 1))?);
+        }
     }
+    return Ok((tile_map, directions));
+}
 
+fn gps_after_moves(tile_map: &TileMap, directions: &[Direction], part_2: bool) -> Result<usize, AocError> {
+    let mut tile_map: TileMap = if !part_2 { tile_map.clone() } else { tile_map.widen()? };
     for d in directions.iter() {
-        println!("Moving in direction {:?}", d);
+        trace!("Moving in direction {:?}", d);
         tile_map.move_robot(*d);
         // tile_map.print_map();
-        println!("Done");
+        trace!("Done");
         // break;
     }
 
 
     
-    return tile_map.get_gps();
+    return Ok(tile_map.get_gps());
+}
+
+#[derive(Default)]
+pub struct Day15 {
+    tile_map: Option<TileMap>,
+    directions: Vec<Direction>,
+}
+
+impl Solution for Day15 {
+    fn day(&self) -> usize {
+        return 15;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        let (tile_map, directions) = parse_warehouse(&input_lines)?;
+        self.tile_map = Some(tile_map);
+        self.directions = directions;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(gps_after_moves(solution::parsed(&self.tile_map, DAY)?, &self.directions, false)?));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(gps_after_moves(solution::parsed(&self.tile_map, DAY)?, &self.directions, true)?));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
         // Do a quick test here
     }
 
+    #[test]
+    fn bad_warehouse() {
+        let to_lines = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        assert!(TileMap::new(&to_lines(&["####", "#@x#", "####"])).unwrap_err().to_string() == "line 2, column 3: bad warehouse tile 'x'");
+        assert!(TileMap::new(&to_lines(&["####", "#@O.", "####"])).unwrap_err().to_string() == "line 2, column 4: the warehouse must be walled in");
+        assert!(TileMap::new(&to_lines(&["####", "#@@#", "####"])).unwrap_err().to_string() == "line 2, column 3: more than one robot (@) on the map");
+        assert!(TileMap::new(&to_lines(&["####", "#..#", "####"])).unwrap_err().to_string() == "no robot (@) on the map");
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 10092);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2028);
+        answers::check(DAY, 1, "example_2", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1456590);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 9021);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn example_2_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_3.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 618);
+        answers::check(DAY, 2, "example_3", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1489116);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
-const INPUTS_FOLDER: &str = "inputs/day_16";
+const DAY: usize = 16;
 
-use std::{collections::{HashMap, HashSet}};
+use std::collections::HashSet;
 
-use itertools::all;
-
-use crate::generic::{self, Direction, Position};
+use crate::generic::{Direction, Grid, Position};
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::debug;
+use crate::search::{self, SearchResult};
+use crate::solution::{self, Answer, Solution};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum Tile {
@@ -14,408 +17,171 @@ enum Tile {
     End
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '#' => Self::Wall,
-            '.' => Self::Empty,
-            'S' => Self::Start,
-            'E' => Self::End,
-            _ => panic!("Bad char given to tile from"),
+            '#' => Ok(Self::Wall),
+            '.' => Ok(Self::Empty),
+            'S' => Ok(Self::Start),
+            'E' => Ok(Self::End),
+            _ => Err(AocError::parse(format!("bad maze tile {:?}", value))),
         }
     }
 }
 
+// What a step forward and a 90 degree turn cost. The puzzle uses 1 and 1000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    pub step: usize,
+    pub turn: usize,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        return Self { step: 1, turn: 1000 };
+    }
+}
+
+// Where the reindeer is and which way it faces, since the cost of the next move depends on both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    position: Position,
+    direction: Direction,
+}
+
+#[derive(Debug, Clone)]
+pub struct MazeSolution {
+    pub score: usize,
+    // Every tile on at least one of the best paths, start and end included.
+    pub tiles: HashSet<Position>,
+}
+
 #[derive(Debug, Clone)]
 struct TileMap {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
     start: Position,
     end: Position,
 }
 
 impl TileMap {
-    fn new(value: Vec<String>) -> Self {
-        let mut tiles = value.iter().map(|x| x.chars().map(|y| Tile::from(y)).collect::<Vec<Tile>>()).collect::<Vec<Vec<Tile>>>();
-        let mut start: Position = Position { row: 0, col: 0 };
-        let mut end: Position = Position { row: 0, col: 0 };
-        let mut found_start: bool = false;
-        let mut found_end: bool = false;
-        for r in 0..tiles.len() {
-            for c in 0..tiles[0].len() {
-                if !found_start && tiles[r][c] == Tile::Start {
-                    start = Position{ row: r, col: c };
-                    tiles[r][c] = Tile::Empty;
-                    found_start = true;
-                } else if !found_end && tiles[r][c] == Tile::End {
-                    end = Position{ row: r, col: c };
-                    tiles[r][c] = Tile::Empty;
-                    found_end = true;
-                }
-                if found_start && found_end {
-                    break;
-                }
-            }
-            if found_start && found_end {
-                break;
-            }
-        }
-        if !found_start || !found_end {
-            panic!("Could not find start or end on map!");
-        }
-        return Self { tiles: tiles, start: start, end: end };
+    fn new(value: Vec<String>) -> Result<Self, AocError> {
+        let mut tiles: Grid<Tile> = Grid::parse(&value)?;
+        let start: Position = tiles.find(&Tile::Start).ok_or(AocError::parse("no start tile (S) on the map"))?;
+        let end: Position = tiles.find(&Tile::End).ok_or(AocError::parse("no end tile (E) on the map"))?;
+        tiles[start] = Tile::Empty;
+        tiles[end] = Tile::Empty;
+        return Ok(Self { tiles, start, end });
     }
 
-    fn get_tile(&self, p: Position) -> Tile {
-        return self.tiles[p.row][p.col];
+    fn is_open(&self, p: Position) -> bool {
+        return self.tiles.get(p).is_some_and(|x| *x != Tile::Wall);
     }
 
-    fn set_tile(&mut self, p: Position, value: Tile) {
-        self.tiles[p.row][p.col] = value;
-    }
-
-    fn get_path_cost(&self, position: Position, direction: Direction, tail: Option<HashSet<Position>>, visited: &mut HashMap<Position, Option<usize>>, dead_ends: &mut HashSet<Position>, depth: usize) -> Option<usize> {
-        if dead_ends.contains(&position) {
-            return None;
-        }
-        
-        let mut current_tail: HashSet<Position> = HashSet::new();
-        if tail.is_some() {
-            current_tail.extend(tail.unwrap());
+    // Step forward if there is no wall, or turn on the spot either way.
+    fn moves(&self, state: &State, costs: Costs) -> Vec<(State, usize)> {
+        let mut moves: Vec<(State, usize)> = vec![
+            (State { position: state.position, direction: state.direction.rotate_90_cw() }, costs.turn),
+            (State { position: state.position, direction: state.direction.rotate_90_ccw() }, costs.turn),
+        ];
+        if let Some(next) = self.tiles.offset(state.position, state.direction.delta()).filter(|x| self.is_open(*x)) {
+            moves.push((State { position: next, direction: state.direction }, costs.step));
         }
-
-        if current_tail.contains(&position) {
-            return None;
-        } else {
-            current_tail.insert(position);
-        }
-        // println!("{}Checking {:?} @ {:?}", " ".repeat(depth), position, direction);
-        let mut neighbour_path_costs: Vec<usize> = Vec::new();
-        let mut neighbours: Vec<Position> = Vec::new();
-        for d in Direction::iter() {
-            if d != direction.reverse() {
-                let neighbour: Position = position.walk(1, d);
-                if neighbour == self.end {
-                    println!("Found the end!");
-                    if d == direction {
-                        visited.insert(position, Some(1));
-                        return Some(1);
-                    } else {
-                        visited.insert(position, Some(1001));
-                        return Some(1001);
-                    }
-                }
-                if self.get_tile(neighbour) == Tile::Empty {
-                    neighbours.push(neighbour);
-                    // println!("{}Checking neighbour{:?} @ {:?}", " ".repeat(depth), neighbour, direction);
-                    let neighbour_cost: Option<usize> = self.get_path_cost(neighbour, d, Some(current_tail.clone()), visited, dead_ends, depth + 1);
-                    if neighbour_cost.is_some() {
-                        if d == direction {
-                            neighbour_path_costs.push(1 + neighbour_cost.unwrap());
-                        } else {
-                            neighbour_path_costs.push(1 + 1000 + neighbour_cost.unwrap());
-                        }
-                    }
-                }
-            }
-        }
-        if neighbour_path_costs.len() == 0 {
-            let mut all_dead_ends: bool = true;
-            for n in neighbours {
-                all_dead_ends = all_dead_ends && dead_ends.contains(&n);
-            }
-            if all_dead_ends {
-                println!("Adding {:?} as dead end", position);
-                dead_ends.insert(position);
-            }
-            visited.insert(position, None);
-            // println!("We're at position {:?}", position);
-            return None;
-        }
-
-        let min_cost: Option<usize> = Some(neighbour_path_costs.iter().copied().min().unwrap());
-        visited.insert(position, min_cost);
-        return min_cost;
+        return moves;
     }
 
-    fn djikstra(&self, position: Position, direction: Direction, done_set: &mut HashSet<Position>, distances: &mut HashMap<Position, Position>, neighbours_to_do: &mut Vec<(Position, Direction)>) {
-        // println!("Running djikstra on {:?}", position);
-        if done_set.contains(&position) {
-            return;
-        } else {
-            done_set.insert(position);
-        }
-        let current_distance_to_end_option: Option<usize> = self.get_distance_recursive(position, &mut Vec::new(), distances);
-        if current_distance_to_end_option.is_none() {
-            return;
-        }
-        let current_distance_to_end: usize = current_distance_to_end_option.unwrap();
-        // if current_distance_to_end > 150000 {
-        //     return;
-        // }
-        // println!("current distance to end = {}", current_distance_to_end);
-
-        for (neighbour, neighbour_direction) in self.get_neighbours(position) {
-            // println!("\tCheck neighbour {:?}, d = {:?}, direction = {:?}", neighbour, 0, neighbour_direction);
-            if !distances.contains_key(&neighbour) {
-                // println!("\t\tSet neighbour {:?} with distance {}", neighbour, 0 + current_distance_to_end);
-                distances.insert(neighbour, position);
-            } else {
-                let neighbour_distance: usize;
-                if direction.reverse() == neighbour_direction {
-                    neighbour_distance = 1;
-                } else {
-                    neighbour_distance = 1001;
-                }
-                let neighbour_distance_to_end_option: Option<usize> = self.get_distance_recursive(neighbour, &mut Vec::new(), distances);
-                
-                // println!("\t\tAlready contains neighbour at distance {}", neighbour_distance_to_end);
-                if neighbour_distance_to_end_option.is_none() || neighbour_distance_to_end_option.unwrap() > (current_distance_to_end + neighbour_distance) {
-                    // println!("\t\t\tReplacing neighbour distance to end. with {}", current_distance_to_end + neighbour_distance);
-                    distances.insert(neighbour, position);
-                    neighbours_to_do.push((neighbour, neighbour_direction));
-                    if done_set.contains(&neighbour) {
-                        done_set.remove(&neighbour);
-                    }
-                } else {
-                    // println!("\t\t\tKeeping neighbour distance to end. ignoring {}", current_distance_to_end + neighbour_distance);
-                }
-            }
-
-            if !done_set.contains(&neighbour) {
-                neighbours_to_do.push((neighbour, neighbour_direction));
-            }
-        }
+    // The reindeer starts facing east and can reach the end facing any way.
+    fn solve(&self, costs: Costs) -> Option<MazeSolution> {
+        let result: SearchResult<State> = search::dijkstra([State { position: self.start, direction: Direction::Right }], |x| self.moves(x, costs));
+        let end_states: Vec<(State, usize)> = Direction::iter()
+            .map(|d| State { position: self.end, direction: d })
+            .filter_map(|x| result.distance(&x).map(|d| (x, d)))
+            .collect::<Vec<(State, usize)>>();
+        let score: usize = end_states.iter().map(|x| x.1).min()?;
+        let best_ends = end_states.iter().filter(|x| x.1 == score).map(|x| x.0);
+        let tiles: HashSet<Position> = result.on_shortest_paths(best_ends).iter().map(|x| x.position).collect::<HashSet<Position>>();
+        return Some(MazeSolution { score, tiles });
     }
 
-    fn get_distance_recursive(&self, position: Position, direction_list: &mut Vec<Direction>, distances: &mut HashMap<Position, Position>) -> Option<usize> {
-        if position == self.end {
-            return Some(0);
-        }
-        if !distances.contains_key(&position) {
-            return None
-        }
-        let next_point: Position = *distances.get(&position).unwrap();
-        let next_direction: Direction = position.direction(next_point);
-        direction_list.push(next_direction);
-        // println!("\tFrom {:?} to {:?}", position, next_point);
-        if next_point == self.end {
-            let mut last_direction: Direction = direction_list.remove(0);
-            let mut distance = 1;
-            for d in direction_list {
-                if *d == last_direction {
-                    distance += 1;
-                } else {
-                    distance += 1001;
-                    last_direction = *d;
+    // The map with the given tiles drawn as O.
+    fn render(&self, path: &HashSet<Position>) -> Vec<String> {
+        return self.tiles.rows().enumerate()
+            .map(|(r, row)| row.iter().enumerate().map(|(c, tile)| {
+                let p: Position = Position { row: r, col: c };
+                if p == self.start {
+                    return 'S';
+                } else if p == self.end {
+                    return 'E';
+                } else if path.contains(&p) {
+                    return 'O';
+                } else if *tile == Tile::Wall {
+                    return '#';
                 }
-            }
-            return Some(distance);
-        }
-        return self.get_distance_recursive(next_point, direction_list, distances);
-    }
-
-    fn get_path(&self, position: Position, distances: &HashMap<Position, Position>) -> Vec<Position> {
-        if position == self.end {
-            return vec![self.end];
-        }
-        let mut positions: Vec<Position> = vec![position];
-        let next_point: Position = *distances.get(&position).unwrap();
-        positions.append(&mut self.get_path(next_point, distances));
-        return positions;
-    }
-
-    fn get_ditance_to_end(&self, position: Position, distances: &mut HashMap<Position, Position>, debug: bool) -> usize {
-        if position == self.start {
-            return 0;
-        }
-        if debug {
-            println!("Checking distance to end for {:?}", position);
-            // println!("distances = {:?}", distances);
-        }
-        let mut last_point: Position = position.clone();
-        let mut next_point: Position = *distances.get(&last_point).unwrap();
-        let mut last_direction: Direction = last_point.direction(next_point);
-        let mut total_distance: usize = 1;
-        if next_point == self.start {
-
-        }
-        while next_point != self.start {
-            if debug {
-                println!("\tnext = {:?}, total_distance = {}", next_point, total_distance);
-            }
-            let direction = next_point.direction(last_point);
-            if direction == last_direction {
-                total_distance += 1;
-            } else {
-                total_distance += 1001;
-            }
-            last_direction = direction;
-            last_point = next_point;
-            next_point = *distances.get(&next_point).unwrap();
-        }
-
-        return total_distance;
+                return '.';
+            }).collect::<String>())
+            .collect::<Vec<String>>();
     }
+}
 
-    fn get_neighbours(&self, position: Position) -> Vec<(Position, Direction)> {
-        let mut neighbours: Vec<(Position, Direction)> = Vec::new();
-        for d in Direction::iter() {
-            let neighbour = position.walk(1, d);
-            match self.get_tile(neighbour) {
-                Tile::Empty => {
-                    neighbours.push((position.walk(1, d), d));
-                },
-                _ => (),
-            }
-        }
-        return neighbours;
-    }
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
-    fn col_count(&self) -> usize {
-        return self.tiles[0].len();
-    }
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return best_paths(&TileMap::new(input_lines)?, part_2);
+}
 
-    fn row_count(&self) -> usize {
-        return self.tiles.len();
+fn best_paths(tile_map: &TileMap, part_2: bool) -> Result<usize, AocError> {
+    let solution: MazeSolution = solve_tile_map(tile_map, Costs::default())?;
+    if !part_2 {
+        return Ok(solution.score);
+    } else {
+        return Ok(solution.tiles.len());
     }
+}
 
-    fn print_map(&self) {
-        for r in 0..self.row_count() {
-            let mut row_string: String = String::new();
-            for c in 0..self.col_count() {
-                if r == self.start.row && c == self.start.col {
-                    row_string.push('S');
-                } else if r == self.end.row && c == self.end.col {
-                    row_string.push('E');
-                } else {
-                    match self.get_tile(Position { row: r, col: c }) {
-                        Tile::Wall => row_string.push('#'),
-                        _ => row_string.push('.'),
-                    }
-                }
-            }
-            println!("{:?}", row_string);
-        }
-    }
+pub fn solve_maze(input_lines: Vec<String>, costs: Costs) -> Result<MazeSolution, AocError> {
+    return solve_tile_map(&TileMap::new(input_lines)?, costs);
+}
 
-    fn get_empty_points(&self) -> HashSet<Position> {
-        let mut empty_tiles: HashSet<Position> = HashSet::new();
-        for r in 0..self.row_count() {
-            for c in 0..self.col_count() {
-                let p = Position { row: r, col: c };
-                if self.get_tile(p) == Tile::Empty {
-                    empty_tiles.insert(p);
-                }
-            }
-        }
-        return empty_tiles;
+fn solve_tile_map(tile_map: &TileMap, costs: Costs) -> Result<MazeSolution, AocError> {
+    let solution: MazeSolution = tile_map.solve(costs).ok_or(AocError::parse("no path from the start to the end"))?;
+    for row in tile_map.render(&solution.tiles) {
+        debug!("{}", row);
     }
+    return Ok(solution);
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut tile_map: TileMap = TileMap::new(input_lines);
-    let mut distances: HashMap<Position, Position> = HashMap::new();
-    let mut done_set: HashSet<Position> = HashSet::new();
-    let mut neighbours_to_do: Vec<(Position, Direction)> = vec![(tile_map.end, Direction::Right)];
+// The map with every tile on a best path drawn as O.
+pub fn render_best_paths(input_lines: Vec<String>, costs: Costs) -> Result<Vec<String>, AocError> {
+    let tile_map: TileMap = TileMap::new(input_lines)?;
+    let solution: MazeSolution = tile_map.solve(costs).ok_or(AocError::parse("no path from the start to the end"))?;
+    return Ok(tile_map.render(&solution.tiles));
+}
 
-    tile_map.print_map();
-    // tile_map.set_tile(Position { row: 138, col: 7 }, Tile::Wall);
+#[derive(Default)]
+pub struct Day16 {
+    tile_map: Option<TileMap>,
+}
 
-    while neighbours_to_do.len() > 0 {
-        let (next_neighbour, next_neighbour_direction): (Position, Direction) = neighbours_to_do.remove(0);
-        tile_map.djikstra(next_neighbour, next_neighbour_direction, &mut done_set, &mut distances, &mut neighbours_to_do);
+impl Solution for Day16 {
+    fn day(&self) -> usize {
+        return 16;
     }
 
-    for i in 0..2 {
-        done_set = HashSet::new();
-        neighbours_to_do = vec![(tile_map.start, Direction::Right)];
-        while neighbours_to_do.len() > 0 {
-            let (next_neighbour, next_neighbour_direction): (Position, Direction) = neighbours_to_do.remove(0);
-            tile_map.djikstra(next_neighbour, next_neighbour_direction, &mut done_set, &mut distances, &mut neighbours_to_do);
-        }
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.tile_map = Some(TileMap::new(input_lines)?);
+        return Ok(());
     }
 
-    let best_path_length: usize = tile_map.get_distance_recursive(tile_map.start, &mut vec![Direction::Right], &mut distances).unwrap() - 1;
-
-    // with blockages
-    let all_path_points: Vec<Position> = tile_map.get_path(tile_map.start, &distances);
-    let all_path_points_hash: HashSet<Position> = HashSet::from_iter(all_path_points.clone());
-    let intersections: Vec<&Position> = all_path_points.iter().filter(|x| tile_map.get_neighbours(**x).len() > 2).collect::<Vec<&Position>>();
-
-    let mut unique_points: HashSet<Position> = HashSet::from_iter(all_path_points.clone());
-
-    let mut j: usize = 0;
-    for (k, intersection) in intersections.iter().enumerate() {
-        let intersection_index: usize = all_path_points.iter().position(|x| x == *intersection).unwrap();
-        let neighbours: Vec<(Position, Direction)> = tile_map.get_neighbours(**intersection);
-        // let mut block_sites: Vec<Position> = neighbours.clone();
-        let mut block_sites: Vec<Position> = vec![all_path_points[intersection_index + 1]];
-        // println!("Block sites for {:?} are {:?}", intersection, block_sites);
-        let mut new_tile_map: TileMap = tile_map.clone();
-        let mut block_index: usize = 0;
-        while block_index < block_sites.len() {
-            let mut b: Position = block_sites[block_index];
-            new_tile_map.set_tile(b, Tile::Wall);
-
-            // tile_map.print_map();
-            distances = HashMap::new();
-            done_set = HashSet::new();
-
-            neighbours_to_do = vec![(new_tile_map.end, Direction::Right)];
-            while neighbours_to_do.len() > 0 {
-                let (next_neighbour, next_neighbour_direction): (Position, Direction) = neighbours_to_do.remove(0);
-                new_tile_map.djikstra(next_neighbour, next_neighbour_direction, &mut done_set, &mut distances, &mut neighbours_to_do);
-            }
-
-            for i in 0..20 {
-                done_set = HashSet::new();
-                neighbours_to_do = vec![(new_tile_map.start, Direction::Right)];
-                while neighbours_to_do.len() > 0 {
-                    let (next_neighbour, next_neighbour_direction): (Position, Direction) = neighbours_to_do.remove(0);
-                    new_tile_map.djikstra(next_neighbour, next_neighbour_direction, &mut done_set, &mut distances, &mut neighbours_to_do);
-                }
-            }
-
-            let new_distance: Option<usize> = new_tile_map.get_distance_recursive(new_tile_map.start, &mut vec![Direction::Right], &mut distances);
-            if new_distance.is_some() {
-                println!("({}) New distance with blocker {:?}", k, b);
-                println!("\t{:?}", new_distance.unwrap() - 1);
-                if new_distance.unwrap() - 1 == best_path_length {
-                    let full_path: Vec<Position> = new_tile_map.get_path(new_tile_map.start, &distances);
-                    unique_points.extend(HashSet::<Position>::from_iter(full_path));
-                    println!("\tunique points len = {:?}", unique_points.len());
-                    let current_all_path_points: Vec<Position> = new_tile_map.get_path(tile_map.start, &distances);
-                    let current_path_points_hash: HashSet<Position> = HashSet::from_iter(current_all_path_points.clone());
-                    println!("\tThere are {} different intersections", current_path_points_hash.difference(&all_path_points_hash).collect::<Vec<&Position>>().len());
-
-                    if neighbours.len() == 4 {
-                        println!("\t{:?} has 4 neighbours", b);
-                        let current_intersection_index_option: Option<usize> = current_all_path_points.iter().position(|x| x == *intersection);
-                        if current_intersection_index_option.is_none() {
-                            println!("\t\tCurrent intersection not included in new path.");
-                        } else {
-                            let current_intersection_index: usize = current_all_path_points.iter().position(|x| x == *intersection).unwrap();
-                            block_sites.push(current_all_path_points[current_intersection_index + 1]);
-                            println!("\t\tAdding new block site at {:?}", block_sites.last().unwrap());
-                        }
-                    }
-                }
-            }
-            block_index += 1;
-        }
-
-        j += 1;
-        if j == 10 {
-            // break;
-        }
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(best_paths(solution::parsed(&self.tile_map, DAY)?, false)?));
     }
 
-
-    if !part_2 {
-        return best_path_length;
-    } else {
-        return unique_points.len();
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(best_paths(solution::parsed(&self.tile_map, DAY)?, true)?));
     }
 }
 
@@ -423,6 +189,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -441,48 +208,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn costs() {
+        let lines: Vec<String> = ["#####", "#..E#", "#.#.#", "#S..#", "#####"].iter().map(|x| x.to_string()).collect();
+
+        // Facing east, going up first costs an extra turn.
+        let solution: MazeSolution = solve_maze(lines.clone(), Costs::default()).unwrap();
+        assert!(solution.score == 1004);
+        assert!(solution.tiles.len() == 5);
+        assert!(render_best_paths(lines.clone(), Costs::default()).unwrap() == vec!["#####", "#..E#", "#.#O#", "#SOO#", "#####"]);
+
+        // With free turns both ways round are as good.
+        let solution: MazeSolution = solve_maze(lines.clone(), Costs { step: 1, turn: 0 }).unwrap();
+        assert!(solution.score == 4);
+        assert!(solution.tiles.len() == 8);
+        assert!(render_best_paths(lines.clone(), Costs { step: 1, turn: 0 }).unwrap() == vec!["#####", "#OOE#", "#O#O#", "#SOO#", "#####"]);
+
+        // The two ways round meet in the middle facing different ways, and only a free turn joins them up.
+        let converging: Vec<String> = ["#####", "##.E#", "#..##", "#S.##", "#####"].iter().map(|x| x.to_string()).collect();
+        let solution: MazeSolution = solve_maze(converging.clone(), Costs { step: 1, turn: 0 }).unwrap();
+        assert!(solution.score == 4);
+        assert!(solution.tiles.len() == 6);
+
+        let walled: Vec<String> = ["#####", "#..E#", "#####", "#S..#", "#####"].iter().map(|x| x.to_string()).collect();
+        assert!(solve_maze(walled, Costs::default()).is_err());
+
+        let stray: Vec<String> = ["#####", "#..E#", "#.#?#", "#S..#", "#####"].iter().map(|x| x.to_string()).collect();
+        assert!(solve_maze(stray, Costs::default()).unwrap_err().to_string() == "line 3, column 4: bad maze tile '?'");
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 7036);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 11048);
+        answers::check(DAY, 1, "example_2", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 143580);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
 
         // 153536 too high
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 45);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn example_2_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 64);
+        answers::check(DAY, 2, "example_2", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 645);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
         //581 too low
         //600 too low
         // 615 is wrong
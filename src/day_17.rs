@@ -1,8 +1,11 @@
-const INPUTS_FOLDER: &str = "inputs/day_17";
+const DAY: usize = 17;
 
-use std::{collections::btree_map::Values, ops::BitXor};
+use std::{fmt, str::FromStr};
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::{debug, info};
+use crate::solution::{self, Answer, Solution};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Instruction {
@@ -16,314 +19,702 @@ enum Instruction {
     Cdv,
 }
 
-impl From<usize> for Instruction {
-    fn from(value: usize) -> Self {
+impl TryFrom<usize> for Instruction {
+    type Error = AocError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
         match value {
-            0 => Self::Adv,
-            1 => Self::Bxl,
-            2 => Self::Bst,
-            3 => Self::Jnz,
-            4 => Self::Bxc,
-            5 => Self::Out,
-            6 => Self::Bdv,
-            7 => Self::Cdv,
-            _ => panic!("Bad program number given!"),
+            0 => Ok(Self::Adv),
+            1 => Ok(Self::Bxl),
+            2 => Ok(Self::Bst),
+            3 => Ok(Self::Jnz),
+            4 => Ok(Self::Bxc),
+            5 => Ok(Self::Out),
+            6 => Ok(Self::Bdv),
+            7 => Ok(Self::Cdv),
+            _ => Err(AocError::parse(format!("bad opcode {}", value))),
         }
     }
 }
 
-impl From<&str> for Instruction {
-    fn from(value: &str) -> Self {
-        return Instruction::from(value.parse::<usize>().unwrap());
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        return Instruction::try_from(error::parse_number::<usize>(value)?);
     }
 }
 
 impl Instruction {
-    fn run(&self, value: usize, register_a: &mut i64, register_b: &mut i64, register_c: &mut i64) -> Option<usize> {
+    fn mnemonic(&self) -> &'static str {
         match self {
-            Instruction::Adv => {
-                *register_a = *register_a / 2i64.pow(value as u32);
-            },
-            Instruction::Bxl => {
-                *register_b = register_b.bitxor(value as i64);
-            },
-            Instruction::Bst => {
-                *register_b = (value % 8) as i64;
-            },
+            Instruction::Adv => "adv",
+            Instruction::Bxl => "bxl",
+            Instruction::Bst => "bst",
+            Instruction::Jnz => "jnz",
+            Instruction::Bxc => "bxc",
+            Instruction::Out => "out",
+            Instruction::Bdv => "bdv",
+            Instruction::Cdv => "cdv",
+        }
+    }
+
+    // bxl and jnz read their operand as a number, bxc ignores it and the rest read it as a combo operand.
+    fn operand_text(&self, operand: usize) -> String {
+        match self {
+            Instruction::Bxl | Instruction::Jnz => operand.to_string(),
+            Instruction::Bxc => String::new(),
+            _ => combo_text(operand).to_string(),
+        }
+    }
+
+    fn describe(&self, operand: usize) -> String {
+        let combo: &str = combo_text(operand);
+        match self {
+            Instruction::Adv => format!("A = A >> {}", combo),
+            Instruction::Bxl => format!("B = B ^ {}", operand),
+            Instruction::Bst => format!("B = {} % 8", combo),
+            Instruction::Jnz => format!("if A != 0 jump to {}", operand),
+            Instruction::Bxc => "B = B ^ C".to_string(),
+            Instruction::Out => format!("output {} % 8", combo),
+            Instruction::Bdv => format!("B = A >> {}", combo),
+            Instruction::Cdv => format!("C = A >> {}", combo),
+        }
+    }
+}
+
+fn combo_text(operand: usize) -> &'static str {
+    return ["0", "1", "2", "3", "A", "B", "C", "reserved"][operand];
+}
+
+// One line per instruction: its address, mnemonic, decoded operand and what it does.
+pub fn disassemble(program: &[usize]) -> Result<Vec<String>, AocError> {
+    check_program(program)?;
+    return program.chunks(2).enumerate()
+        .map(|(i, x)| {
+            let instruction: Instruction = Instruction::try_from(x[0])?;
+            let operand: usize = x[1];
+            let text: String = format!("{} {}", instruction.mnemonic(), instruction.operand_text(operand));
+            return Ok(format!("{:>3}: {:<8} {}", 2 * i, text.trim_end(), instruction.describe(operand)));
+        })
+        .collect::<Result<Vec<String>, AocError>>();
+}
+
+// Every opcode and operand is a 3-bit number, and every opcode has an operand.
+fn check_program(program: &[usize]) -> Result<(), AocError> {
+    for (i, x) in program.iter().enumerate() {
+        Instruction::try_from(*x).map_err(|e| e.at_column(i + 1))?;
+    }
+    if !program.len().is_multiple_of(2) {
+        return Err(AocError::parse("program has an opcode without an operand"));
+    }
+    return Ok(());
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Step {
+    Continue,
+    Output(usize),
+    Halted,
+}
+
+// The 3-bit computer. Runs until the instruction pointer goes past the end of the program.
+#[derive(Debug, Clone)]
+pub struct Computer {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+    pub ip: usize,
+    pub output: Vec<usize>,
+    program: Vec<usize>,
+    instruction_count: usize,
+    instruction_limit: Option<usize>,
+    trace: Option<Vec<String>>,
+}
+
+impl Computer {
+    pub fn new(program: Vec<usize>, a: u64, b: u64, c: u64) -> Result<Self, AocError> {
+        check_program(&program)?;
+        return Ok(Self { a, b, c, ip: 0, output: Vec::new(), program, instruction_count: 0, instruction_limit: None, trace: None });
+    }
+
+    pub fn parse(input_lines: &[String]) -> Result<Self, AocError> {
+        if input_lines.len() < 5 {
+            return Err(AocError::parse("expected three registers, a blank line and a program"));
+        }
+        let a: u64 = parse_register(&input_lines[0], "A").map_err(|e| e.at_line(1))?;
+        let b: u64 = parse_register(&input_lines[1], "B").map_err(|e| e.at_line(2))?;
+        let c: u64 = parse_register(&input_lines[2], "C").map_err(|e| e.at_line(3))?;
+        let program: Vec<usize> = parse_program(&input_lines[4]).map_err(|e| e.at_line(5))?;
+        return Self::new(program, a, b, c).map_err(|e| e.at_line(5));
+    }
+
+    // Running more instructions than this is an error rather than a hang.
+    pub fn with_limit(mut self, instruction_limit: usize) -> Self {
+        self.instruction_limit = Some(instruction_limit);
+        return self;
+    }
+
+    // Record every instruction run along with the registers before it.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        return self;
+    }
+
+    pub fn program(&self) -> &[usize] {
+        return &self.program;
+    }
+
+    pub fn instruction_count(&self) -> usize {
+        return self.instruction_count;
+    }
+
+    pub fn trace(&self) -> &[String] {
+        return self.trace.as_deref().unwrap_or(&[]);
+    }
+
+    pub fn output_string(&self) -> String {
+        return self.output.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+    }
+
+    // Put the registers back and clear the output so the same program can be run again.
+    pub fn reset(&mut self, a: u64, b: u64, c: u64) {
+        (self.a, self.b, self.c) = (a, b, c);
+        self.ip = 0;
+        self.output.clear();
+        self.instruction_count = 0;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.clear();
+        }
+    }
+
+    fn combo(&self, operand: usize) -> Result<u64, AocError> {
+        match operand {
+            0..=3 => return Ok(operand as u64),
+            4 => return Ok(self.a),
+            5 => return Ok(self.b),
+            6 => return Ok(self.c),
+            _ => return Err(AocError::parse(format!("reserved combo operand {} at {}", operand, self.ip))),
+        }
+    }
+
+    fn divide(&self, operand: usize) -> Result<u64, AocError> {
+        let shift: u64 = self.combo(operand)?;
+        return Ok(if shift >= 64 { 0 } else { self.a >> shift });
+    }
+
+    pub fn step(&mut self) -> Result<Step, AocError> {
+        if self.ip + 1 >= self.program.len() {
+            return Ok(Step::Halted);
+        }
+        if self.instruction_limit.is_some_and(|x| self.instruction_count >= x) {
+            return Err(AocError::parse(format!("instruction limit of {} reached", self.instruction_count)));
+        }
+        let instruction: Instruction = Instruction::try_from(self.program[self.ip])?;
+        let operand: usize = self.program[self.ip + 1];
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(format!("{:>3}: {:<8} A={} B={} C={}", self.ip, format!("{} {}", instruction.mnemonic(), instruction.operand_text(operand)).trim_end(), self.a, self.b, self.c));
+        }
+        self.instruction_count += 1;
+
+        let mut step: Step = Step::Continue;
+        let mut next_ip: usize = self.ip + 2;
+        match instruction {
+            Instruction::Adv => self.a = self.divide(operand)?,
+            Instruction::Bxl => self.b ^= operand as u64,
+            Instruction::Bst => self.b = self.combo(operand)? % 8,
             Instruction::Jnz => {
-                if *register_a == 0 {
-                    return None;
-                } else {
-                    // Figure out Jumping
-                    return Some(0);
+                if self.a != 0 {
+                    next_ip = operand;
                 }
             },
-            Instruction::Bxc => {
-                
-            },
+            Instruction::Bxc => self.b ^= self.c,
             Instruction::Out => {
-                return Some(value % 8);
-            },
-            Instruction::Bdv => {
-                
-            },
-            Instruction::Cdv => {
-                
+                let value: usize = (self.combo(operand)? % 8) as usize;
+                self.output.push(value);
+                step = Step::Output(value);
             },
+            Instruction::Bdv => self.b = self.divide(operand)?,
+            Instruction::Cdv => self.c = self.divide(operand)?,
         }
+        self.ip = next_ip;
+        return Ok(step);
+    }
 
-        return None;
+    // The next output value, or None if the program halts first.
+    pub fn run_until_output(&mut self) -> Result<Option<usize>, AocError> {
+        loop {
+            match self.step()? {
+                Step::Continue => (),
+                Step::Output(x) => return Ok(Some(x)),
+                Step::Halted => return Ok(None),
+            }
+        }
+    }
+
+    pub fn run(&mut self) -> Result<&[usize], AocError> {
+        while self.step()? != Step::Halted {}
+        return Ok(&self.output);
     }
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> (String, i64, i64, i64) {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let register_a_init: i64 = input_lines[0].split(" ").last().unwrap().parse::<i64>().unwrap();
-    let register_b_init: i64 = input_lines[1].split(" ").last().unwrap().parse::<i64>().unwrap();
-    let register_c_init: i64 = input_lines[2].split(" ").last().unwrap().parse::<i64>().unwrap();
+// A value worked out from register A as it is at the start of a pass round the program's loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    A,
+    Const(u64),
+    Xor(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+    Shr(Box<Expr>, Box<Expr>),
+}
 
-    let mut register_a: i64 = register_a_init;
-    let mut register_b: i64 = register_b_init;
-    let mut register_c: i64 = register_c_init;
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::A => write!(f, "A"),
+            Expr::Const(x) => write!(f, "{}", x),
+            Expr::Xor(x, y) => write!(f, "({} ^ {})", x, y),
+            Expr::Mod8(x) => write!(f, "({} % 8)", x),
+            Expr::Shr(x, y) => write!(f, "({} >> {})", x, y),
+        }
+    }
+}
 
-    let program: Vec<usize> = input_lines[4].split(" ").last().unwrap().split(",").map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
-    
-    let mut output_string: String = String::new();
-    if !part_2 {
-        output_string = do_program(program, &mut register_a, &mut register_b, &mut register_c, part_2);
-        return (output_string, register_a, register_b, register_c);
-    } else {
-        let program_string: String = program.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
-        let mut finished: bool = false;
-        let mut loop_index: usize = 0;
-        let start_offset: i64 = (812262 << 27) + (3 << 24) + (1 << 21) + (2 << 18) + (2 << 15) + (4 << 9) + (2 << 6) + (3 << 3);
-        // println!("Start offset {} bits = {:0b}", start_offset.ilog2() + 1, start_offset);
-        println!("Program string is {:?}", program_string);
-        // let mut loop_index: usize =  35184372000000;
-        let total_loops: usize = 29000000 - 3518400;
-        // let mut loop_index: usize = 290000000000000;
-        // Running loop index 35184663000000 last finished
-        // let mut loop_index: usize = 0;
-        while !finished {
-            let start_a: i64 = (loop_index << 0) as i64 + start_offset;
-            register_a = start_a;
-            register_b = register_b_init;
-            register_c = register_c_init;
-            output_string = do_program(program.clone(), &mut register_a, &mut register_b, &mut register_c, part_2);
-            
-            if output_string == program_string {
-                finished = true;
-                println!("Register A is {}", start_a);
-                //00011000110010011100110011101110001000111100000001 = 2,4,1,5,7,5,0,3,4,1,1,6,5,5,3,0
-            }
+impl Expr {
+    pub fn eval(&self, a: u64) -> u64 {
+        match self {
+            Expr::A => a,
+            Expr::Const(x) => *x,
+            Expr::Xor(x, y) => x.eval(a) ^ y.eval(a),
+            Expr::Mod8(x) => x.eval(a) % 8,
+            Expr::Shr(x, y) => {
+                let shift: u64 = y.eval(a);
+                if shift >= 64 { 0 } else { x.eval(a) >> shift }
+            },
+        }
+    }
 
-            if output_string.ends_with("4,1,5,7,5,0,3,4,1,1,6,5,5,3,0") {
-                println!("{:050b} = {}", (loop_index << 0) as i64 + start_offset, output_string);
-            }
+    // The largest value this can be, None if that depends on how big A is.
+    fn max_value(&self) -> Option<u64> {
+        match self {
+            Expr::A => None,
+            Expr::Const(x) => Some(*x),
+            Expr::Xor(x, y) => {
+                let largest: u64 = x.max_value()?.max(y.max_value()?);
+                return Some(if largest == 0 { 0 } else { u64::MAX >> largest.leading_zeros() });
+            },
+            Expr::Mod8(_) => Some(7),
+            Expr::Shr(x, _) => x.max_value(),
+        }
+    }
 
-            let mut matches_end: bool = true;
-            for j in 0..output_string.len() {
-                matches_end = matches_end && output_string.chars().nth_back(j) == program_string.chars().nth(j);
-            }
+    // How many of the low bits of A it takes to know the low `bits` bits of this, None if it takes all of them.
+    fn bits_needed(&self, bits: u32) -> Option<u32> {
+        match self {
+            Expr::A => Some(bits),
+            Expr::Const(_) => Some(0),
+            Expr::Xor(x, y) => Some(x.bits_needed(bits)?.max(y.bits_needed(bits)?)),
+            Expr::Mod8(x) => x.bits_needed(bits.min(3)),
+            Expr::Shr(x, y) => {
+                let largest_shift: u64 = y.max_value()?;
+                let shift_bits: u32 = y.bits_needed(64 - largest_shift.leading_zeros())?;
+                return Some(x.bits_needed(bits + largest_shift as u32)?.max(shift_bits));
+            },
+        }
+    }
+}
 
-            // if matches_end {
-            //     println!("Loop index = {}, output len = {}, end matches", loop_index, output_string.len());
-            // }
+// A program that is one loop ending in jnz 0, where each pass prints one value worked out from A and then shifts A
+// right by a fixed amount, with B and C set from A before they are read. Most puzzle inputs look like this.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopProgram {
+    pub output: Expr,
+    pub shift: u32,
+}
 
-            if loop_index % 1 == 0 {
-                // println!("Running loop index ({}/{})", (loop_index/10000000), total_loops);
-                // println!("{:020b} = {}", (loop_index << 14) as i64 + 3841, output_string);
-                // println!("\tanswer len = {}", output_string.len());
-                // println!("\tprogram_string = {:?}", program_string);
-                // println!("\toutput_string = {:?}", output_string);
-            }
+impl fmt::Display for LoopProgram {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "out = {}\nA = A >> {}", self.output, self.shift)?;
+        if let Some(bits) = self.bits_used() {
+            write!(f, "\nout = f(A mod 2^{})", bits)?;
+        }
+        return Ok(());
+    }
+}
 
-            if loop_index == 10000000  {
-                break;
+impl LoopProgram {
+    // Runs the loop body over expressions instead of numbers. None if the program has some other shape.
+    pub fn analyse(program: &[usize]) -> Option<Self> {
+        let (body, last): (&[usize], &[usize]) = program.split_at(program.len().checked_sub(2)?);
+        if last != [3, 0] {
+            return None;
+        }
+        let mut a: Expr = Expr::A;
+        let mut b: Option<Expr> = None;
+        let mut c: Option<Expr> = None;
+        let mut output: Option<Expr> = None;
+        for x in body.chunks(2) {
+            let operand: usize = x[1];
+            let combo: Option<Expr> = match operand {
+                0..=3 => Some(Expr::Const(operand as u64)),
+                4 => Some(a.clone()),
+                5 => b.clone(),
+                6 => c.clone(),
+                _ => None,
+            };
+            match Instruction::try_from(x[0]).ok()? {
+                Instruction::Adv => {
+                    // Only a fixed shift keeps the passes in step with the digits of A.
+                    combo.clone().filter(|x| matches!(x, Expr::Const(_)))?;
+                    a = Expr::Shr(Box::new(a), Box::new(combo?));
+                },
+                Instruction::Bxl => b = Some(Expr::Xor(Box::new(b?), Box::new(Expr::Const(operand as u64)))),
+                Instruction::Bst => b = Some(Expr::Mod8(Box::new(combo?))),
+                Instruction::Jnz => return None,
+                Instruction::Bxc => b = Some(Expr::Xor(Box::new(b?), Box::new(c.clone()?))),
+                Instruction::Out => {
+                    if output.is_some() {
+                        return None;
+                    }
+                    output = Some(Expr::Mod8(Box::new(combo?)));
+                },
+                Instruction::Bdv => b = Some(Expr::Shr(Box::new(a.clone()), Box::new(combo?))),
+                Instruction::Cdv => c = Some(Expr::Shr(Box::new(a.clone()), Box::new(combo?))),
             }
-            loop_index += 1;
         }
+
+        let mut shift: u64 = 0;
+        while let Expr::Shr(x, y) = a {
+            shift += y.eval(0);
+            a = *x;
+        }
+        if shift == 0 || shift >= 64 {
+            return None;
+        }
+        return Some(Self { output: output?, shift: shift as u32 });
     }
 
+    // The value printed by the pass that starts with this A.
+    pub fn output_for(&self, a: u64) -> usize {
+        return self.output.eval(a) as usize;
+    }
 
-    return (output_string, register_a, register_b, register_c);
+    // Everything the program prints when started with this A, without running the Computer.
+    pub fn outputs(&self, a: u64) -> Vec<usize> {
+        let mut outputs: Vec<usize> = vec![self.output_for(a)];
+        let mut a: u64 = a >> self.shift;
+        while a != 0 {
+            outputs.push(self.output_for(a));
+            a >>= self.shift;
+        }
+        return outputs;
+    }
+
+    // How many low bits of A each printed value depends on.
+    pub fn bits_used(&self) -> Option<u32> {
+        return self.output.bits_needed(3);
+    }
 }
 
-fn do_program(program: Vec<usize>, register_a: &mut i64, register_b: &mut i64, register_c: &mut i64, part_2: bool) -> (String) {
-    let mut output: Vec<usize> = Vec::new();
-    let mut instruction_index: usize = 0;
-    let mut instruction_count: usize = 0;
-    let mut output_matches: Vec<bool> = Vec::new();
-
-    while instruction_index < program.len() {
-        instruction_count += 1;
-        let current_instruction: Instruction = Instruction::from(program[instruction_index]);
-        let current_literal_operand: usize = program[instruction_index + 1];
-        let current_combo_operand: i64 = match current_literal_operand {
-            0 | 1 | 2 | 3 => current_literal_operand as i64,
-            4 => *register_a,
-            5 => *register_b,
-            6 => *register_c,
-            7 => 7,
-            _ => panic!("Bad number in program!"),
-        };
+// Plenty for any real input, and stops a program that never halts.
+const INSTRUCTION_LIMIT: usize = 10_000_000;
 
-        match current_instruction {
-            Instruction::Adv => {
-                *register_a = *register_a / 2i64.pow(current_combo_operand as u32);
-            },
-            Instruction::Bxl => {
-                *register_b = register_b.bitxor(current_literal_operand as i64);
-            },
-            Instruction::Bst => {
-                *register_b = (current_combo_operand % 8) as i64;
-            },
-            Instruction::Jnz => {
-                if *register_a != 0 {
-                    instruction_index = current_literal_operand;
-                }
-            },
-            Instruction::Bxc => {
-                *register_b = register_b.bitxor(*register_c);
-            },
-            Instruction::Out => {
-                output.push((current_combo_operand % 8) as usize);
-            },
-            Instruction::Bdv => {
-                *register_b = *register_a / 2i64.pow(current_combo_operand as u32);
-            },
-            Instruction::Cdv => {
-                *register_c = *register_a / 2i64.pow(current_combo_operand as u32);
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<(String, u64, u64, u64), AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<(String, u64, u64, u64), AocError> {
+    return run_program(&Computer::parse(&input_lines)?, part_2);
+}
+
+fn run_program(computer: &Computer, part_2: bool) -> Result<(String, u64, u64, u64), AocError> {
+    let mut computer: Computer = computer.clone().with_limit(INSTRUCTION_LIMIT);
+    let (register_b_init, register_c_init): (u64, u64) = (computer.b, computer.c);
+    for line in disassemble(computer.program())? {
+        debug!("{}", line);
+    }
+    if let Some(lifted) = LoopProgram::analyse(computer.program()) {
+        debug!("{}", lifted);
+    }
+
+    if !part_2 {
+        computer.run()?;
+        return Ok((computer.output_string(), computer.a, computer.b, computer.c));
+    } else {
+        // The registers the program is started with, rather than where it ends up.
+        let a: u64 = find_quine(&computer)?.ok_or(AocError::parse("no value of register A makes the program output itself"))?;
+        info!("Register A is {}", a);
+        return Ok((a.to_string(), a, register_b_init, register_c_init));
+    }
+}
+
+// The smallest value of register A that makes the program output a copy of itself, or None if there isn't one.
+//
+// Programs like these loop, printing a digit and shifting A three bits right each time round until A is 0, so
+// the last digit printed depends on only the top three bits of A, the one before on the top six, and so on. The
+// search builds A up from the top three bits at a time, keeping each candidate that prints the matching end of the
+// program, and trying the smaller bits first so the first full match is the smallest. A candidate that doesn't
+// survive a full run is dropped and the search carries on, so None means there was nothing left to try.
+pub fn find_quine(computer: &Computer) -> Result<Option<u64>, AocError> {
+    let mut computer: Computer = computer.clone();
+    let (b, c): (u64, u64) = (computer.b, computer.c);
+    // Checking just the new digit is enough when each pass takes exactly three bits off A.
+    let lifted: Option<LoopProgram> = LoopProgram::analyse(computer.program()).filter(|x| x.shift == 3);
+    return Ok(extend_quine(&mut computer, lifted.as_ref(), b, c, 0, 0));
+}
+
+fn extend_quine(computer: &mut Computer, lifted: Option<&LoopProgram>, b: u64, c: u64, matched: usize, a: u64) -> Option<u64> {
+    let program: Vec<usize> = computer.program().to_vec();
+    if matched == program.len() {
+        // Check the whole run, in case the program doesn't have the shape the search relies on.
+        computer.reset(a, b, c);
+        return computer.run().is_ok_and(|x| *x == program).then_some(a);
+    }
+    if a >= 1 << 61 {
+        return None;
+    }
+    for bits in 0..8 {
+        let candidate: u64 = (a << 3) | bits;
+        let matches: bool = match lifted {
+            // The passes after this one start from a, which already prints the rest, unless a is 0 and the loop
+            // stops early.
+            Some(x) => (matched == 0 || a != 0) && x.output_for(candidate) == program[program.len() - matched - 1],
+            None => {
+                computer.reset(candidate, b, c);
+                // A program that errors or runs too long for this A just doesn't match.
+                computer.run().is_ok_and(|x| *x == program[(program.len() - matched - 1)..])
             },
+        };
+        if matches {
+            if let Some(x) = extend_quine(computer, lifted, b, c, matched + 1, candidate) {
+                return Some(x);
+            }
         }
+    }
+    return None;
+}
 
-        if !(current_instruction == Instruction::Jnz && *register_a != 0) {
-            instruction_index += 2;
-        }
+fn parse_register(value: &str, name: &str) -> Result<u64, AocError> {
+    let number: &str = value.strip_prefix(format!("Register {}:", name).as_str()).ok_or(AocError::parse(format!("expected register {}, got {:?}", name, value)))?;
+    return error::parse_number::<u64>(number);
+}
+
+fn parse_program(value: &str) -> Result<Vec<usize>, AocError> {
+    let numbers: &str = value.strip_prefix("Program:").ok_or(AocError::parse(format!("expected a program, got {:?}", value)))?;
+    return numbers.trim().split(",").map(error::parse_number::<usize>).collect::<Result<Vec<usize>, AocError>>();
+}
 
-        // if output_matches.len() < output.len() {
-        //     for i in output_matches.len()..output.len() {
-        //         if output[i] != program[i] {
-        //             return String::new();
-        //         } else {
-        //             output_matches.push(true);
-        //         }
-        //     }
-        // }
+#[derive(Default)]
+pub struct Day17 {
+    computer: Option<Computer>,
+}
 
-        // println!("A = {}, B = {}, C = {}", register_a, register_b, register_c);
+impl Solution for Day17 {
+    fn day(&self) -> usize {
+        return 17;
+    }
 
-        // if instruction_count == 10 {
-        //     break;
-        // }
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.computer = Some(Computer::parse(&input_lines)?);
+        return Ok(());
     }
 
-    return output.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(",");
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(run_program(solution::parsed(&self.computer, DAY)?, false)?.0));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(run_program(solution::parsed(&self.computer, DAY)?, true)?.1));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
+
+    fn run(program: Vec<usize>, a: u64, b: u64, c: u64) -> Computer {
+        let mut computer: Computer = Computer::new(program, a, b, c).unwrap().with_limit(1000);
+        computer.run().unwrap();
+        return computer;
+    }
 
     #[test]
     fn quick_test() {
         // Do a quick test here
+        assert!(run(vec![0, 1], 11, 0, 0).a == 5);
+        assert!(run(vec![0, 2], 11, 0, 0).a == 2);
+    }
+
+    #[test]
+    fn small_programs() {
+        // The small examples from the puzzle that the example_1_x tests don't cover.
+        assert!(run(vec![5, 0, 5, 1, 5, 4], 10, 0, 0).output_string() == "0,1,2");
+        assert!(run(vec![0, 1, 5, 4, 3, 0], 729, 0, 0).output_string() == "4,6,3,5,6,3,5,2,1,0");
+
+        let mut computer: Computer = Computer::new(vec![0, 1, 5, 4, 3, 0], 2024, 0, 0).unwrap();
+        assert!(computer.run_until_output().unwrap() == Some(4));
+        assert!(computer.run_until_output().unwrap() == Some(2));
+        assert!(computer.step().unwrap() == Step::Continue);
+        computer.run().unwrap();
+        assert!(computer.run_until_output().unwrap().is_none());
+        assert!(computer.step().unwrap() == Step::Halted);
+
+        assert!(Computer::new(vec![2, 7], 0, 0, 0).unwrap().run().unwrap_err().to_string() == "reserved combo operand 7 at 0");
+    }
 
-        let mut register_a: i64 = 0;
-        let mut register_b: i64 = 0;
-        let mut register_c: i64 = 0;
+    #[test]
+    fn limit_and_trace() {
+        // Never halts, since A is never changed.
+        let mut computer: Computer = Computer::new(vec![1, 1, 3, 0], 1, 0, 0).unwrap().with_limit(10);
+        assert!(computer.run().unwrap_err().to_string() == "instruction limit of 10 reached");
+        assert!(computer.instruction_count() == 10);
+
+        let mut computer: Computer = Computer::new(vec![0, 1, 5, 4, 3, 0], 2, 0, 0).unwrap().with_trace();
+        computer.run().unwrap();
+        assert!(computer.trace() == [
+            "  0: adv 1    A=2 B=0 C=0",
+            "  2: out A    A=1 B=0 C=0",
+            "  4: jnz 0    A=1 B=0 C=0",
+            "  0: adv 1    A=1 B=0 C=0",
+            "  2: out A    A=0 B=0 C=0",
+            "  4: jnz 0    A=0 B=0 C=0",
+        ]);
+    }
+
+    #[test]
+    fn disassembly() {
+        assert!(disassemble(&[2, 4, 1, 5, 7, 5, 4, 0, 0, 3, 5, 5, 3, 0]).unwrap() == vec![
+            "  0: bst A    B = A % 8",
+            "  2: bxl 5    B = B ^ 5",
+            "  4: cdv B    C = A >> B",
+            "  6: bxc      B = B ^ C",
+            "  8: adv 3    A = A >> 3",
+            " 10: out B    output B % 8",
+            " 12: jnz 0    if A != 0 jump to 0",
+        ]);
+    }
 
+    #[test]
+    fn quine() {
+        // The part 2 example, and a program shaped like the real inputs.
+        let computer: Computer = Computer::new(vec![0, 3, 5, 4, 3, 0], 2024, 0, 0).unwrap();
+        assert!(find_quine(&computer).unwrap() == Some(117440));
+        let program: Vec<usize> = vec![2, 4, 1, 5, 7, 5, 0, 3, 4, 1, 1, 6, 5, 5, 3, 0];
+        let a: u64 = find_quine(&Computer::new(program.clone(), 0, 0, 0).unwrap()).unwrap().unwrap();
+        assert!(a == 109020013201563);
+        assert!(run(program.clone(), a, 0, 0).output == program);
+
+        // Only ever prints 0s, so it can't print itself.
+        assert!(find_quine(&Computer::new(vec![0, 3, 5, 0, 3, 0], 0, 0, 0).unwrap()).unwrap().is_none());
+        // Never prints anything.
+        assert!(find_quine(&Computer::new(vec![0, 3, 3, 0], 0, 0, 0).unwrap()).unwrap().is_none());
+
+        // A lifted loop that is wrong when the top digit of A is 2, so the search goes down a branch the full run
+        // rejects before it gets to the real answer.
+        let mut computer: Computer = Computer::new(vec![0, 3, 5, 4, 3, 0], 0, 0, 0).unwrap();
+        let shifted: Expr = Expr::Shr(Box::new(Expr::A), Box::new(Expr::Const(3)));
+        let wrong_at_2: Expr = Expr::Shr(Box::new(Expr::Const(1)), Box::new(Expr::Xor(Box::new(shifted.clone()), Box::new(Expr::Const(2)))));
+        let lifted: LoopProgram = LoopProgram { output: Expr::Mod8(Box::new(Expr::Xor(Box::new(shifted), Box::new(wrong_at_2)))), shift: 3 };
+        assert!(lifted.output_for(0o24) == 3);
+        assert!(extend_quine(&mut computer, Some(&lifted), 0, 0, 0, 0) == Some(117440));
+    }
 
-        register_a = 11;
-        Instruction::Adv.run(1, &mut register_a, &mut register_b, &mut register_c);
-        assert!(register_a == 5);
+    #[test]
+    fn unchecked_programs() {
+        assert!(Computer::new(vec![0, 3, 8, 0], 0, 0, 0).unwrap_err().to_string() == "column 3: bad opcode 8");
+        assert!(Computer::new(vec![0, 3, 5], 0, 0, 0).unwrap_err().to_string() == "program has an opcode without an operand");
+        assert!(disassemble(&[9, 0]).is_err());
+        assert!(disassemble(&[5]).is_err());
+    }
 
-        register_a = 11;
-        Instruction::Adv.run(2, &mut register_a, &mut register_b, &mut register_c);
-        assert!(register_a == 2);
+    #[test]
+    fn lifted_loop() {
+        let program: Vec<usize> = vec![2, 4, 1, 5, 7, 5, 0, 3, 4, 1, 1, 6, 5, 5, 3, 0];
+        let lifted: LoopProgram = LoopProgram::analyse(&program).unwrap();
+        assert!(lifted.shift == 3);
+        assert!(lifted.bits_used() == Some(10));
+        assert!(lifted.to_string() == "out = (((((A % 8) ^ 5) ^ (A >> ((A % 8) ^ 5))) ^ 6) % 8)\nA = A >> 3\nout = f(A mod 2^10)");
+        for a in [0, 1, 7, 8, 729, 2024, 117440, 123456789, 109020013201563] {
+            assert!(lifted.outputs(a) == run(program.clone(), a, 0, 0).output);
+        }
 
-        register_b = 29;
-        Instruction::Bxl.run(7, &mut register_a, &mut register_b, &mut register_c);
-        assert!(register_b == 26);
+        let example: LoopProgram = LoopProgram::analyse(&[0, 3, 5, 4, 3, 0]).unwrap();
+        assert!(example.to_string() == "out = ((A >> 3) % 8)\nA = A >> 3\nout = f(A mod 2^6)");
+        assert!(example.outputs(117440) == vec![0, 3, 5, 4, 3, 0]);
 
+        // Reads B before setting it, shifts A by an amount that changes, and has no loop.
+        assert!(LoopProgram::analyse(&[1, 5, 0, 3, 5, 5, 3, 0]).is_none());
+        assert!(LoopProgram::analyse(&[0, 4, 5, 4, 3, 0]).is_none());
+        assert!(LoopProgram::analyse(&[0, 3, 5, 4]).is_none());
+    }
 
-        println!("A = {}, B = {}, C = {}", register_a, register_b, register_c);
+    #[test]
+    fn bad_input() {
+        let input_lines: Vec<String> = ["Register A: 729", "Register B: 0", "Register C: 0", "", "Program: 0,1,8,4,3,0"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let error = solve_lines(input_lines, false).unwrap_err();
+        assert!(error.to_string() == "line 5, column 3: bad opcode 8");
+
+        let input_lines: Vec<String> = ["Register A: 729", "Register X: 0", "Register C: 0", "", "Program: 0,1"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let error = solve_lines(input_lines, false).unwrap_err();
+        assert!(error.to_string().starts_with("line 2: expected register B"));
     }
 
     #[test]
     fn example_1_1() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1_1.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
+        let input_lines: Vec<String> = ["Register A: 0", "Register B: 0", "Register C: 9", "", "Program: 2,6"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let (_, _, b, _) = solve_lines(input_lines, false).unwrap();
         assert!(b == 1);
     }
 
     #[test]
     fn example_1_2() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1_2.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
-        assert!(answer == "0,1,2");
+        answers::check(DAY, 1, "example_1_2", |x| solve_puzzle(x, false).map(|r| r.0));
     }
 
     #[test]
     fn example_1_3() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1_3.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
+        let input_lines: Vec<String> = ["Register A: 2024", "Register B: 0", "Register C: 0", "", "Program: 0,1,5,4,3,0"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let (answer, a, _, _) = solve_lines(input_lines, false).unwrap();
         assert!(a == 0);
         assert!(answer == "4,2,5,6,7,7,7,7,3,1,0");
     }
 
     #[test]
     fn example_1_4() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1_4.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
+        let input_lines: Vec<String> = ["Register A: 0", "Register B: 29", "Register C: 0", "", "Program: 1,7"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let (_, _, b, _) = solve_lines(input_lines, false).unwrap();
         assert!(b == 26);
     }
 
     #[test]
     fn example_1_5() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1_5.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
+        let input_lines: Vec<String> = ["Register A: 0", "Register B: 2024", "Register C: 43690", "", "Program: 4,0"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let (_, _, b, _) = solve_lines(input_lines, false).unwrap();
         assert!(b == 44354);
     }
 
 
     #[test]
     fn example_1() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
-        assert!(answer == "4,6,3,5,6,3,5,2,1,0");
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false).map(|r| r.0));
     }
 
     #[test]
     fn part_1() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
-        assert!(answer == "7,0,3,1,2,6,3,7,1");
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false).map(|r| r.0));
     }
 
     #[test]
     fn example_2() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", true);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
-        assert!(answer == "4,6,3,5,6,3,5,2,1,0");
-        // 117440
+        answers::check(DAY, 2, "example_2", |x| solve_puzzle(x, true).map(|r| r.1));
     }
 
     #[test]
     fn part_2() {
-        let (answer, a, b, c) = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        println!("A = {}, B = {}, C = {}", a, b, c);
-        assert!(answer == "4,6,3,5,6,3,5,2,1,0");
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true).map(|r| r.1));
         // 109020013201563 answer
 
 
@@ -343,4 +734,4 @@ mod tests {
         println!("Register A = {}", register_a);
         println!("Register A also = {:b}", register_a);
     }
-}
\ No newline at end of file
+}
@@ -1,114 +1,215 @@
-const INPUTS_FOLDER: &str = "inputs/day_18";
+const DAY: usize = 18;
 
-use std::collections::{HashMap, HashSet};
 
-use itertools::Itertools;
+use crate::generic::{Grid, Position};
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::search::{self, SearchResult};
+use crate::solution::{Answer, Solution};
 
-use crate::generic;
-use crate::generic::Position;
-
-enum MemoryType {
-    Free,
-    Corrupted,
+// A stream of bytes falling onto a square memory grid, with the path from the top left to the bottom right
+// corner checked after any number of them have landed.
+#[derive(Default)]
+pub struct FallingBytes {
+    grid_size: usize,
+    bytes: Vec<Position>,
+    // The index of the first byte to land on each cell, usize::MAX for cells that stay free.
+    fall_time: Grid<usize>,
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool, grid_size: usize, bytes_fallen: usize) -> String {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let corrupt_points: Vec<Position> = input_lines.iter().map(|x| {
-        let coordinates: Vec<usize> = x.split(",").map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
-        return Position{ row: coordinates[1], col: coordinates[0] };
-    }).collect::<Vec<Position>>();
+impl FallingBytes {
+    pub fn new(grid_size: usize, bytes: Vec<Position>) -> Result<Self, AocError> {
+        let mut fall_time: Grid<usize> = Grid::new(grid_size, grid_size, usize::MAX);
+        for (i, b) in bytes.iter().enumerate() {
+            let time: &mut usize = fall_time.get_mut(*b).ok_or(AocError::parse(format!("byte {} at {},{} is outside the {}x{} grid", i, b.col, b.row, grid_size, grid_size)).at_line(i + 1))?;
+            *time = (*time).min(i);
+        }
+        return Ok(Self { grid_size, bytes, fall_time });
+    }
+
+    fn start(&self) -> Position {
+        return Position { row: 0, col: 0 };
+    }
 
-    let all_points: Vec<Position> = (0..grid_size).map(|r| (0..grid_size).map(|c| Position{row: r, col: c}).collect::<Vec<Position>>()).concat();
-    let all_points_hash: HashSet<Position> = HashSet::from_iter(all_points);
-    let corrupt_points_hash: HashSet<Position> = HashSet::from_iter(corrupt_points[..bytes_fallen].iter().copied());
+    fn end(&self) -> Position {
+        return Position { row: self.grid_size - 1, col: self.grid_size - 1 };
+    }
 
-    let free_points: Vec<Position> = all_points_hash.difference(&corrupt_points_hash).copied().collect_vec();
-    let start_position: Position = Position { row: 0, col: 0 };
-    let end_position: Position = Position { row: grid_size - 1, col: grid_size - 1 };
+    fn is_free(&self, p: Position, bytes_fallen: usize) -> bool {
+        return self.fall_time[p] >= bytes_fallen;
+    }
 
-    if !part_2 {
-        let distance_to_end: usize = djikstra(grid_size, start_position, end_position, corrupt_points_hash).unwrap();
-        return distance_to_end.to_string();
-    } else {
-        let mut finished: bool = false;
-        let mut byte_index: usize = 0;
-        while !finished {
-            let corrupt_points_hash_copy: HashSet<Position> = HashSet::from_iter(corrupt_points[0..byte_index].iter().copied());
-            let distance_to_end: Option<usize> = djikstra(grid_size, start_position, end_position, corrupt_points_hash_copy);
-            if distance_to_end.is_none() {
-                finished = true;
+    // Steps from corner to corner once the first bytes_fallen bytes have landed, None if there is no way through.
+    pub fn path_length(&self, bytes_fallen: usize) -> Option<usize> {
+        if self.grid_size == 0 || !self.is_free(self.start(), bytes_fallen) {
+            return None;
+        }
+        let result: SearchResult<Position> = search::bfs([self.start()], |p| self.fall_time.neighbours(*p)
+            .filter(|x| self.is_free(*x, bytes_fallen))
+            .collect());
+        return result.distance(&self.end());
+    }
+
+    // The index of the first byte that cuts the corners off from each other, found by binary search over how many
+    // bytes have fallen.
+    pub fn first_blocking_byte(&self) -> Option<usize> {
+        if self.path_length(self.bytes.len()).is_some() {
+            return None;
+        }
+        // The path is open after low bytes and blocked after high bytes.
+        let (mut low, mut high): (usize, usize) = (0, self.bytes.len());
+        while low + 1 < high {
+            let middle: usize = (low + high) / 2;
+            if self.path_length(middle).is_some() {
+                low = middle;
             } else {
-                byte_index += 1
-            };
+                high = middle;
+            }
         }
-        return format!("{},{}", corrupt_points[byte_index - 1].col, corrupt_points[byte_index - 1].row);
+        // None when there is no path even before any bytes fall.
+        self.path_length(low)?;
+        return Some(high - 1);
     }
-}
 
-fn djikstra(grid_size: usize, start_position: Position, end_position: Position, corrupt_points_hash: HashSet<Position>) -> Option<usize> {
-    let mut point_distances_to_start: HashMap<Position, usize> = HashMap::new();
-    let mut points_to_do: Vec<Position> = vec![start_position];
-    let mut done_points: HashSet<Position> = HashSet::new();
-
-    while points_to_do.len() > 0 {
-        // println!("distances = {:?}", point_distances_to_start);
-        let current_point: Position = points_to_do.remove(0);
-
-        if !done_points.contains(&current_point) {
-            done_points.insert(current_point);
-
-            let neighbours = current_point.get_neighbours(grid_size, grid_size);
-
-            // println!("current_point = {:?}, neighbours = {:?}", current_point, neighbours);
-
-            for n in neighbours.iter().copied().filter(|x| !corrupt_points_hash.contains(&x)) {
-                if current_point == start_position {
-                    point_distances_to_start.insert(current_point, 0);
-                    point_distances_to_start.insert(n, 1);
-                    if !points_to_do.contains(&n) {
-                        points_to_do.push(n);
-                    }
-                    done_points.remove(&n);
-                } else {
-                    let distance_to_current: usize = *point_distances_to_start.get(&current_point).unwrap();
-                    if point_distances_to_start.contains_key(&n) {
-                        let distance_to_neighbour: usize = *point_distances_to_start.get(&n).unwrap();
-                        if distance_to_current + 1 < distance_to_neighbour {
-                            point_distances_to_start.insert(n, distance_to_current + 1);
-                            if !points_to_do.contains(&n) {
-                                points_to_do.push(n);
-                            }
-                            done_points.remove(&n);
-                        }
-                    } else {
-                        point_distances_to_start.insert(n, distance_to_current + 1);
-                        if !points_to_do.contains(&n) {
-                            points_to_do.push(n);
-                        }
-                    }
-                }
+    // The same as first_blocking_byte, but lifts the bytes back off in reverse order and joins up the free cells
+    // until the corners meet, which only visits each cell once.
+    pub fn first_blocking_byte_union_find(&self) -> Option<usize> {
+        let index = |p: Position| p.row * self.grid_size + p.col;
+        let mut sets: UnionFind = UnionFind::new(self.grid_size * self.grid_size);
+        let (start, end): (Position, Position) = (self.start(), self.end());
+        if self.grid_size == 0 {
+            return None;
+        }
+
+        let mut free: Grid<bool> = self.fall_time.map(|x| *x == usize::MAX);
+        for p in self.fall_time.positions().filter(|x| free[*x]) {
+            for n in self.fall_time.neighbours(p).filter(|x| free[*x]) {
+                sets.union(index(p), index(n));
             }
         }
+        if free[start] && free[end] && sets.find(index(start)) == sets.find(index(end)) {
+            return None;
+        }
+
+        for (i, b) in self.bytes.iter().enumerate().rev() {
+            if self.fall_time[*b] != i {
+                continue;
+            }
+            free[*b] = true;
+            for n in self.fall_time.neighbours(*b).filter(|x| free[*x]) {
+                sets.union(index(*b), index(n));
+            }
+            if free[start] && free[end] && sets.find(index(start)) == sets.find(index(end)) {
+                return Some(i);
+            }
+        }
+        return None;
+    }
+}
+
+struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        return Self { parents: (0..size).collect::<Vec<usize>>(), sizes: vec![1; size] };
     }
 
-    // print_map(grid_size, &point_distances_to_start);
+    fn find(&mut self, x: usize) -> usize {
+        let mut root: usize = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current: usize = x;
+        while self.parents[current] != root {
+            let next: usize = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        return root;
+    }
 
-    return point_distances_to_start.get(&end_position).copied();
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+    }
 }
 
-fn print_map(grid_size: usize, point_distances_to_start: &HashMap<Position, usize>) {
-    for r in 0..grid_size {
-        for c in 0..grid_size {
-            let quick_position: Position = Position { row: r, col: c };
-            if !point_distances_to_start.contains_key(&quick_position) {
-                print!("     ");
-            } else {
-                print!("{:5}", point_distances_to_start.get(&quick_position).unwrap());
-            }
-            print!(" ");
+pub fn solve_puzzle(variant: &str, part_2: bool, grid_size: usize, bytes_fallen: usize) -> Result<String, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2, grid_size, bytes_fallen);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool, grid_size: usize, bytes_fallen: usize) -> Result<String, AocError> {
+    let falling_bytes: FallingBytes = FallingBytes::new(grid_size, error::parse_lines(&input_lines, |x| parse_byte(x))?)?;
+    return escape(&falling_bytes, part_2, bytes_fallen);
+}
+
+fn escape(falling_bytes: &FallingBytes, part_2: bool, bytes_fallen: usize) -> Result<String, AocError> {
+    if !part_2 {
+        let distance_to_end: usize = falling_bytes.path_length(bytes_fallen).ok_or(AocError::parse(format!("no path after {} bytes have fallen", bytes_fallen)))?;
+        return Ok(distance_to_end.to_string());
+    } else {
+        let byte_index: usize = falling_bytes.first_blocking_byte_union_find().ok_or(AocError::parse("no byte blocks the path"))?;
+        let byte: Position = falling_bytes.bytes[byte_index];
+        return Ok(format!("{},{}", byte.col, byte.row));
+    }
+}
+
+fn parse_byte(value: &str) -> Result<Position, AocError> {
+    let (col, row) = value.split_once(",").ok_or(AocError::parse(format!("expected x,y, got {:?}", value)))?;
+    return Ok(Position{ row: error::parse_number::<usize>(row)?, col: error::parse_number::<usize>(col)? });
+}
+
+pub struct Day18 {
+    falling_bytes: FallingBytes,
+    grid_size: usize,
+    bytes_fallen: usize,
+}
+
+impl Default for Day18 {
+    fn default() -> Self {
+        return Self { falling_bytes: FallingBytes::default(), grid_size: 71, bytes_fallen: 1024 };
+    }
+}
+
+impl Solution for Day18 {
+    fn day(&self) -> usize {
+        return 18;
+    }
+
+    // The examples are a 7x7 grid after 12 bytes, the real input a 71x71 grid after 1024 bytes. Inputs with bytes
+    // further out than that get a grid just big enough to hold them.
+    fn set_variant(&mut self, variant: &str) {
+        if variant.starts_with("example") {
+            (self.grid_size, self.bytes_fallen) = (7, 12);
+        } else {
+            (self.grid_size, self.bytes_fallen) = (71, 1024);
         }
-        print!("\n");
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        let bytes: Vec<Position> = error::parse_lines(&input_lines, |x| parse_byte(x))?;
+        let grid_size: usize = bytes.iter().map(|x| x.row.max(x.col) + 1).max().unwrap_or(0).max(self.grid_size);
+        self.falling_bytes = FallingBytes::new(grid_size, bytes)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(escape(&self.falling_bytes, false, self.bytes_fallen)?));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(escape(&self.falling_bytes, true, self.bytes_fallen)?));
     }
 }
 
@@ -116,37 +217,77 @@ fn print_map(grid_size: usize, point_distances_to_start: &HashMap<Position, usiz
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
         // Do a quick test here
     }
 
+    #[test]
+    fn blocking_byte() {
+        // A wall down column 2, finished off by byte 4.
+        let bytes: Vec<Position> = vec![(4, 2), (2, 2), (3, 2), (0, 2), (1, 2), (0, 0)].into_iter()
+            .map(|(row, col)| Position { row, col })
+            .collect();
+        let falling_bytes: FallingBytes = FallingBytes::new(5, bytes.clone()).unwrap();
+        assert!(falling_bytes.path_length(0) == Some(8));
+        assert!(falling_bytes.path_length(3) == Some(8));
+        assert!(falling_bytes.path_length(4) == Some(8));
+        assert!(falling_bytes.path_length(5).is_none());
+        assert!(falling_bytes.first_blocking_byte() == Some(4));
+        assert!(falling_bytes.first_blocking_byte_union_find() == Some(4));
+
+        let open: FallingBytes = FallingBytes::new(5, bytes[..4].to_vec()).unwrap();
+        assert!(open.first_blocking_byte().is_none());
+        assert!(open.first_blocking_byte_union_find().is_none());
+
+        assert!(FallingBytes::new(5, vec![Position { row: 5, col: 0 }]).is_err());
+    }
+
+    #[test]
+    fn large_grid() {
+        // A wall across the middle row with the gap at the far right closed last.
+        let grid_size: usize = 1000;
+        let bytes: Vec<Position> = (0..grid_size).map(|col| Position { row: grid_size / 2, col }).collect();
+        let falling_bytes: FallingBytes = FallingBytes::new(grid_size, bytes).unwrap();
+        assert!(falling_bytes.first_blocking_byte_union_find() == Some(grid_size - 1));
+    }
+
+    #[test]
+    fn size_from_variant() {
+        let input_lines: Vec<String> = vec!["3,3".to_string()];
+        let mut day: Day18 = Day18::default();
+        day.parse(input_lines.clone()).unwrap();
+        assert!(day.part1().unwrap() == Answer::from("140"));
+
+        day.set_variant("example_1");
+        day.parse(input_lines).unwrap();
+        assert!(day.part1().unwrap() == Answer::from("12"));
+
+        // A custom input bigger than the real one, run as a file, gets a grid that fits it.
+        day.set_variant("input");
+        day.parse(vec!["999,0".to_string(), "0,1".to_string(), "1,0".to_string()]).unwrap();
+        assert!(day.part2().unwrap() == Answer::from("1,0"));
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false, 7, 12);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "22");
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false, 7, 12));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false, 71, 1024);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "284");
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false, 71, 1024));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true, 7, 12);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "6,1");
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true, 7, 12));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true, 71, 1024);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "51,50");
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true, 71, 1024));
     }
 }
\ No newline at end of file
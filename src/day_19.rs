@@ -1,21 +1,37 @@
-const INPUTS_FOLDER: &str = "inputs/day_19";
+const DAY: usize = 19;
 
 use std::collections::HashMap;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::inputs;
+use crate::solution::{Answer, Solution};
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let towels: Vec<String> = input_lines[0].split(", ").map(|x| x.to_string()).collect::<Vec<String>>();
-    let designs: Vec<String> = input_lines[2..].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let (towels, designs) = parse_towels(&input_lines)?;
+    return Ok(count_designs(&towels, &designs, part_2));
+}
+
+// The towel patterns on the first line, then the designs after a blank line.
+fn parse_towels(input_lines: &[String]) -> Result<(Vec<String>, Vec<String>), AocError> {
+    let first_line: &String = input_lines.first().ok_or(AocError::parse("expected a line of towel patterns").at_line(1))?;
+    let towels: Vec<String> = first_line.split(", ").map(|x| x.to_string()).collect::<Vec<String>>();
+    let designs: Vec<String> = input_lines.iter().skip(2).map(|x| x.to_string()).collect::<Vec<String>>();
+    return Ok((towels, designs));
+}
 
+fn count_designs(towels: &Vec<String>, designs: &[String], part_2: bool) -> usize {
     let mut sum_good_designs: usize = 0;
     let mut sum_design_options: usize = 0;
     let mut memoisation: HashMap<String, usize> = HashMap::new();
 
     for design in designs {
-        let design_options: usize = check_string(design, &towels, &mut memoisation);
+        let design_options: usize = check_string(design.clone(), towels, &mut memoisation);
         if design_options > 0 {
             sum_good_designs += 1;
             sum_design_options += design_options;
@@ -50,10 +66,36 @@ fn check_string(design: String, towels: &Vec<String>, memoisation: &mut HashMap<
     return total_options;
 }
 
+#[derive(Default)]
+pub struct Day19 {
+    towels: Vec<String>,
+    designs: Vec<String>,
+}
+
+impl Solution for Day19 {
+    fn day(&self) -> usize {
+        return 19;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        (self.towels, self.designs) = parse_towels(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_designs(&self.towels, &self.designs, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_designs(&self.towels, &self.designs, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -62,29 +104,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 6);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 255);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 16);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 621820080273474);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
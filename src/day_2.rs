@@ -1,6 +1,9 @@
-const INPUTS_FOLDER: &str = "inputs/day_2";
+const DAY: usize = 2;
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::trace;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, PartialEq)]
 enum ReportSafety {
@@ -13,9 +16,11 @@ struct Report {
     levels: Vec<usize>,
 }
 
-impl From<&String> for Report {
-    fn from(value: &String) -> Self {
-        return Self{levels: value.split(" ").map(|x| x.parse::<usize>().expect("Bad integer given")).collect::<Vec<usize>>()};
+impl TryFrom<&String> for Report {
+    type Error = AocError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        return Ok(Self{levels: value.split(" ").map(error::parse_number::<usize>).collect::<Result<Vec<usize>, AocError>>()?});
     }
 }
 
@@ -45,8 +50,8 @@ impl Report {
         let mut new_report: Self = self.clone();
         new_report.levels.remove(target_level_index);
 
-        println!("old report = {:?}", self);
-        println!("new report = {:?}", new_report);
+        trace!("old report = {:?}", self);
+        trace!("new report = {:?}", new_report);
 
         return new_report;
     }
@@ -59,7 +64,7 @@ impl Report {
         let mut difference: i32;
         let mut difference_1_problem: bool = false;
         
-        println!("Running {:?}", self);
+        trace!("Running {:?}", self);
 
         for i in 1..self.levels.len() {
             difference = self.levels[i] as i32 - self.levels[i - 1] as i32;
@@ -92,11 +97,11 @@ impl Report {
                 }
             }
 
-            println!("\tdifference={}", difference);
-            println!("\tincreases={}", increases);
-            println!("\tincreases_1_problem={}", increases_1_problem);
-            println!("\tdecreases={}", decreases);
-            println!("\tdecreases_1_problem={}", decreases_1_problem);
+            trace!("\tdifference={}", difference);
+            trace!("\tincreases={}", increases);
+            trace!("\tincreases_1_problem={}", increases_1_problem);
+            trace!("\tdecreases={}", decreases);
+            trace!("\tdecreases_1_problem={}", decreases_1_problem);
 
             
 
@@ -111,16 +116,22 @@ impl Report {
 
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
-    let reports: Vec<Report> = input_lines.iter().map(|x| Report::from(x)).collect::<Vec<Report>>();
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let reports: Vec<Report> = error::parse_lines(&input_lines, |x| Report::try_from(x))?;
+    return Ok(count_safe(&reports, part_2));
+}
 
+fn count_safe(reports: &[Report], part_2: bool) -> usize {
     for r in reports.iter() {
         if !part_2 {
-            println!("{:?}: {:?}", r, r.safety());
+            trace!("{:?}: {:?}", r, r.safety());
         } else {
-            println!("{:?}: {:?}", r, r.safety_part2());
+            trace!("{:?}: {:?}", r, r.safety_part2());
         }
     }
 
@@ -131,10 +142,35 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     }
 }
 
+#[derive(Default)]
+pub struct Day2 {
+    reports: Vec<Report>,
+}
+
+impl Solution for Day2 {
+    fn day(&self) -> usize {
+        return 2;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.reports = error::parse_lines(&input_lines, |x| Report::try_from(x))?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_safe(&self.reports, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_safe(&self.reports, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -152,7 +188,7 @@ mod tests {
             "54 57 59 59 61"
         ];
 
-        let reports = report_strings.iter().map(|x| Report::from(&x.to_string())).collect::<Vec<Report>>();
+        let reports = report_strings.iter().map(|x| Report::try_from(&x.to_string()).unwrap()).collect::<Vec<Report>>();
 
         assert!(reports[0].safety_part2() == ReportSafety::Safe);
         assert!(reports[1].safety_part2() == ReportSafety::Safe);
@@ -168,29 +204,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 421);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 4);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 476);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
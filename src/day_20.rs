@@ -1,10 +1,13 @@
-const INPUTS_FOLDER: &str = "inputs/day_20";
+const DAY: usize = 20;
 
-use std::{collections::{HashMap, HashSet}};
+use std::collections::BTreeMap;
 
-use itertools::Itertools;
-
-use crate::generic::{self, Position};
+use crate::generic::{Grid, Position};
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::debug;
+use crate::search::{self, SearchResult};
+use crate::solution::{self, Answer, Solution};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Tile {
@@ -12,288 +15,139 @@ enum Tile {
     Wall,
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
+// The start and end are both on the track.
+impl TryFrom<char> for Tile {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '#' => Self::Wall,
-            _ => Self::Empty,
+            '#' => Ok(Self::Wall),
+            '.' | 'S' | 'E' => Ok(Self::Empty),
+            _ => Err(AocError::parse(format!("bad racetrack tile {:?}", value))),
         }
     }
 }
 
+// The puzzle only counts cheats that save at least this many picoseconds.
+const MIN_SAVING: usize = 100;
+
 struct Map {
     start: Position,
     end: Position,
-    tiles: Vec<Vec<Tile>>,
-}
-
-impl From<Vec<String>> for Map {
-    fn from(value: Vec<String>) -> Self {
-        let tiles: Vec<Vec<Tile>> = value.iter().map(|row| row.chars().map(|col| Tile::from(col)).collect::<Vec<Tile>>()).collect::<Vec<Vec<Tile>>>();
-        let mut start: Option<Position> = None;
-        let mut end: Option<Position> = None;
-        for (row, string) in value.iter().enumerate() {
-            for (col, char) in string.chars().enumerate() {
-                match char {
-                    'S' => start = Some(Position{row: row, col: col}),
-                    'E' => end = Some(Position{row: row, col: col}),
-                    _ => (),
-                }
-            }
-        }
-
-        return Map { start: start.unwrap(), end: end.unwrap(), tiles: tiles };
-    }
+    tiles: Grid<Tile>,
+    // Picoseconds from the start and to the end along the track, usize::MAX where the track doesn't reach.
+    from_start: Grid<usize>,
+    to_end: Grid<usize>,
 }
 
 impl Map {
-    fn get_walls(&self) -> Vec<Position> {
-        return self.tiles.iter().enumerate()
-            .map(|(row, tiles)| {
-                let mut row_tiles: Vec<Position> = Vec::new();
-                for (col, tile) in tiles.iter().enumerate() {
-                    if *tile == Tile::Wall {
-                        row_tiles.push(Position{row: row, col: col});
-                    }
-                }
-                return row_tiles;
-            })
-            .concat();
-    }
-
-    fn row_count(&self) -> usize {
-        return self.tiles.len();
-    }
-
-    fn col_count(&self) -> usize {
-        return self.tiles[0].len();
-    }
-
-    fn get_tile(&self, p: Position) -> Tile {
-        return self.tiles[p.row][p.col].clone();
-    }
-
-    fn get_distance_to_start(&self, current_point: &Position, point_to_point: &HashMap<Position, Position>) -> Option<usize> {
-        let mut point: Option<&Position> = Some(current_point);
-        let mut distance: usize = 0;
-        while point.is_some() && *point.unwrap() != self.start {
-            point = point_to_point.get(point.unwrap());
-            distance += 1;
-        }
-
-        if point.is_none() {
-            return None
-        } else {
-            return Some(distance);
-        }
-    }
-
-    fn get_path_to_start(&self, current_point: &Position, point_to_point: &HashMap<Position, Position>) -> Option<Vec<Position>> {
-        let mut point: Option<&Position> = Some(current_point);
-        let mut path: Vec<Position> = Vec::new();
-        while point.is_some() && *point.unwrap() != self.start {
-            path.push(*point.unwrap());
-            point = point_to_point.get(point.unwrap());
-        }
-
-        if point.is_none() {
-            return None
-        } else {
-            path.push(self.start);
-            path.reverse();
-            return Some(path);
-        }
-    }
-
-    fn djikstra(&self, walls_vec: &Vec<Position>) -> Option<(usize, Vec<Position>)> {
-        let walls: HashSet<Position> = HashSet::from_iter(walls_vec.clone());
-        let mut point_to_point: HashMap<Position, Position> = HashMap::new();
-        let mut points_to_do: Vec<Position> = vec![self.start];
-        let mut done_points: HashSet<Position> = HashSet::new();
-    
-        while points_to_do.len() > 0 {
-            // println!("distances = {:?}", point_distances_to_start);
-            let current_point: Position = points_to_do.remove(0);
-            // println!("Running djikstra on {:?}", current_point);
-            if !done_points.contains(&current_point) {
-                done_points.insert(current_point);
-    
-                let neighbours = current_point.get_neighbours(self.row_count(), self.col_count());
-    
-                // println!("current_point = {:?}, neighbours = {:?}", current_point, neighbours);
-    
-                for n in neighbours.iter().copied().filter(|x| !walls.contains(&x)) {
-                    if current_point == self.start {
-                        point_to_point.insert(current_point, current_point);
-                        point_to_point.insert(n, current_point);
-                        if !points_to_do.contains(&n) {
-                            points_to_do.push(n);
-                        }
-                        done_points.remove(&n);
-                    } else {
-                        let distance_to_current: usize = self.get_distance_to_start(&current_point, &point_to_point).unwrap();
-                        if point_to_point.contains_key(&n) {
-                            let distance_to_neighbour: usize = self.get_distance_to_start(&n, &point_to_point).unwrap();
-                            if distance_to_current + 1 < distance_to_neighbour {
-                                point_to_point.insert(n, current_point);
-                                if !points_to_do.contains(&n) {
-                                    points_to_do.push(n);
-                                }
-                                done_points.remove(&n);
-                            }
-                        } else {
-                            point_to_point.insert(n, current_point);
-                            if !points_to_do.contains(&n) {
-                                points_to_do.push(n);
-                            }
-                        }
+    fn new(input_lines: &[String]) -> Result<Self, AocError> {
+        let tiles: Grid<Tile> = Grid::parse(input_lines)?;
+        let chars: Grid<char> = Grid::parse(input_lines)?;
+        let start: Position = chars.find(&'S').ok_or(AocError::parse("no start tile (S) on the map"))?;
+        let end: Position = chars.find(&'E').ok_or(AocError::parse("no end tile (E) on the map"))?;
+        let from_start: Grid<usize> = Map::distances(&tiles, start);
+        let to_end: Grid<usize> = Map::distances(&tiles, end);
+        if from_start[end] == usize::MAX {
+            return Err(AocError::parse("no path from the start to the end"));
+        }
+        return Ok(Map { start, end, tiles, from_start, to_end });
+    }
+
+    fn distances(tiles: &Grid<Tile>, from: Position) -> Grid<usize> {
+        let result: SearchResult<Position> = search::bfs([from], |p| tiles.neighbours(*p)
+            .filter(|x| tiles[*x] == Tile::Empty)
+            .collect());
+        let mut distances: Grid<usize> = Grid::new(tiles.row_count(), tiles.col_count(), usize::MAX);
+        for (p, d) in result.distances() {
+            distances[*p] = *d;
+        }
+        return distances;
+    }
+
+    // The time from start to end without cheating.
+    fn base_time(&self) -> usize {
+        return self.from_start[self.end];
+    }
+
+    // How many cheats save each amount of time. A cheat goes from one track tile to another through anything in
+    // at most max_cheat_duration picoseconds, so every track tile within that Manhattan distance is a cheat end.
+    fn cheat_savings(&self, max_cheat_duration: usize, min_saving: usize) -> BTreeMap<usize, usize> {
+        let mut savings: BTreeMap<usize, usize> = BTreeMap::new();
+        let radius: isize = max_cheat_duration as isize;
+        for (p, from_start) in self.from_start.iter().filter(|x| *x.1 != usize::MAX) {
+            for dr in -radius..=radius {
+                let remaining: isize = radius - dr.abs();
+                for dc in -remaining..=remaining {
+                    let cheat_end: Position = match self.to_end.offset(p, (dr, dc)) {
+                        Some(x) if self.to_end[x] != usize::MAX => x,
+                        _ => continue,
+                    };
+                    let time: usize = from_start + (dr.abs() + dc.abs()) as usize + self.to_end[cheat_end];
+                    if time + min_saving <= self.base_time() && time < self.base_time() {
+                        *savings.entry(self.base_time() - time).or_default() += 1;
                     }
                 }
             }
         }
-
-        let distance_to_start: Option<usize> = self.get_distance_to_start(&self.end, &point_to_point);
-        let path_to_start: Option<Vec<Position>> = self.get_path_to_start(&self.end, &point_to_point);
-
-        if distance_to_start.is_some() && path_to_start.is_some() {
-            return Some((distance_to_start.unwrap(), path_to_start.unwrap()));
-        } else {
-            return None;
-        }
+        return savings;
     }
+}
 
-    fn get_cheat_ends(&self, current_point: &Position, path_points: &HashSet<Position>, depth: usize, cheat_ends: &mut HashMap<Position, usize>, tail: Option<HashSet<Position>>) {
-        if depth == 20 {
-            return;
-        }
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
-        let mut current_tail: HashSet<Position>;
-        if tail.is_none() {
-            current_tail = HashSet::new();
-        } else {
-            current_tail = tail.unwrap();
-        }
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return Ok(count_cheats(&Map::new(&input_lines)?, part_2));
+}
 
-        let neighbours: Vec<Position> = current_point.get_neighbours(self.row_count(), self.col_count());
-        // println!("Getting cheat ends for {:?} with neighbours {:?}", current_point, neighbours);
-        for n in neighbours {
-            let neighbour_is_edge: bool = n.row ==0 || n.row == self.row_count() - 1 || n.col == 0 || n.col == self.col_count() - 1;
-            if self.get_tile(n) == Tile::Empty && path_points.contains(&n) {
-                if cheat_ends.contains_key(&n) {
-                    let cheat_end_depth: usize = *cheat_ends.get(&n).unwrap();
-                    if depth < cheat_end_depth {
-                        // println!("\tadding {:?} at depth {}", n, depth);
-                        cheat_ends.insert(n, depth);
-                    }
-                } else {
-                    cheat_ends.insert(n, depth);
-                }
-            } else if self.get_tile(n) == Tile::Wall && !current_tail.contains(&n) {
-                current_tail.insert(current_point.clone());
-                self.get_cheat_ends(&n, path_points, depth + 1, cheat_ends, Some(current_tail.clone()));
-            }
-        }
+fn count_cheats(map: &Map, part_2: bool) -> usize {
+    let max_cheat_duration: usize = if part_2 { 20 } else { 2 };
+    let savings: BTreeMap<usize, usize> = map.cheat_savings(max_cheat_duration, MIN_SAVING);
+    for (time, count_cheats) in savings.iter() {
+        debug!("There are {} cheats that save {} picoseconds", count_cheats, time);
     }
+    return savings.values().sum::<usize>();
 }
 
+// Saving -> number of cheats with that saving, for cheats of up to max_cheat_duration picoseconds that save at
+// least min_saving.
+pub fn cheat_histogram(input_lines: &[String], max_cheat_duration: usize, min_saving: usize) -> Result<BTreeMap<usize, usize>, AocError> {
+    let map: Map = Map::new(input_lines)?;
+    return Ok(map.cheat_savings(max_cheat_duration, min_saving));
+}
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let map = Map::from(input_lines);
-    let walls: Vec<Position> = map.get_walls();
-
-    let (base_time, base_path): (usize, Vec<Position>) = map.djikstra(&walls).unwrap();
-    let base_path_hash: HashSet<Position> = HashSet::from_iter(base_path.clone());
-    let mut cheat_times: HashMap<usize, usize> = HashMap::new();
+#[derive(Default)]
+pub struct Day20 {
+    map: Option<Map>,
+}
 
-    let max_cheat_distance: usize;
-    if !part_2 {
-        max_cheat_distance = 2;
-    } else {
-        max_cheat_distance = 20;
+impl Solution for Day20 {
+    fn day(&self) -> usize {
+        return 20;
     }
 
-    // println!("{:?}", base_path);
-    for (i, p) in base_path.iter().enumerate() {
-        let mut cheat_savings: HashMap<(Position, Position), usize> = HashMap::new();
-        if i % 100 == 0 {
-            println!("Running point {}/{}", i, base_path.len());
-        }
-        let mut cheat_ends: HashMap<Position, usize> = HashMap::new();
-        for end_point in base_path[i..].iter() {
-            let manhattan_distance = p.manhattan_distance(end_point);
-            if manhattan_distance > 1 && manhattan_distance <= max_cheat_distance {
-                cheat_ends.insert(*end_point, manhattan_distance);
-            }
-        }
-
-        // println!("Cheat ends = {:?}", cheat_ends);
-
-        let start_index: usize = base_path.iter().position(|x| *x == *p).unwrap();
-        for cheat in cheat_ends.keys().sorted() {
-            let end_index: usize = base_path.iter().position(|x| *x == *cheat).unwrap();
-            let cheat_time_int: i32 = end_index as i32 - start_index as i32 - *cheat_ends.get(&cheat).unwrap() as i32;
-            let cheat_time: usize;
-            if cheat_time_int > 0 {
-                cheat_time = cheat_time_int as usize;
-                let cheat_path: (Position, Position);
-                if start_index > end_index {
-                    cheat_path = (*cheat, *p);
-                } else {
-                    cheat_path = (*p, *cheat);
-                }
-                // println!("cheat path {:?} saves {}", cheat_path, cheat_time);
-                // println!("\tstart_index = {}", start_index);
-                // println!("\tend_index = {}", end_index);
-                // println!("\tactual time = {}", start_index.abs_diff(end_index));
-                // println!("\tcheat cost = {}", cheat_ends.get(&cheat).unwrap());
-                if cheat_savings.contains_key(&cheat_path) {
-                    if cheat_time < *cheat_savings.get(&cheat_path).unwrap() {
-                        cheat_savings.insert(cheat_path, cheat_time);
-                    }
-                } else {
-                    cheat_savings.insert(cheat_path, cheat_time);
-                }
-            }
-        }
-
-        
-        
-        // if i == 5 {
-        //     break;
-        // }
-
-        for cheat_time in cheat_savings.values() {
-            if !cheat_times.contains_key(&cheat_time) {
-                cheat_times.insert(*cheat_time, 1);
-            } else {
-                cheat_times.insert(*cheat_time, cheat_times.get(&cheat_time).unwrap() + 1);
-            }
-        }
-
-        // println!("Cheat savings = {:?}", cheat_savings);
-        // println!("Cheat times = {:?}", cheat_times);
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.map = Some(Map::new(&input_lines)?);
+        return Ok(());
     }
 
-
-    let mut total_cheats: usize = 0;
-    let target_difference: usize = 100;
-    for time in cheat_times.keys().sorted() {
-        let count_cheats: usize = *cheat_times.get(time).unwrap();
-        println!("There are {} cheats that save {} picoseconds", count_cheats, time);
-        if *time >= target_difference {
-            total_cheats += count_cheats;
-            // println!("Adding {} cheats from saving {} picoseconds, total = {}", count_cheats, time, total_cheats);
-        }
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_cheats(solution::parsed(&self.map, DAY)?, false)));
     }
 
-    return total_cheats;
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_cheats(solution::parsed(&self.map, DAY)?, true)));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -301,18 +155,49 @@ mod tests {
         // let start_point
     }
 
+    #[test]
+    fn histogram() {
+        let input_lines: Vec<String> = vec![
+            "###############",
+            "#...#...#.....#",
+            "#.#.#.#.#.###.#",
+            "#S#...#.#.#...#",
+            "#######.#.#.###",
+            "#######.#.#...#",
+            "#######.#.###.#",
+            "###..E#...#...#",
+            "###.#######.###",
+            "#...###...#...#",
+            "#.#####.#.###.#",
+            "#.#...#.#.#...#",
+            "#.#.#.#.#.#.###",
+            "#...#...#...###",
+            "###############",
+        ].iter().map(|x| x.to_string()).collect();
+
+        let two: BTreeMap<usize, usize> = cheat_histogram(&input_lines, 2, 1).unwrap();
+        assert!(two == BTreeMap::from([(2, 14), (4, 14), (6, 2), (8, 4), (10, 2), (12, 3), (20, 1), (36, 1), (38, 1), (40, 1), (64, 1)]));
+
+        let twenty: BTreeMap<usize, usize> = cheat_histogram(&input_lines, 20, 50).unwrap();
+        assert!(twenty == BTreeMap::from([(50, 32), (52, 31), (54, 29), (56, 39), (58, 25), (60, 23), (62, 20), (64, 19), (66, 12), (68, 14), (70, 12), (72, 22), (74, 4), (76, 3)]));
+        assert!(twenty.values().sum::<usize>() == 285);
+
+        // Long enough to reach anywhere, so the best cheat walks straight from S to E.
+        let any: BTreeMap<usize, usize> = cheat_histogram(&input_lines, 100, 1).unwrap();
+        assert!(*any.keys().last().unwrap() == 84 - 8);
+
+        let stray: Vec<String> = ["#####", "#S.E#", "#.x.#", "#####"].iter().map(|x| x.to_string()).collect();
+        assert!(cheat_histogram(&stray, 2, 1).unwrap_err().to_string() == "line 3, column 3: bad racetrack tile 'x'");
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 13);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1409);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
 
         // 393 too low
         // 5581 too high
@@ -320,16 +205,12 @@ mod tests {
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 285);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1012821);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
         // 1102213 too high
         // 1012821
         // 1243052
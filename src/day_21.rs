@@ -1,94 +1,133 @@
-const INPUTS_FOLDER: &str = "inputs/day_21";
+const DAY: usize = 21;
 
 use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::generic::{self, Direction, Position};
+use crate::generic::{Direction, Position};
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::debug;
+use crate::solution::{Answer, Solution};
 
-struct NumberPad {
+// Spaces (or #) are gaps a robot arm must never point at. Every robot starts on A.
+const NUMERIC_LAYOUT: [&str; 4] = ["789", "456", "123", " 0A"];
+const DIRECTIONAL_LAYOUT: [&str; 2] = [" ^A", "<v>"];
+
+// Building a press sequence longer than this is an error, since the real chains make ones far too long to hold.
+const SEQUENCE_LIMIT: usize = 10_000_000;
+
+#[derive(Debug, Clone)]
+pub struct Keypad {
     buttons: HashMap<char, Position>,
-    points_map: HashMap<Position, HashMap<Position, Vec<String>>>,
+    gaps: HashSet<Position>,
+    // Every shortest way from one button to another that stays off the gaps, without the final A press.
+    paths: HashMap<(char, char), Vec<String>>,
 }
 
-impl NumberPad {
-    fn new() -> Self {
+impl Keypad {
+    // One string per row of buttons. Short rows are padded with gaps.
+    pub fn parse(layout: &[&str]) -> Result<Self, AocError> {
+        let width: usize = layout.iter().map(|x| x.chars().count()).max().unwrap_or(0);
         let mut buttons: HashMap<char, Position> = HashMap::new();
-        buttons.insert('7', Position { row: 0, col: 0 });
-        buttons.insert('8', Position { row: 0, col: 1 });
-        buttons.insert('9', Position { row: 0, col: 2 });
-        buttons.insert('4', Position { row: 1, col: 0 });
-        buttons.insert('5', Position { row: 1, col: 1 });
-        buttons.insert('6', Position { row: 1, col: 2 });
-        buttons.insert('1', Position { row: 2, col: 0 });
-        buttons.insert('2', Position { row: 2, col: 1 });
-        buttons.insert('3', Position { row: 2, col: 2 });
-        buttons.insert('X', Position { row: 3, col: 0 });
-        buttons.insert('0', Position { row: 3, col: 1 });
-        buttons.insert('A', Position { row: 3, col: 2 });
-
-        let excluded_positions: HashSet<Position> = HashSet::from_iter(vec![Position{ row: 3, col: 0 }]);
-
-        return Self { buttons: buttons.clone(), points_map: map_paths(buttons.values().collect::<Vec<&Position>>(), &excluded_positions) };
+        let mut gaps: HashSet<Position> = HashSet::new();
+        for (row, line) in layout.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect::<Vec<char>>();
+            for col in 0..width {
+                let position: Position = Position { row, col };
+                match chars.get(col) {
+                    None | Some(' ') | Some('#') => {
+                        gaps.insert(position);
+                    },
+                    Some(c) => {
+                        if buttons.insert(*c, position).is_some() {
+                            return Err(AocError::parse(format!("button {:?} is on the keypad twice", c)).at_line(row + 1));
+                        }
+                    },
+                }
+            }
+        }
+        if !buttons.contains_key(&'A') {
+            return Err(AocError::parse("the keypad needs an A button for its robot to start on"));
+        }
+
+        let mut paths: HashMap<(char, char), Vec<String>> = HashMap::new();
+        for ((from, p1), (to, p2)) in buttons.iter().cartesian_product(buttons.iter()) {
+            let from_to_paths: Vec<String> = get_all_paths(p1, p2, &gaps);
+            if from_to_paths.is_empty() {
+                return Err(AocError::parse(format!("every shortest way from {:?} to {:?} crosses a gap", from, to)));
+            }
+            paths.insert((*from, *to), from_to_paths);
+        }
+        return Ok(Self { buttons, gaps, paths });
     }
-}
 
-struct DirectionPad {
-    buttons: HashMap<char, Position>,
-    points_map: HashMap<Position, HashMap<Position, Vec<String>>>,
-}
+    pub fn numeric() -> Self {
+        return Self::parse(&NUMERIC_LAYOUT).unwrap();
+    }
 
-impl DirectionPad {
-    fn new() -> Self {
-        let mut buttons: HashMap<char, Position> = HashMap::new();
-        buttons.insert('X', Position { row: 0, col: 0 });
-        buttons.insert('^', Position { row: 0, col: 1 });
-        buttons.insert('A', Position { row: 0, col: 2 });
-        buttons.insert('<', Position { row: 1, col: 0 });
-        buttons.insert('v', Position { row: 1, col: 1 });
-        buttons.insert('>', Position { row: 1, col: 2 });
+    pub fn directional() -> Self {
+        return Self::parse(&DIRECTIONAL_LAYOUT).unwrap();
+    }
 
-        let excluded_positions: HashSet<Position> = HashSet::from_iter(vec![Position{ row: 0, col: 0 }]);
+    pub fn has_button(&self, button: char) -> bool {
+        return self.buttons.contains_key(&button);
+    }
 
-        return Self { buttons: buttons.clone(), points_map: map_paths(buttons.values().collect::<Vec<&Position>>(), &excluded_positions) };
+    pub fn paths(&self, from: char, to: char) -> &[String] {
+        return self.paths.get(&(from, to)).map(|x| x.as_slice()).unwrap_or(&[]);
     }
-}
 
-fn map_paths(all_points: Vec<&Position>, excluded_positions: &HashSet<Position>) -> HashMap<Position, HashMap<Position, Vec<String>>> {
-    let mut all_paths: HashMap<Position, HashMap<Position, Vec<String>>> = HashMap::new();
-    for p1 in all_points.iter() {
-        let mut point_paths: HashMap<Position, Vec<String>> = HashMap::new();
-        for p2 in all_points.iter() {
-            if p1 != p2 {
-                point_paths.insert(**p2, get_all_paths(p1, p2, &excluded_positions));
-            }
-        }
-        all_paths.insert(**p1, point_paths);
+    fn button_at(&self, position: Position) -> Option<char> {
+        return self.buttons.iter().find(|x| *x.1 == position).map(|x| *x.0);
     }
 
-    return all_paths;
+    // The buttons this keypad's robot presses when it's sent the given moves and presses, starting on A.
+    pub fn replay(&self, moves: &str) -> Result<String, AocError> {
+        let mut position: Position = self.buttons[&'A'];
+        let mut pressed: String = String::new();
+        for (i, c) in moves.chars().enumerate() {
+            let direction: Direction = match c {
+                'A' => {
+                    pressed.push(self.button_at(position).unwrap());
+                    continue;
+                },
+                '^' | 'v' | '<' | '>' => Direction::try_from(c)?,
+                _ => return Err(AocError::parse(format!("press {} is {:?}, which isn't a move or A", i + 1, c))),
+            };
+            position = position.checked_walk(1, direction)
+                .filter(|x| self.button_at(*x).is_some())
+                .ok_or(AocError::parse(format!("press {} points the arm at a gap", i + 1)))?;
+        }
+        return Ok(pressed);
+    }
 }
 
+// Every shortest way from one button to another that never crosses a gap.
 fn get_all_paths(position: &Position, target_position: &Position, excluded_positions: &HashSet<Position>) -> Vec<String> {
-    let (vertical_delta, horizontal_delta): (i32, i32) = target_position.delta(&position);
-    // println!("position = {:?}, target = {:?}, horizontal_delta = {}, vertical_delta = {}", position, target_position, horizontal_delta, vertical_delta);
-    let mut path: String = String::new();
-    if horizontal_delta > 0 {
-        path.push_str(">".repeat(horizontal_delta.abs() as usize).as_str());
-    } else {
-        path.push_str("<".repeat(horizontal_delta.abs() as usize).as_str());
-    }
-    if vertical_delta > 0 {
-        path.push_str("v".repeat(vertical_delta.abs() as usize).as_str());
-    } else {
-        path.push_str("^".repeat(vertical_delta.abs() as usize).as_str());
-    }
-
-    if path.len() == 0 {
-        return vec![path];
-    } else {
-        let all_paths: HashSet<Vec<char>> = HashSet::from_iter(path.chars().permutations(path.len()));
-        return all_paths.iter().map(|x| x.into_iter().collect::<String>()).filter(|x| verify_path(position, x.to_string(), excluded_positions)).collect::<Vec<String>>();
+    let (vertical_delta, horizontal_delta): (isize, isize) = position.offset_to(target_position);
+    let horizontal: char = if horizontal_delta > 0 { '>' } else { '<' };
+    let vertical: char = if vertical_delta > 0 { 'v' } else { '^' };
+    let mut paths: Vec<String> = Vec::new();
+    interleave((horizontal, horizontal_delta.unsigned_abs()), (vertical, vertical_delta.unsigned_abs()), &mut String::new(), &mut paths);
+    return paths.into_iter().filter(|x| verify_path(position, x.to_string(), excluded_positions)).collect::<Vec<String>>();
+}
+
+// Every order of the given number of each move.
+fn interleave(first: (char, usize), second: (char, usize), path: &mut String, paths: &mut Vec<String>) {
+    if first.1 == 0 && second.1 == 0 {
+        paths.push(path.clone());
+        return;
+    }
+    if first.1 > 0 {
+        path.push(first.0);
+        interleave((first.0, first.1 - 1), second, path, paths);
+        path.pop();
+    }
+    if second.1 > 0 {
+        path.push(second.0);
+        interleave(first, (second.0, second.1 - 1), path, paths);
+        path.pop();
     }
 }
 
@@ -96,7 +135,10 @@ fn verify_path(position: &Position, path: String, excluded_positions: &HashSet<P
     let mut current_position: Position = position.clone();
     // println!("Verifying position = {:?} with path {:?}", position, path);
     for c in path.chars() {
-        current_position = current_position.walk(1, Direction::from(c));
+        current_position = match Direction::try_from(c) {
+            Ok(d) => current_position.walk(1, d),
+            Err(_) => return false,
+        };
         if excluded_positions.contains(&current_position) {
             return false;
         }
@@ -104,187 +146,182 @@ fn verify_path(position: &Position, path: String, excluded_positions: &HashSet<P
     return true;
 }
 
-struct CodeCombinations {
-    codes: Vec<Vec<String>>,
-    max_codes: usize,
-    code_indices: Vec<usize>,
-    index: usize,
+// Keypads where each one's robot is driven from the next. The code is typed on the first keypad and the last one
+// is pressed by hand.
+#[derive(Debug, Clone)]
+pub struct KeypadChain {
+    keypads: Vec<Keypad>,
 }
 
-impl CodeCombinations {
-    fn new(codes: &Vec<Vec<String>>) -> Self {
-        let max_codes: usize = codes.iter().map(|x| x.len()).product();
-        let code_indices: Vec<usize> = (0..codes.len()).map(|x| {
-            if x == 0 {
-                return 1;
-            } else {
-                return codes[..x].iter().map(|a| a.len()).product();
+impl KeypadChain {
+    pub fn new(keypads: Vec<Keypad>) -> Result<Self, AocError> {
+        if keypads.is_empty() {
+            return Err(AocError::parse("a keypad chain needs at least one keypad"));
+        }
+        for (i, keypad) in keypads.iter().enumerate().skip(1) {
+            if let Some(missing) = ['^', 'v', '<', '>', 'A'].iter().find(|x| !keypad.has_button(**x)) {
+                return Err(AocError::parse(format!("keypad {} drives a robot but has no {:?} button", i, missing)));
             }
-        }).collect::<Vec<usize>>();
+        }
+        return Ok(Self { keypads });
+    }
 
-        return Self { codes: codes.clone(), max_codes: max_codes, code_indices: code_indices, index: 0 }
+    // The puzzle's chain: the door's numeric keypad, then a directional keypad for each robot in between and
+    // one more for us.
+    pub fn puzzle(robots: usize) -> Self {
+        let mut keypads: Vec<Keypad> = vec![Keypad::numeric()];
+        keypads.extend(std::iter::repeat_n(Keypad::directional(), robots + 1));
+        return Self::new(keypads).unwrap();
+    }
+
+    pub fn keypads(&self) -> &[Keypad] {
+        return &self.keypads;
+    }
+
+    // The fewest presses on the last keypad that type the code on the first.
+    pub fn cost(&self, code: &str) -> Result<usize, AocError> {
+        return self.cost_at(code, self.keypads.len() - 1);
     }
-}
 
-impl Iterator for CodeCombinations {
-    type Item = String;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index == self.max_codes {
-            return None;
-        } else {
-            let mut new_code: String = String::new();
-            for (i, x) in self.codes.iter().enumerate() {
-                new_code.push_str(x[(self.index/self.code_indices[i]) % x.len()].as_str());
+    // The fewest presses on keypad `depth` that type the code on the first, so depth 0 is just the code's length.
+    pub fn cost_at(&self, code: &str, depth: usize) -> Result<usize, AocError> {
+        self.check(code, depth)?;
+        let costs: Vec<HashMap<(char, char), usize>> = self.move_costs(depth);
+        return Ok(sequence_cost(&costs[0], code));
+    }
+
+    // One of the shortest sequences to press on keypad `depth` to type the code on the first.
+    pub fn press_sequence(&self, code: &str, depth: usize) -> Result<String, AocError> {
+        self.check(code, depth)?;
+        let costs: Vec<HashMap<(char, char), usize>> = self.move_costs(depth);
+        let length: usize = sequence_cost(&costs[0], code);
+        if length > SEQUENCE_LIMIT {
+            return Err(AocError::parse(format!("the press sequence for {} would be {} presses long", code, length)));
+        }
+        let mut sequence: String = code.to_string();
+        for layer in 0..depth {
+            let mut next: String = String::new();
+            let mut previous: char = 'A';
+            for c in sequence.chars() {
+                let best: &String = self.keypads[layer].paths(previous, c).iter().min_by_key(|x| sequence_cost(&costs[layer + 1], &(x.to_string() + "A"))).unwrap();
+                next.push_str(best);
+                next.push('A');
+                previous = c;
             }
-            self.index += 1;
-            return Some(new_code);
+            sequence = next;
         }
+        return Ok(sequence);
     }
-}
 
-fn code_combinations(codes: &Vec<Vec<String>>, ) {
-    let codes: Vec<Vec<&str>> = vec![vec!["aa", "bb", "cc"], vec!["dd"], vec!["ee", "ff"]];
-    let max_codes: usize = codes.iter().map(|x| x.len()).product();
-    let code_index: Vec<usize> = (0..codes.len()).map(|x| {
-        if x == 0 {
-            return 1;
-        } else {
-            return codes[..x].iter().map(|a| a.len()).product();
+    // For each keypad up to depth, what moving its robot from one button to another and pressing it costs in
+    // presses on keypad depth. Pressing keypad depth directly costs 1 whatever the move.
+    fn move_costs(&self, depth: usize) -> Vec<HashMap<(char, char), usize>> {
+        let mut costs: Vec<HashMap<(char, char), usize>> = vec![self.keypads[depth].paths.keys().map(|x| (*x, 1)).collect::<HashMap<(char, char), usize>>()];
+        for layer in (0..depth).rev() {
+            let next: &HashMap<(char, char), usize> = &costs[0];
+            let layer_costs: HashMap<(char, char), usize> = self.keypads[layer].paths.iter()
+                .map(|(k, paths)| (*k, paths.iter().map(|x| sequence_cost(next, &(x.to_string() + "A"))).min().unwrap()))
+                .collect::<HashMap<(char, char), usize>>();
+            costs.insert(0, layer_costs);
         }
-    }).collect::<Vec<usize>>();
-    for i in 0..max_codes {
-        let code: String = codes.iter().enumerate().map(|(x_index, x)| x[(i/code_index[x_index]) % x.len()]).collect::<String>();
-        println!("{}", code);
+        return costs;
     }
-}
 
-fn get_shortest_code_length(direction_pad: &DirectionPad, depth: usize, position: &Position, code: &String, excluded_positions: &HashSet<Position>, code_lengths: Option<&mut HashMap<(usize, String), usize>>) -> usize {
-    // println!("{}Depth = {}: Running get_shortest_code_length on {}", "\t".repeat(4 - depth), depth, code);
-
-    if depth == 0 {
-        if code_lengths.is_some() {
-            code_lengths.unwrap().insert((depth, code.clone()), code.len());
+    // Press the buttons on the last keypad and pass what each robot presses down the chain, giving what gets
+    // typed on the first keypad.
+    pub fn simulate(&self, presses: &str) -> Result<String, AocError> {
+        let last: usize = self.keypads.len() - 1;
+        if let Some(c) = presses.chars().find(|x| !self.keypads[last].has_button(*x)) {
+            return Err(AocError::parse(format!("there is no {:?} button on keypad {}", c, last)));
         }
-        return code.len();
-    } else {
-        let current_code_lengths: &mut HashMap<(usize, String), usize>;
-        let mut empty_hashmap: HashMap<(usize, String), usize> = HashMap::new();
-        if code_lengths.is_some() {
-            current_code_lengths = code_lengths.unwrap();
-        } else {
-            current_code_lengths = &mut empty_hashmap;
+        let mut sequence: String = presses.to_string();
+        for layer in (0..last).rev() {
+            sequence = self.keypads[layer].replay(&sequence).map_err(|e| AocError::parse(format!("the robot at keypad {} panics, {}", layer, e)))?;
         }
+        return Ok(sequence);
+    }
 
-        if current_code_lengths.contains_key(&(depth, code.clone())) {
-            return *current_code_lengths.get(&(depth, code.clone())).unwrap();
+    fn check(&self, code: &str, depth: usize) -> Result<(), AocError> {
+        if depth >= self.keypads.len() {
+            return Err(AocError::parse(format!("the chain only has {} keypads", self.keypads.len())));
         }
-
-        let mut current_position: &Position = position;
-        let mut all_code_paths: Vec<Vec<String>> = Vec::new();
-        let mut total_min_distance: usize = 0;
-        let mut target_path: String = String::new();
-        for c in code.chars() {
-            let target_position: &Position = direction_pad.buttons.get(&c).unwrap();
-            let mut paths: Vec<String> = get_all_paths(current_position, target_position, excluded_positions);
-            paths.iter_mut().for_each(|x| x.push('A'));
-            let mut path_distance: usize = 1000000000000;
-            let mut shortest_path: String = String::new();
-            for p in paths.iter() {
-                let path_min_distance: usize = get_shortest_code_length(direction_pad, depth - 1, &Position { row: 0, col: 2 }, &p, excluded_positions, Some(current_code_lengths));
-                current_code_lengths.insert((depth - 1, p.clone()), path_min_distance);
-                if path_min_distance < path_distance {
-                    path_distance = path_min_distance;
-                    shortest_path = p.clone();
-                }
-            }
-            total_min_distance += path_distance;
-            target_path.push_str(&shortest_path.as_str());
-            all_code_paths.push(paths);
-            current_position = target_position;
+        if let Some(c) = code.chars().find(|x| !self.keypads[0].has_button(*x)) {
+            return Err(AocError::parse(format!("there is no {:?} button to type {}", c, code)));
         }
+        return Ok(());
+    }
+}
 
-        // println!("{}Shortest path for code {} is {}", "\t".repeat(3 - depth), code, target_path);
-        current_code_lengths.insert((depth, code.clone()), total_min_distance);
-        return total_min_distance;
+// Presses to type the sequence, starting from A.
+fn sequence_cost(costs: &HashMap<(char, char), usize>, sequence: &str) -> usize {
+    let mut previous: char = 'A';
+    let mut total: usize = 0;
+    for c in sequence.chars() {
+        total += costs[&(previous, c)];
+        previous = c;
     }
+    return total;
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let codes: Vec<Vec<char>> = input_lines.iter().map(|x| x.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
-    
-    println!("Building numpad");
-    let numpad: NumberPad = NumberPad::new();
-    println!("Building dirpad");
-    let dirpad: DirectionPad = DirectionPad::new();
-    println!("All done");
-
-    for (k,v) in numpad.points_map.clone().into_iter() {
-        println!("Point {:?}", k);
-        for (xk, xv) in v.into_iter() {
-            println!("\tPoint {:?}: {:?}", xk, xv);
-        }
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return total_complexity(&error::parse_lines(&input_lines, |x| parse_code(x))?, part_2);
+}
+
+// A door code and the number it spells out.
+fn parse_code(code: &str) -> Result<(String, usize), AocError> {
+    let code_numbers: usize = code.replace("A", "").parse::<usize>().map_err(|_| AocError::parse(format!("expected a number followed by A, got {:?}", code)))?;
+    return Ok((code.to_string(), code_numbers));
+}
+
+fn total_complexity(codes: &[(String, usize)], part_2: bool) -> Result<usize, AocError> {
+    let robot_dirpads: usize = if !part_2 { 2 } else { 25 };
+    let chain: KeypadChain = KeypadChain::puzzle(robot_dirpads);
+
+    let mut total_complexity: usize = 0;
+    for (code, code_numbers) in codes.iter() {
+        let distance: usize = chain.cost(code)?;
+        debug!("Code {} has numeric {:3} x distance {}", code, code_numbers, distance);
+        total_complexity += code_numbers * distance;
     }
 
-    let mut start_position: Position = Position { row: 3, col: 2 };
-    let mut code_distances: HashMap<String, usize> = HashMap::new();
-    let robot_dirpads: usize;
-    if !part_2 {
-        robot_dirpads = 2;
-    } else {
-        robot_dirpads = 25;
-    }
-    
-    for c in codes {
-        println!("{:?}", c);
-        let mut paths: Vec<String> = Vec::new();
-        let mut end_position: Position = *numpad.buttons.get(&c[0]).unwrap();
-        paths.append(&mut numpad.points_map.get(&start_position).unwrap().get(&end_position).unwrap().clone());
-        paths.iter_mut().for_each(|x| x.push('A'));
-        start_position = end_position;
-        for x in c[1..].iter() {
-            end_position = *numpad.buttons.get(x).unwrap();
-            let mut bigger_paths: Vec<String> = Vec::new();
-            for p1 in paths {
-                for p2 in numpad.points_map.get(&start_position).unwrap().get(&end_position).unwrap().clone() {
-                    bigger_paths.push(p1.to_owned() + p2.as_str());
-                }
-            }
-            paths = bigger_paths.clone();
-            paths.iter_mut().for_each(|x| x.push('A'));
-            start_position = end_position;
-        }
+    return Ok(total_complexity);
+}
 
-        
-        let directional_excluded_positions: HashSet<Position> = HashSet::from_iter(vec![Position{row: 0, col: 0}]);
-        let mut current_min: usize = 1000000000000;
-        
-        for p in paths.iter() {
-            let new_min_path: usize = get_shortest_code_length(&dirpad, robot_dirpads, &Position { row: 0, col: 2}, p, &directional_excluded_positions, None);
-            if new_min_path < current_min {
-                current_min = new_min_path;
-            }
-        }
-        println!("Code = {:?}, min = {}, Paths = {:?}", c.iter().collect::<String>(), current_min, paths);
-        code_distances.insert(c.iter().collect::<String>(), current_min);
+#[derive(Default)]
+pub struct Day21 {
+    codes: Vec<(String, usize)>,
+}
+
+impl Solution for Day21 {
+    fn day(&self) -> usize {
+        return 21;
     }
 
-    let mut total_complexity: usize = 0;
-    for (code, distance) in code_distances {
-        let code_numbers: usize = code.replace("A", "").parse::<usize>().unwrap();
-        let complexity: usize = code_numbers * distance;
-        println!("Code {} has numeric {:3} x distance {}", code, code_numbers, distance);
-        total_complexity += complexity;
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.codes = error::parse_lines(&input_lines, |x| parse_code(x))?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_complexity(&self.codes, false)?));
     }
 
-    return total_complexity;
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(total_complexity(&self.codes, true)?));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -319,39 +356,79 @@ mod tests {
     }
 
     #[test]
-    fn quick_test3() {
-        // Do a quick test here
-        let codes: Vec<Vec<String>> = vec![vec!["aa".to_string(), "bb".to_string(), "cc".to_string()], vec!["dd".to_string()], vec!["ee".to_string(), "ff".to_string()]];
-        for x in CodeCombinations::new(&codes) {
-            println!("x = {:?}", x);
+    fn keypad_chain() {
+        let chain: KeypadChain = KeypadChain::puzzle(2);
+        assert!(chain.keypads().len() == 4);
+        assert!(chain.cost_at("029A", 0).unwrap() == 4);
+        assert!(chain.cost_at("029A", 1).unwrap() == "<A^A>^^AvvvA".len());
+        assert!(chain.cost_at("029A", 2).unwrap() == "v<<A>>^A<A>AvA<^AA>A<vAAA>^A".len());
+        let lengths: Vec<usize> = ["029A", "980A", "179A", "456A", "379A"].iter().map(|x| chain.cost(x).unwrap()).collect::<Vec<usize>>();
+        assert!(lengths == vec![68, 60, 68, 64, 64]);
+        assert!(chain.press_sequence("029A", 3).unwrap().len() == 68);
+        assert!(chain.press_sequence("029A", 1).unwrap().chars().filter(|x| *x == 'A').count() == 4);
+        assert!(KeypadChain::puzzle(25).press_sequence("029A", 26).is_err());
+        assert!(chain.cost("12B").is_err());
+        assert!(chain.cost_at("029A", 4).is_err());
+
+        // A phone style keypad with gaps top left and bottom right, typed through one directional keypad.
+        let phone: Keypad = Keypad::parse(&[" 12", "345", "6A"]).unwrap();
+        let mut routes: Vec<String> = phone.paths('A', '2').to_vec();
+        routes.sort();
+        assert!(routes == vec!["^>^", "^^>"]);
+        routes = phone.paths('6', '1').to_vec();
+        routes.sort();
+        assert!(routes == vec![">^^", "^>^"]);
+        let chain: KeypadChain = KeypadChain::new(vec![phone, Keypad::directional()]).unwrap();
+        assert!(chain.press_sequence("1A", 1).unwrap() == "^^AvvA");
+        assert!(chain.cost("1A").unwrap() == 6);
+
+        assert!(Keypad::parse(&["12", "3"]).is_err());
+        assert!(Keypad::parse(&["1A1"]).is_err());
+        assert!(Keypad::parse(&["1 A"]).is_err());
+        assert!(KeypadChain::new(vec![Keypad::numeric(), Keypad::numeric()]).is_err());
+    }
+
+    #[test]
+    fn simulate() {
+        let chain: KeypadChain = KeypadChain::puzzle(2);
+        assert!(chain.simulate("<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A").unwrap() == "029A");
+        assert!(chain.simulate("<v<A>>^AvA^A<vA<AA>>^AAvA<^A>AAvA^A<vA>^AA<A>A<v<A>A>^AAAvA<^A>A").unwrap() == "379A");
+        for code in ["029A", "980A", "179A", "456A", "379A"] {
+            assert!(chain.simulate(&chain.press_sequence(code, 3).unwrap()).unwrap() == code);
         }
+
+        let chain: KeypadChain = KeypadChain::puzzle(0);
+        assert!(chain.simulate("<A^A").unwrap() == "02");
+        assert!(chain.simulate("").unwrap() == "");
+        let error: String = chain.simulate("<<A").unwrap_err().to_string();
+        assert!(error == "the robot at keypad 0 panics, press 2 points the arm at a gap");
+        assert!(chain.simulate("^^^^A").is_err());
+        assert!(chain.simulate(">").is_err());
+        assert!(chain.simulate("1").is_err());
+
+        // The middle robot panics before anything reaches the numeric keypad.
+        assert!(KeypadChain::puzzle(1).simulate("<<A").unwrap_err().to_string().contains("keypad 1"));
+        assert!(Keypad::directional().replay("vA<A^A").unwrap() == ">v^");
+        assert!(Keypad::directional().replay("N").is_err());
     }
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 126384);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 94426);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 30);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 118392478819140);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
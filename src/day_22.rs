@@ -1,74 +1,197 @@
-const INPUTS_FOLDER: &str = "inputs/day_22";
+const DAY: usize = 22;
 
-use std::{collections::{HashMap, HashSet}, ops::BitXor};
+use std::cmp::Reverse;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{Answer, Solution};
 
+const PRUNE_MODULUS: u64 = 16777216;
+// New secret numbers each buyer has in a day, and how many price changes the monkey watches for.
+const STEPS: usize = 2000;
+const WINDOW: usize = 4;
+// Changes run from -9 to 9, so a window of them is a number in base 19. Bigger windows than this would need
+// too much memory to count.
+const CHANGE_BASE: usize = 19;
+const MAX_WINDOW: usize = 5;
 
-fn mix(a: usize, b: usize) -> usize {
-    return (a as i32).bitxor(b as i32) as usize;
+// Mixing is done in u128, where multiplying any u64 by 2048 still fits.
+fn mix(a: u128, b: u128) -> u128 {
+    return a ^ b;
 }
 
-fn prune(a: usize) -> usize {
-    return a % 16777216;
+fn prune(a: u128, modulus: u64) -> u64 {
+    return (a % modulus as u128) as u64;
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let secret_numbers: Vec<usize> = input_lines.iter().map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
+// A buyer's secret numbers, starting with the one they begin the day with.
+#[derive(Debug, Clone)]
+pub struct SecretNumbers {
+    secret: u64,
+    modulus: u64,
+}
+
+impl SecretNumbers {
+    pub fn new(seed: u64) -> Self {
+        return Self { secret: seed, modulus: PRUNE_MODULUS };
+    }
 
-    let mut results: Vec<usize> = Vec::new();
-    let mut difference_map: HashMap<Vec<i32>, Vec<usize>> = HashMap::new();
-    for s in secret_numbers {
-        let mut bananas: Vec<usize> = Vec::new();
-        let mut result = s;
-        bananas.push(result % 10);
-        for _ in 0..2000 {
-            result = prune(mix(result * 64, result));
-            result = prune(mix(result / 32, result));
-            result = prune(mix(result * 2048, result));
-            bananas.push(result % 10);
+    pub fn with_modulus(mut self, modulus: u64) -> Result<Self, AocError> {
+        if modulus == 0 {
+            return Err(AocError::parse("the prune modulus can't be 0"));
         }
-        println!("{}: {}", s, result);
-        results.push(result);
-        let banana_difference: Vec<i32> = (1..bananas.len()).map(|i| bananas[i] as i32 - bananas[i - 1] as i32).collect::<Vec<i32>>();
-        let banana_difference_vec: Vec<Vec<i32>> = (4..banana_difference.len()).map(|i| banana_difference[(i-4)..i].iter().copied().collect::<Vec<i32>>()).collect::<Vec<Vec<i32>>>();
-        let mut map_per_secret: HashMap<Vec<i32>, usize> = HashMap::new();
-        for (i, difference_vec) in banana_difference_vec.iter().enumerate() {
-            let bananas_bought: usize = bananas[i + 4];
-            if !map_per_secret.contains_key(difference_vec) {
-                map_per_secret.insert(difference_vec.clone(), bananas_bought);
-            } else {
-                // if bananas_bought > *map_per_secret.get(difference_vec).unwrap() {
-                //     map_per_secret.insert(difference_vec.clone(), bananas_bought);
-                // }
+        self.modulus = modulus;
+        return Ok(self);
+    }
+}
+
+impl Iterator for SecretNumbers {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current: u64 = self.secret;
+        let mut result: u64 = prune(mix(current as u128 * 64, current as u128), self.modulus);
+        result = prune(mix(result as u128 / 32, result as u128), self.modulus);
+        result = prune(mix(result as u128 * 2048, result as u128), self.modulus);
+        self.secret = result;
+        return Some(current);
+    }
+}
+
+// A window of price changes as one index, the first change being the most significant digit.
+pub fn encode_changes(changes: &[i32]) -> usize {
+    return changes.iter().fold(0, |index, x| index * CHANGE_BASE + (x + 9) as usize);
+}
+
+pub fn decode_changes(index: usize, window: usize) -> Vec<i32> {
+    let mut changes: Vec<i32> = (0..window).map(|i| (index / CHANGE_BASE.pow(i as u32) % CHANGE_BASE) as i32 - 9).collect::<Vec<i32>>();
+    changes.reverse();
+    return changes;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestSequence {
+    pub changes: Vec<i32>,
+    pub bananas: usize,
+    // What each buyer pays for the sequence, in the order they were given, when asked for.
+    pub per_buyer: Option<Vec<usize>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Market {
+    // Each buyer's prices, the last digit of each of their secret numbers.
+    prices: Vec<Vec<u8>>,
+    window: usize,
+}
+
+impl Market {
+    // Any source of secret numbers will do. Each buyer gets `steps` prices after their first.
+    pub fn new<I: Iterator<Item = u64>>(buyers: impl IntoIterator<Item = I>, steps: usize, window: usize) -> Result<Self, AocError> {
+        if window == 0 || window > MAX_WINDOW {
+            return Err(AocError::parse(format!("the window must be between 1 and {} changes, not {}", MAX_WINDOW, window)));
+        }
+        let prices: Vec<Vec<u8>> = buyers.into_iter().map(|x| x.take(steps + 1).map(|s| (s % 10) as u8).collect::<Vec<u8>>()).collect::<Vec<Vec<u8>>>();
+        return Ok(Self { prices, window });
+    }
+
+    pub fn from_seeds(seeds: &[u64], steps: usize, window: usize) -> Result<Self, AocError> {
+        return Self::new(seeds.iter().map(|x| SecretNumbers::new(*x)), steps, window);
+    }
+
+    // Each window of changes a buyer sees, as an index, along with the price it sells at. Only the first time
+    // a window turns up counts, since the monkey sells as soon as it sees it.
+    fn sales(&self, prices: &[u8]) -> Vec<(usize, usize)> {
+        let size: usize = CHANGE_BASE.pow(self.window as u32);
+        let mut sales: Vec<(usize, usize)> = Vec::new();
+        let mut index: usize = 0;
+        for i in 1..prices.len() {
+            index = (index * CHANGE_BASE + (prices[i] as i32 - prices[i - 1] as i32 + 9) as usize) % size;
+            if i >= self.window {
+                sales.push((index, prices[i] as usize));
             }
         }
+        return sales;
+    }
 
-        for (difference_vec, bananas) in map_per_secret {
-            if !difference_map.contains_key(&difference_vec) {
-                difference_map.insert(difference_vec.clone(), Vec::new());
+    // The window of changes that gets the most bananas over all the buyers, or None if nobody has enough prices.
+    pub fn best_sequence(&self, per_buyer: bool) -> Option<BestSequence> {
+        let size: usize = CHANGE_BASE.pow(self.window as u32);
+        let mut totals: Vec<usize> = vec![0; size];
+        // The last buyer to sell at each index, so later sightings from the same buyer are skipped.
+        let mut sold: Vec<usize> = vec![usize::MAX; size];
+        for (buyer, prices) in self.prices.iter().enumerate() {
+            for (index, price) in self.sales(prices) {
+                if sold[index] != buyer {
+                    sold[index] = buyer;
+                    totals[index] += price;
+                }
             }
-            difference_map.get_mut(&difference_vec).unwrap().push(bananas);
         }
+
+        let (best_index, bananas) = totals.iter().enumerate()
+            .filter(|x| sold[x.0] != usize::MAX)
+            .max_by_key(|x| (*x.1, Reverse(x.0)))?;
+        let per_buyer: Option<Vec<usize>> = per_buyer.then(|| self.prices.iter()
+            .map(|x| self.sales(x).iter().find(|s| s.0 == best_index).map(|s| s.1).unwrap_or(0))
+            .collect::<Vec<usize>>());
+        return Some(BestSequence { changes: decode_changes(best_index, self.window), bananas: *bananas, per_buyer });
     }
+}
+
+fn parse_seeds(input_lines: &[String]) -> Result<Vec<u64>, AocError> {
+    return input_lines.iter().enumerate()
+        .map(|(i, x)| x.parse::<u64>().map_err(|_| AocError::parse(format!("bad secret number {:?}", x)).at_line(i + 1)))
+        .collect::<Result<Vec<u64>, AocError>>();
+}
+
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return sell_bananas(&parse_seeds(&input_lines)?, part_2);
+}
+
+fn sell_bananas(secret_numbers: &[u64], part_2: bool) -> Result<usize, AocError> {
     if !part_2 {
-        return results.iter().sum::<usize>();
+        let mut total: u64 = 0;
+        for &s in secret_numbers {
+            let result: u64 = SecretNumbers::new(s).nth(STEPS).unwrap();
+            trace!("{}: {}", s, result);
+            total += result;
+        }
+        return Ok(total as usize);
     } else {
-        let mut max_bananas: usize = 0;
-        for (difference_vec, bananas)  in difference_map {
-            let banana_sum = bananas.iter().sum::<usize>();
-            if banana_sum > max_bananas {
-                max_bananas = banana_sum;
-            }
+        let market: Market = Market::from_seeds(secret_numbers, STEPS, WINDOW)?;
+        let best: BestSequence = market.best_sequence(false).ok_or(AocError::parse("no buyer has enough prices to sell on"))?;
+        debug!("Best changes {:?} get {} bananas", best.changes, best.bananas);
+        return Ok(best.bananas);
+    }
+}
 
-            if bananas.len() > 2 {
-                println!("Difference {:?} has {:?}", difference_vec, bananas );
-            }
-        }
+#[derive(Default)]
+pub struct Day22 {
+    secret_numbers: Vec<u64>,
+}
+
+impl Solution for Day22 {
+    fn day(&self) -> usize {
+        return 22;
+    }
 
-        return max_bananas;
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.secret_numbers = parse_seeds(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sell_bananas(&self.secret_numbers, false)?));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sell_bananas(&self.secret_numbers, true)?));
     }
 }
 
@@ -76,38 +199,69 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
         // Do a quick test here
     }
 
+    #[test]
+    fn secret_numbers() {
+        let secrets: Vec<u64> = SecretNumbers::new(123).skip(1).take(10).collect::<Vec<u64>>();
+        assert!(secrets == vec![15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432, 5908254]);
+        let results: Vec<u64> = [1, 10, 100, 2024].iter().map(|x| SecretNumbers::new(*x).nth(STEPS).unwrap()).collect::<Vec<u64>>();
+        assert!(results == vec![8685429, 4700978, 15273692, 8667524]);
+        assert!(SecretNumbers::new(123).with_modulus(1000).unwrap().take(3).collect::<Vec<u64>>() == vec![123, 512, 328]);
+        // Nothing overflows however big the seed or modulus.
+        assert!(SecretNumbers::new(u64::MAX).nth(1) == Some(127038));
+        assert!(SecretNumbers::new(123).with_modulus(u64::MAX).unwrap().skip(1).take(2).collect::<Vec<u64>>() == vec![15887950, 2114870555168]);
+        assert!(SecretNumbers::new(123).with_modulus(0).is_err());
+
+        assert!(encode_changes(&[-2, 1, -1, 3]) == 7 * 6859 + 10 * 361 + 8 * 19 + 12);
+        assert!(decode_changes(encode_changes(&[-2, 1, -1, 3]), 4) == vec![-2, 1, -1, 3]);
+        assert!(decode_changes(encode_changes(&[9, -9]), 2) == vec![9, -9]);
+    }
+
+    #[test]
+    fn market() {
+        let market: Market = Market::from_seeds(&[1, 2, 3, 2024], STEPS, WINDOW).unwrap();
+        let best: BestSequence = market.best_sequence(true).unwrap();
+        assert!(best.changes == vec![-2, 1, -1, 3]);
+        assert!(best.bananas == 23);
+        assert!(best.per_buyer == Some(vec![7, 7, 0, 9]));
+        assert!(market.best_sequence(false).unwrap().per_buyer.is_none());
+
+        // Prices 3 0 6 5 4 4 6 4 4 2 for the first ten numbers from 123.
+        let market: Market = Market::from_seeds(&[123], 9, WINDOW).unwrap();
+        assert!(market.best_sequence(false).unwrap() == BestSequence { changes: vec![-1, -1, 0, 2], bananas: 6, per_buyer: None });
+        // The generator is pluggable, so made up prices work just as well.
+        let market: Market = Market::new([vec![5, 7, 3].into_iter(), vec![1, 3, 2, 2].into_iter()], 3, 2).unwrap();
+        assert!(market.best_sequence(true).unwrap() == BestSequence { changes: vec![2, -4], bananas: 3, per_buyer: Some(vec![3, 0]) });
+
+        assert!(Market::from_seeds(&[123], 2, WINDOW).unwrap().best_sequence(false).is_none());
+        assert!(Market::from_seeds(&[123], STEPS, MAX_WINDOW + 1).is_err());
+        assert!(solve_lines(vec!["12x".to_string()], false).is_err());
+    }
+
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 37327623);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 14476723788);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_3.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 23);
+        answers::check(DAY, 2, "example_3", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1630);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
 
         // 1672 too high
     }
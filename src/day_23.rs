@@ -1,99 +1,64 @@
-const INPUTS_FOLDER: &str = "inputs/day_23";
+const DAY: usize = 23;
 
-use std::collections::{HashMap, HashSet};
+use crate::error::AocError;
+use crate::graph::Graph;
+use crate::inputs;
+use crate::log::{debug, info};
+use crate::solution::{Answer, Solution};
 
-use itertools::Itertools;
 
-use crate::generic;
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<String, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<String, AocError> {
+    return Ok(find_lan(&parse_network(&input_lines)?, part_2));
+}
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> String {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let mut computer_map: HashMap<String, HashSet<String>> = HashMap::new();
+fn parse_network(input_lines: &[String]) -> Result<Graph, AocError> {
+    let mut computer_map: Graph = Graph::new();
+    for (i, line) in input_lines.iter().enumerate() {
+        let (computer1, computer2) = line.split_once("-").ok_or(AocError::parse(format!("expected two computers joined by -, got {:?}", line)).at_line(i + 1))?;
+        computer_map.add_edge(computer1, computer2);
+    }
 
-    for line in input_lines {
-        let computer1: String = line[..2].to_string();
-        let computer2: String = line[3..].to_string();
+    debug!("Map built.");
+    return Ok(computer_map);
+}
 
-        if !computer_map.contains_key(&computer1) {
-            computer_map.insert(computer1.clone(), HashSet::new());
-        }
-        computer_map.get_mut(&computer1).unwrap().insert(computer2.clone());
+fn find_lan(computer_map: &Graph, part_2: bool) -> String {
+    if !part_2 {
+        let t_lans: Vec<Vec<usize>> = computer_map.with_name_prefix(computer_map.triangles(), "t");
+        return t_lans.len().to_string();
+    } else {
+        let max_result: Vec<usize> = computer_map.maximum_clique();
+        info!("Good result = {:?}", computer_map.password(&max_result));
+        return computer_map.password(&max_result);
+    }
+}
 
-        if !computer_map.contains_key(&computer2) {
-            computer_map.insert(computer2.clone(), HashSet::new());
-        }
-        computer_map.get_mut(&computer2).unwrap().insert(computer1.clone());
+#[derive(Default)]
+pub struct Day23 {
+    computer_map: Graph,
+}
 
+impl Solution for Day23 {
+    fn day(&self) -> usize {
+        return 23;
     }
 
-    println!("Map built.");
-
-    if !part_2 {
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.computer_map = parse_network(&input_lines)?;
+        return Ok(());
+    }
 
-        let mut connected_computers: HashMap<Vec<String>, bool> = HashMap::new();
-        for (c, c_map) in computer_map.iter() {
-            // println!("{}: {:?}", c, c_map);
-            if c_map.len() >= 2 {
-                for permutation in c_map.iter().cloned().permutations(2) {
-                    let mut attempt_connected: Vec<String> = permutation.clone();
-                    attempt_connected.push(c.clone());
-                    attempt_connected.sort();
-                    // println!("Checking {:?}", attempt_connected);
-                    if !connected_computers.contains_key(&attempt_connected) {
-                        if computer_map.get(&permutation[0]).unwrap().contains(&permutation[1]) && computer_map.get(&permutation[1]).unwrap().contains(&permutation[0]){
-                            // println!("\t{}: {:?} contains {}", permutation[0], computer_map.get(&permutation[0]).unwrap(), c);
-                            // println!("\t{}: {:?} contains {}", permutation[1], computer_map.get(&permutation[1]).unwrap(), c);
-                            connected_computers.insert(attempt_connected, true);
-                        } else {
-                            connected_computers.insert(attempt_connected, false);
-                        }
-                    }
-                }
-            }
-            // break;
-        }
-        
-        let mut sum_t_lans: usize = 0;
-        for (connection, is_connected) in connected_computers {
-            if is_connected {
-                for computer in connection {
-                    if computer.starts_with('t') {
-                        sum_t_lans += 1;
-                        break;
-                    }
-                }
-            }
-        }
-        return sum_t_lans.to_string();
-    } else {
-        let mut all_results: Vec<HashSet<String>> = Vec::new();
-        let mut result: HashSet<String> = HashSet::new();
-        let mut points: HashSet<String> = HashSet::from_iter(computer_map.keys().cloned());
-        let mut excluded: HashSet<String> = HashSet::new();
-        bron_kerbosch(&computer_map, &mut all_results, &mut result, &mut points, &mut excluded);
-        let mut max_result: Vec<&String> = Vec::from_iter(all_results.iter().max_by_key(|x| x.len()).unwrap());
-        max_result.sort();
-        println!("Good result = {:?}", max_result.iter().join(","));
-
-        return max_result.iter().join(",");
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(find_lan(&self.computer_map, false)));
     }
-}
 
-fn bron_kerbosch(computer_map: &HashMap<String, HashSet<String>>, all_results: &mut Vec<HashSet<String>>, result: &mut HashSet<String>, points: &mut HashSet<String>, excluded: &mut HashSet<String>) {
-    if points.is_empty() && excluded.is_empty() {
-        all_results.push(result.clone());
-    } else {
-        while points.len() > 0 {
-            let p: String = points.iter().next().cloned().unwrap();
-            let mut new_result: HashSet<String> = result.clone();
-            let mut good_neighbours: HashSet<String> = HashSet::from_iter(points.intersection(computer_map.get(&p).unwrap()).cloned());
-            let mut new_excluded: HashSet<String> = HashSet::from_iter(points.intersection(&excluded).cloned());
-            new_result.insert(p.clone());
-            bron_kerbosch(computer_map, all_results, &mut new_result, &mut good_neighbours, &mut new_excluded);
-            points.remove(&p);
-            excluded.insert(p);
-        }
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(find_lan(&self.computer_map, true)));
     }
 }
 
@@ -101,6 +66,7 @@ fn bron_kerbosch(computer_map: &HashMap<String, HashSet<String>>, all_results: &
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -115,30 +81,22 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "7");
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "1215");
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "co,de,ka,ta");
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "bm,by,dv,ep,ia,ja,jb,ks,lv,ol,oy,uz,yt");
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
         // "bm,by,dv,ep,ia,ja,jb,ks,lv,ol,oy,uz,yt"
     }
 }
\ No newline at end of file
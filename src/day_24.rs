@@ -1,25 +1,34 @@
-const INPUTS_FOLDER: &str = "inputs/day_24";
+const DAY: usize = 24;
 
-use std::{collections::{HashMap, HashSet}, ops::{BitAnd, BitXor}};
+use std::{collections::{HashMap, VecDeque}, fs, path::{Path, PathBuf}};
 
-use itertools::{all, Itertools};
+use itertools::Itertools;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{self, Answer, Solution};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum GateType {
+// The most wires find_adder_swaps will choose swaps from. Every set of disjoint pairs of n wires is a candidate,
+// 140152 of them for 12, so a few more than the puzzle's 8 is as far as it's worth trying.
+const MAX_SWAPPED_WIRES: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GateType {
     AND,
     OR,
     XOR,
 }
 
-impl From<&str> for GateType {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for GateType {
+    type Error = AocError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "AND" => Self::AND,
-            "OR" => Self::OR,
-            "XOR" => Self::XOR,
-            _ => panic!("Bad input in GateType::From<T>"),
+            "AND" => Ok(Self::AND),
+            "OR" => Ok(Self::OR),
+            "XOR" => Ok(Self::XOR),
+            _ => Err(AocError::parse(format!("bad gate type {:?}", value))),
         }
     }
 }
@@ -32,45 +41,42 @@ impl GateType {
             GateType::XOR => "XOR",
         }
     }
+
+    // Fill colours for the Graphviz export.
+    fn colour(&self) -> &str {
+        match self {
+            GateType::AND => "lightblue",
+            GateType::OR => "palegreen",
+            GateType::XOR => "lightsalmon",
+        }
+    }
+
+    fn apply(&self, first: usize, second: usize) -> usize {
+        return match self {
+            GateType::AND => first & second,
+            GateType::OR => first | second,
+            GateType::XOR => first ^ second,
+        };
+    }
 }
 
 #[derive(Debug, Clone)]
-struct Gate {
-    inputs: Vec<String>,
-    output: String,
-    gate_type: GateType,
+pub struct Gate {
+    // Sorted, so x00 always comes before y00.
+    pub inputs: Vec<String>,
+    pub output: String,
+    pub gate_type: GateType,
 }
 
 impl Gate {
-    fn calc(&self, wires: &mut HashMap<String, usize>) {
-        wires.insert(self.output.clone(), match self.gate_type {
-            GateType::AND => (self.inputs.iter().map(|x| wires.get(x).unwrap()).sum::<usize>() >= 2) as usize,
-            GateType::OR => (self.inputs.iter().map(|x| wires.get(x).unwrap()).sum::<usize>() >= 1) as usize,
-            GateType::XOR => (self.inputs.iter().map(|x| wires.get(x).unwrap()).sum::<usize>() == 1) as usize,
-        });
-    }
-
-    fn calc_dfs(&self, gates_map: &HashMap<String, Gate>, initial_wires: &HashMap<String, usize>, cheats: &HashMap<usize, usize>, depth: usize) -> usize {
-        let first_input: usize;
-        if initial_wires.contains_key(&self.inputs[0]) {
-            first_input = *initial_wires.get(&self.inputs[0]).unwrap();
-        } else {
-            first_input = gates_map.get(&self.inputs[0]).unwrap().calc_dfs(gates_map, initial_wires, cheats, depth + 1);
-        }
-
-        let second_input: usize;
-        if initial_wires.contains_key(&self.inputs[1]) {
-            second_input = *initial_wires.get(&self.inputs[1]).unwrap();
-        } else {
-            second_input = gates_map.get(&self.inputs[1]).unwrap().calc_dfs(gates_map, initial_wires, cheats, depth + 1);
-        }
-
-        println!("{}{}", "\t".repeat(depth), self.as_string());
-        return match self.gate_type {
-            GateType::AND => (first_input + second_input > 1) as usize,
-            GateType::OR => (first_input + second_input >= 1) as usize,
-            GateType::XOR => (first_input + second_input == 1) as usize,
-        };
+    fn parse(line: &str) -> Result<Self, AocError> {
+        let gate_list: Vec<&str> = line.split(" ").collect::<Vec<&str>>();
+        if gate_list.len() != 5 || gate_list[3] != "->" {
+            return Err(AocError::parse(format!("expected \"a OP b -> c\", got {:?}", line)));
+        }
+        let mut inputs: Vec<String> = vec![gate_list[0].to_string(), gate_list[2].to_string()];
+        inputs.sort();
+        return Ok(Self { inputs, output: gate_list[4].to_string(), gate_type: GateType::try_from(gate_list[1])? });
     }
 
     fn as_string(&self) -> String {
@@ -78,411 +84,396 @@ impl Gate {
     }
 }
 
+// A gate that doesn't fit the ripple-carry adder pattern. The bit comes from its x, y or z wire if it has one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub gate: String,
+    pub output: String,
+    pub bit: Option<usize>,
+    pub reason: String,
+}
 
+// The gates as a DAG, kept in an order where every gate comes after the gates driving its inputs so one pass
+// evaluates the lot.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    gates: Vec<Gate>,
+    // Wires that no gate drives. Each one needs a value to run the circuit.
+    inputs: Vec<String>,
+    order: Vec<usize>,
+}
 
-
-fn solve_puzzle(input_filename: String, part_2: bool) -> String {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let blank_line_index: usize = input_lines.iter().position(|x| x == "").unwrap();
-
-    let mut all_wires: HashMap<String, usize> = HashMap::new();
-    for wire_line in input_lines[..blank_line_index].iter() {
-        let mut wire_iterator = wire_line.split(": ");
-        let wire_name: String = wire_iterator.next().unwrap().to_string();
-        let wire_value: usize = wire_iterator.next().unwrap().parse::<usize>().unwrap();
-        all_wires.insert(wire_name, wire_value);
-    }
-
-    let mut all_gates: Vec<Gate> = Vec::new();
-    for gate_line in input_lines[(blank_line_index + 1)..].iter() {
-        let gate_list: Vec<String> = gate_line.split(" ").map(|x| x.to_string()).collect::<Vec<String>>();
-        let mut gate_inputs: Vec<String> = vec![gate_list[0].clone(), gate_list[2].clone()];
-        gate_inputs.sort();
-        all_gates.push(Gate { inputs: gate_inputs, output: gate_list[4].clone(), gate_type: GateType::from(gate_list[1].as_str()) });
-    }
-    
-    println!("{:?}", all_wires);
-
-    if !part_2 {
-        all_wires = process_gates(&all_gates, &all_wires);
-        return get_number(&all_wires, 'z').to_string();
-    } else {
-        let x_number: usize = get_number(&all_wires, 'x');
-        let y_number: usize = get_number(&all_wires, 'y');
-        let target_z: usize = x_number + y_number;
-        println!("X = {:b}", x_number);
-        println!("Y = {:b}", y_number);
-        println!("Z = {:b}", target_z);
-        let no_swap_wires = process_gates(&all_gates, &all_wires);
-        let no_swap_z = get_number(&no_swap_wires, 'z');
-        println!("Z = {:b}", no_swap_z);
-
-        let mut z_gates: Vec<&Gate> = all_gates.iter().filter(|x| x.output.starts_with('z')).collect::<Vec<&Gate>>();
-        z_gates.sort_by_key(|x| x.output.clone());
-        
-
-        let gates_map: HashMap<String, Gate> = HashMap::from_iter(all_gates.iter().map(|x| (x.output.clone(), x.clone())));
-        
-        for z_gate in z_gates.iter() {
-            // println!("{} is made up from {:?}", z_gate.output, trace_gates(&gates_map, gates_map.get(&z_gate.output).unwrap().clone()));
-        }
-        
-
-        let mut gate_aliasing: HashMap<String, String> = HashMap::new();
-        let mut new_gates: Vec<Gate> = Vec::new();
-        for g in all_gates.iter() {
-            if g.inputs[0].starts_with("x") || g.inputs[1].starts_with("x") || g.inputs[0].starts_with("y") || g.inputs[1].starts_with("y") {
-                let output_alias: String = g.inputs[0].to_owned() + g.gate_type.to_string() + g.inputs[1].as_str();
-                gate_aliasing.insert(g.output.clone(), output_alias);
-            } else {
-                new_gates.push(g.clone());
+impl Circuit {
+    pub fn new(gates: Vec<Gate>) -> Result<Self, AocError> {
+        let mut drivers: HashMap<String, usize> = HashMap::new();
+        for (i, g) in gates.iter().enumerate() {
+            if drivers.insert(g.output.clone(), i).is_some() {
+                return Err(AocError::parse(format!("wire {} is driven by more than one gate", g.output)));
             }
         }
-
-        // println!("gate_aliasing = {:?}", gate_aliasing);
-
-        for g in new_gates.iter_mut() {
-            if gate_aliasing.contains_key(&g.inputs[0]) {
-                g.inputs[0] = gate_aliasing.get(&g.inputs[0]).unwrap().clone();
-            }
-            if gate_aliasing.contains_key(&g.inputs[1]) {
-                g.inputs[1] = gate_aliasing.get(&g.inputs[1]).unwrap().clone();
+        let inputs: Vec<String> = gates.iter()
+            .flat_map(|g| g.inputs.iter())
+            .filter(|x| !drivers.contains_key(*x))
+            .unique()
+            .sorted()
+            .cloned()
+            .collect::<Vec<String>>();
+
+        // A gate is ready once all the gates driving it are placed. Anything never ready is on or after a cycle.
+        let mut waiting: Vec<usize> = vec![0; gates.len()];
+        let mut consumers: Vec<Vec<usize>> = vec![Vec::new(); gates.len()];
+        for (i, g) in gates.iter().enumerate() {
+            for d in g.inputs.iter().filter_map(|x| drivers.get(x)) {
+                waiting[i] += 1;
+                consumers[*d].push(i);
             }
         }
-
-        let mut gates_to_process = new_gates.clone();
-        let mut processed_gates: Vec<Gate> = Vec::new();
-        let mut loop_count: usize = 0;
-        while gates_to_process.len() > 0 {
-            // println!("{} gates left", gates_to_process.len());
-            gate_aliasing = HashMap::new();
-            let mut remaining_gates: Vec<Gate> = Vec::new();
-            for g in gates_to_process.iter() {
-                if g.inputs[0].len() > 3 && g.inputs[1].len() > 3 {
-                    let output_alias: String = "(".to_owned() + g.inputs[0].as_str() + ")" + g.gate_type.to_string() + "(" + g.inputs[1].as_str() + ")";
-                    gate_aliasing.insert(g.output.clone(), output_alias);
-                    processed_gates.push(g.clone());
-                } else {
-                    remaining_gates.push(g.clone());
+        let mut ready: VecDeque<usize> = (0..gates.len()).filter(|x| waiting[*x] == 0).collect::<VecDeque<usize>>();
+        let mut order: Vec<usize> = Vec::new();
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+            for c in consumers[i].iter() {
+                waiting[*c] -= 1;
+                if waiting[*c] == 0 {
+                    ready.push_back(*c);
                 }
             }
-
-            for g in remaining_gates.iter_mut() {
-                if gate_aliasing.contains_key(&g.inputs[0]) {
-                    g.inputs[0] = gate_aliasing.get(&g.inputs[0]).unwrap().clone();
-                }
-                if gate_aliasing.contains_key(&g.inputs[1]) {
-                    g.inputs[1] = gate_aliasing.get(&g.inputs[1]).unwrap().clone();
-                }
-            }
-
-            gates_to_process = remaining_gates.clone();
         }
 
-        for g in processed_gates {
-            // println!("{:?}", g);
+        if order.len() < gates.len() {
+            let cycle: Vec<String> = find_cycle(&gates, &drivers, &waiting);
+            return Err(AocError::parse(format!("the gates form a cycle: {}", cycle.join(" -> "))));
         }
+        return Ok(Self { gates, inputs, order });
+    }
 
-        println!("Calc Z gates using DFS");
-        let mut bit_filter: usize = 1;
-        let mut cheats: HashMap<usize, usize> = HashMap::new();
-        for i in 0..z_gates.len() {
-            let z_number: usize = get_z_number_dfs(i, &z_gates, &gates_map, &all_wires, &cheats);
-            println!("{} = {:050b}", z_gates[i].output, z_number);
-            if z_number.bitand(bit_filter) != target_z.bitand(bit_filter) {
-                println!("Bad bit at index {}", i);
-                cheats.insert(i, (z_number >> i).bitxor(1));
-                break;
-            }
-
-            bit_filter = (bit_filter << 1) + 1;
-        }
-        println!("Cheats = {:?}", cheats);
+    pub fn gates(&self) -> &[Gate] {
+        return &self.gates;
+    }
 
+    pub fn inputs(&self) -> &[String] {
+        return &self.inputs;
+    }
 
-        for i in 0..z_gates.len() {
-            if gates_map.contains_key(&z_gates[i].inputs[0]) && gates_map.contains_key(&z_gates[i].inputs[1]) {
-                let input_gate1: &Gate = gates_map.get(&z_gates[i].inputs[0]).unwrap();
-                let input_gate2: &Gate = gates_map.get(&z_gates[i].inputs[1]).unwrap();
+    // The wires starting with the given letter, least significant bit first.
+    pub fn bus(&self, prefix: char) -> Vec<String> {
+        return self.inputs.iter()
+            .chain(self.gates.iter().map(|x| &x.output))
+            .filter(|x| x.starts_with(prefix))
+            .sorted()
+            .cloned()
+            .collect::<Vec<String>>();
+    }
 
-                if !gate_is_xy_xor(input_gate1) && !gate_is_xy_xor(input_gate2) {
-                    println!("z{:02} is missing X Y XOR input gate.", i);
-                }
-            } else {
-                println!("z{:02} is missing input gates.", i);
-            }
+    // Every wire's value. The given wires are kept, and every input wire must be among them.
+    pub fn evaluate(&self, wires: &HashMap<String, usize>) -> Result<HashMap<String, usize>, AocError> {
+        if let Some(missing) = self.inputs.iter().find(|x| !wires.contains_key(*x)) {
+            return Err(AocError::parse(format!("no value for input wire {}", missing)));
         }
+        let mut all_wires: HashMap<String, usize> = wires.clone();
+        for i in self.order.iter() {
+            let g: &Gate = &self.gates[*i];
+            let value: usize = g.gate_type.apply(all_wires[&g.inputs[0]], all_wires[&g.inputs[1]]);
+            all_wires.insert(g.output.clone(), value);
+        }
+        return Ok(all_wires);
+    }
 
-        println!("Get z05 DFS");
-        z_gates[5].calc_dfs(&gates_map, &all_wires, &cheats, 0);
-
-
-        // z00 = x00 XOR y00
-        // c00 = x00 AND y00
-        //
-        // z01 = (x01 XOR y01) XOR c00
-        // c01 = ((x01 XOR y01) AND c00) OR (x01 AND y01)
-        //
-        // z02 = (x02 XOR y02) XOR c01
-        // c02 = ((x02 XOR y02) AND c01) OR (x02 AND y02)
-
-        let mut xy_gates: Vec<&Gate> = all_gates.iter().filter(|x| x.inputs[0].starts_with("x") && x.inputs[1].starts_with("y")).collect::<Vec<&Gate>>();
-        xy_gates.sort_by_key(|x| x.inputs[0].clone());
-        let mut xy_XOR_gates: Vec<&Gate> = xy_gates.iter().cloned().filter(|x| x.gate_type == GateType::XOR).collect::<Vec<&Gate>>();
-        let mut xy_AND_gates: Vec<&Gate> = xy_gates.iter().cloned().filter(|x| x.gate_type == GateType::AND).collect::<Vec<&Gate>>();
-
-        let xy_XOR_gates_outputs: Vec<String> = xy_XOR_gates.iter().map(|x| x.output.clone()).collect::<Vec<String>>();
-
-        println!("XOR Gates");
-        println!("{:?}", xy_XOR_gates);
-        println!("AND Gates");
-        println!("{:?}", xy_AND_gates);
-
-        let mut carriers: Vec<&Gate> = Vec::new();
-        let mut actual_z: Vec<&Gate> = Vec::new();
-        carriers.push(xy_AND_gates[0]);
-        actual_z.push(xy_XOR_gates[0]);
-
-        // for i in 1..z_gates.len() {
-        //     let find_actual_z: Option<Gate> = find_gate(&all_gates, vec![xy_XOR_gates[i].output.clone(), carriers[i-1].output.clone()]);
-
-        // }
+    // Put the numbers on the x and y buses and read the z bus.
+    pub fn simulate(&self, x: usize, y: usize) -> Result<usize, AocError> {
+        let mut wires: HashMap<String, usize> = HashMap::new();
+        set_number(&mut wires, &self.bus('x'), x);
+        set_number(&mut wires, &self.bus('y'), y);
+        return Ok(get_number(&self.evaluate(&wires)?, 'z'));
+    }
 
-        for (i, g) in xy_AND_gates.iter().enumerate() {
-            let parent_gates: Vec<&Gate> = all_gates.iter().filter(|x| x.inputs[0] == g.output || x.inputs[1] == g.output).collect::<Vec<&Gate>>();
-            if parent_gates.len() != 1 {
-                println!("{} Gate {:02} does not have exactly 1 parent. It has {} parents.", g.as_string(), i, parent_gates.len());
-            } else {
-                // We know this is the carrier gate for i.
-                let mut parent_inputs: Vec<String> = parent_gates[0].inputs.clone();
-                if parent_inputs[0] == g.output {
-                    parent_inputs.remove(0);
-                } else if parent_inputs[1] == g.output {
-                    parent_inputs.remove(1);
-                } else {
-                    panic!("Cant fidn the right parent inputs!!!");
-                }
+    // The same circuit with the outputs of two gates swapped. Swaps that make a cycle are an error.
+    pub fn with_swap(&self, first: &str, second: &str) -> Result<Self, AocError> {
+        return self.with_swaps(&[(first.to_string(), second.to_string())]);
+    }
 
-                let other_input_gate = gates_map.get(&parent_inputs[0]).unwrap();
-                if !xy_XOR_gates_outputs.contains(&other_input_gate.inputs[0]) && !xy_XOR_gates_outputs.contains(&other_input_gate.inputs[1]) {
-                    println!("\t\t{}", other_input_gate.as_string());
+    // Several swaps at once, so a cycle part way through doesn't matter if the later swaps undo it.
+    pub fn with_swaps(&self, swaps: &[(String, String)]) -> Result<Self, AocError> {
+        let mut renames: HashMap<&str, &str> = HashMap::new();
+        for (first, second) in swaps {
+            for wire in [first, second] {
+                if !self.gates.iter().any(|x| x.output == *wire) {
+                    return Err(AocError::parse(format!("no gate drives wire {}", wire)));
                 }
-
+            }
+            if renames.insert(first, second).is_some() || renames.insert(second, first).is_some() {
+                return Err(AocError::parse(format!("{} and {} can't be swapped, one of them is already swapped", first, second)));
             }
         }
+        let mut gates: Vec<Gate> = self.gates.clone();
+        for g in gates.iter_mut() {
+            if let Some(other) = renames.get(g.output.as_str()) {
+                g.output = other.to_string();
+            }
+        }
+        return Self::new(gates);
+    }
 
-        for (i, g) in xy_XOR_gates.iter().enumerate() {
-            let parent_gates: Vec<&Gate> = all_gates.iter().filter(|x| x.inputs[0] == g.output || x.inputs[1] == g.output).collect::<Vec<&Gate>>();
-            if parent_gates.len() != 2 {
-                println!("{} Gate {:02} does not have exactly two parents. It has {} parents.", g.as_string(), i, parent_gates.len());
+    // Check each gate against its role in a ripple-carry adder of the x and y buses. Bit i should be
+    //   z(i) = (x(i) XOR y(i)) XOR c(i-1)
+    //   c(i) = ((x(i) XOR y(i)) AND c(i-1)) OR (x(i) AND y(i))
+    // with z00 = x00 XOR y00, c00 = x00 AND y00 and the last carry going straight to the top z bit.
+    pub fn adder_violations(&self) -> Vec<Violation> {
+        let top_z: String = format!("z{:02}", self.bus('x').len());
+        let mut consumers: HashMap<&str, Vec<&GateType>> = HashMap::new();
+        for g in self.gates.iter() {
+            for input in g.inputs.iter() {
+                consumers.entry(input.as_str()).or_default().push(&g.gate_type);
             }
         }
 
-        // Known wrong gates
-        // XOR has to take X and Y as input or output a Z.
-        // Z can only be output from a XOR.
-        // X AND Y only appears twice in the input. Once as a input and once as an output.
-        //
-        // NOT THIS ONE.... x00 AND y00 -> pgc
-        // x06 AND y06 -> z06
-        // x10 AND y10 -> nbd
-        // y10 XOR x10 -> kbs
-        // tsm OR dnc -> z20
-        // MAYBE qtf XOR nsp -> ksv
-        // MAYBE bnp XOR mtq -> tqq
-        // MAYBE hpp XOR cmj -> ckb
-        // cmj AND hpp -> z39
-        
-        
-        
-        // y20 XOR x20 -> mtq
-
-
-        let target_outputs: Vec<String> = vec![
-            "z06".to_string(), "nbd".to_string(), 
-            "kbs".to_string(), "z20".to_string(), "ksv".to_string(),
-            "tqq".to_string(), "ckb".to_string(), "z39".to_string()];
-        let target_outputs_indices: Vec<usize> = target_outputs.iter().map(|x| all_gates.iter().position(|g| g.output == *x).unwrap()).collect::<Vec<usize>>();
-        let mut available_indices: Vec<usize> = (0..all_gates.len()).filter(|x| !target_outputs_indices.contains(x)).collect::<Vec<usize>>();
-
-        let mut finished = false;
-        let mut final_answer: String = String::new();
-        for combo in available_indices.iter().combinations(1) {
-            println!("Running combo {:?}", combo);
-            let mut attempt_indices = target_outputs_indices.clone();
-            // for x in combo {
-            //     attempt_indices.push(*x);
-            // }
-
-            // println!("Checking {:?}", attempt_indices);
-            
-            // for permut in attempt_indices.iter().permutations(attempt_indices.len()) {
-            for permut in get_pairs(attempt_indices) {
-                // println!("\tstarting with {:?}", permut);
-                let mut gate_copy: Vec<Gate> = all_gates.clone();
-                swap_gates(&mut gate_copy, &permut);
-                let check_wires = process_gates(&gate_copy, &all_wires);
-                let check_z_number: usize = get_number(&check_wires, 'z');
-                // println!("Z = {:50b}", check_z_number);
-                if check_z_number == target_z {
-                    println!("Z = {:50b}", check_z_number);
-                    println!("Found Z at permut = {:?}", permut);
-                    println!("{:?}", permut.iter().map(|x| all_gates[*x].output.clone()).collect::<Vec<String>>());
-                    final_answer = permut.iter().map(|x| all_gates[*x].output.clone()).sorted().collect::<Vec<String>>().join(",");
-                    finished = true;
-                    break;
+        let mut violations: Vec<Violation> = Vec::new();
+        for g in self.gates.iter() {
+            let from_xy: bool = g.inputs[0].starts_with('x') && g.inputs[1].starts_with('y');
+            let bit: Option<usize> = if from_xy { bit_of(&g.inputs[0]) } else { bit_of(&g.output) };
+            let first_bit: bool = from_xy && bit == Some(0);
+            let feeds = |t: GateType| consumers.get(g.output.as_str()).is_some_and(|x| x.contains(&&t));
+
+            let reason: Option<&str> = if g.output == top_z {
+                (g.gate_type != GateType::OR).then_some("the top z bit must be the last carry, from an OR gate")
+            } else if g.output.starts_with('z') && g.gate_type != GateType::XOR {
+                Some("z bits must come from an XOR gate")
+            } else {
+                match g.gate_type {
+                    GateType::XOR if first_bit => (g.output != "z00").then_some("x00 XOR y00 must drive z00"),
+                    GateType::XOR if gate_is_xy_xor(g) => (!feeds(GateType::XOR)).then_some("x XOR y must feed the XOR making its z bit"),
+                    GateType::XOR => (!g.output.starts_with('z')).then_some("an XOR with the carry in must drive a z bit"),
+                    GateType::AND if first_bit => (!feeds(GateType::XOR)).then_some("x00 AND y00 is the first carry and must feed the next z bit's XOR"),
+                    GateType::AND => (!feeds(GateType::OR)).then_some("AND gates must feed the OR making the carry"),
+                    GateType::OR => (!feeds(GateType::XOR)).then_some("a carry must feed the next z bit's XOR"),
                 }
+            };
+            if let Some(reason) = reason {
+                violations.push(Violation { gate: g.as_string(), output: g.output.clone(), bit, reason: reason.to_string() });
             }
-            // break;
-            if finished {
-                break;
-            }
-
         }
+        violations.sort_by_key(|x| x.output.clone());
+        return violations;
+    }
 
-        // Up to 32 before.
-        // started at 10:37pm.
-        
-        return final_answer;
+    // Spot checks rather than every sum: each bit on its own and with a carry in, plus a carry running the
+    // full width.
+    pub fn adds_correctly(&self) -> bool {
+        let width: usize = self.bus('x').len();
+        if width == 0 || width >= usize::BITS as usize {
+            return false;
+        }
+        let all_ones: usize = (1 << width) - 1;
+        let mut checks: Vec<(usize, usize)> = vec![(0, 0), (all_ones, 1), (all_ones, all_ones)];
+        for i in 0..width {
+            checks.extend([(1 << i, 0), (0, 1 << i), (1 << i, 1 << i)]);
+        }
+        return checks.iter().all(|(x, y)| self.simulate(*x, *y).is_ok_and(|z| z == x + y));
     }
-}
 
-fn get_pairs(target_list: Vec<usize>) -> Vec<Vec<usize>> {
-    let mut result: Vec<Vec<usize>> = Vec::new();
-    let mut possible_set: HashSet<Vec<Vec<usize>>> = HashSet::new();
-    for c1 in target_list.iter().copied().combinations(2) {
-        let remaining = target_list.iter().copied().filter(|x| !c1.contains(&x)).collect::<Vec<usize>>();
-        for c2 in remaining.iter().copied().combinations(2) {
-            let remaining2 = remaining.iter().copied().filter(|x| !c2.contains(&x)).collect::<Vec<usize>>();
-            for c3 in remaining2.iter().copied().combinations(2) {
-                let mut possibilities: Vec<Vec<usize>> = Vec::new();
-                let remaining3 = remaining2.iter().copied().filter(|x| !c3.contains(&x)).collect::<Vec<usize>>();
-
-                possibilities.push(c1.clone());
-                possibilities.push(c2.clone());
-                possibilities.push(c3);
-                possibilities.push(remaining3);
-                possibilities.sort();
-                if !possible_set.contains(&possibilities) {
-                    result.push(possibilities.concat());
-                    possible_set.insert(possibilities);
-                }
+    // The fewest swaps that turn the circuit into a working adder, chosen from the wires out of gates that break the
+    // pattern. Every set of one swap is tried, then every set of two and so on, so a flagged wire that's fine where
+    // it is gets left alone.
+    pub fn find_adder_swaps(&self) -> Result<Vec<(String, String)>, AocError> {
+        let wires: Vec<String> = self.adder_violations().into_iter().map(|x| x.output).unique().collect::<Vec<String>>();
+        if wires.len() > MAX_SWAPPED_WIRES {
+            return Err(AocError::parse(format!("{} wires break the adder pattern, more than the {} that can be swapped around", wires.len(), MAX_SWAPPED_WIRES)));
+        }
+        let mut swaps: Vec<(String, String)> = Vec::new();
+        for size in 0..=(wires.len() / 2) {
+            if self.try_swap_sets(&wires, size, &mut swaps) {
+                return Ok(swaps);
             }
         }
+        return Err(AocError::parse(format!("no way of swapping {} makes the circuit add", wires.join(","))));
     }
 
-    return result;
-}
+    // Tries every set of `size` swaps among the wires, depth first: the first wire either swaps with one of the
+    // others, or is left alone. Only the set being tried is kept.
+    fn try_swap_sets(&self, wires: &[String], size: usize, swaps: &mut Vec<(String, String)>) -> bool {
+        if size == 0 {
+            return self.fixed_by(swaps);
+        }
+        if wires.len() < 2 * size {
+            return false;
+        }
+        for i in 1..wires.len() {
+            let rest: Vec<String> = wires[1..].iter().enumerate().filter(|x| x.0 + 1 != i).map(|x| x.1.clone()).collect::<Vec<String>>();
+            swaps.push((wires[0].clone(), wires[i].clone()));
+            if self.try_swap_sets(&rest, size - 1, swaps) {
+                return true;
+            }
+            swaps.pop();
+        }
+        return self.try_swap_sets(&wires[1..], size, swaps);
+    }
 
-fn swap_gates(gates: &mut Vec<Gate>, swap_indices: &Vec<usize>) {
-    if swap_indices.len() != 8 {
-        panic!("Bad amount of swap indices given to swap_gates function");
+    // Swapping outputs doesn't change whether the other gates fit the pattern, so the swaps fix the adder when
+    // none of the swapped gates breaks it any more and the sums come out right.
+    fn fixed_by(&self, swaps: &[(String, String)]) -> bool {
+        let swapped: Vec<&String> = swaps.iter().flat_map(|x| [&x.0, &x.1]).collect::<Vec<&String>>();
+        return self.with_swaps(swaps).is_ok_and(|x| x.adder_violations().iter().all(|v| !swapped.contains(&&v.output)) && x.adds_correctly());
     }
 
-    let mut temp_output: String;
-    temp_output = gates[swap_indices[1]].output.clone();
-    gates[swap_indices[1]].output = gates[swap_indices[0]].output.clone();
-    gates[swap_indices[0]].output = temp_output;
+    // Gate outputs that no gate reads, which for an adder is the z bus.
+    pub fn outputs(&self) -> Vec<String> {
+        return self.gates.iter()
+            .map(|x| &x.output)
+            .filter(|x| !self.gates.iter().any(|g| g.inputs.contains(x)))
+            .sorted()
+            .cloned()
+            .collect::<Vec<String>>();
+    }
 
-    temp_output = gates[swap_indices[3]].output.clone();
-    gates[swap_indices[3]].output = gates[swap_indices[2]].output.clone();
-    gates[swap_indices[2]].output = temp_output;
+    // One line per gate in evaluation order, e.g. "z01 = XOR(s01, c00)", after the input and output wires.
+    pub fn to_netlist(&self) -> Vec<String> {
+        let mut netlist: Vec<String> = vec![format!("INPUT {}", self.inputs.join(" ")), format!("OUTPUT {}", self.outputs().join(" "))];
+        for i in self.order.iter() {
+            let g: &Gate = &self.gates[*i];
+            netlist.push(format!("{} = {}({}, {})", g.output, g.gate_type.to_string(), g.inputs[0], g.inputs[1]));
+        }
+        return netlist;
+    }
 
-    temp_output = gates[swap_indices[5]].output.clone();
-    gates[swap_indices[5]].output = gates[swap_indices[4]].output.clone();
-    gates[swap_indices[4]].output = temp_output;
+    // A Graphviz digraph with a node per gate, named after its output wire and coloured by type. The x, y and z
+    // buses each get a box of their own, and an edge runs along every wire.
+    pub fn to_dot(&self) -> String {
+        let mut dot: Vec<String> = vec!["digraph circuit {".to_string(), "    rankdir=LR;".to_string(), "    node [style=filled];".to_string()];
+        for prefix in ['x', 'y', 'z'] {
+            let bus: Vec<String> = self.bus(prefix);
+            if bus.is_empty() {
+                continue;
+            }
+            dot.push(format!("    subgraph cluster_{} {{", prefix));
+            dot.push(format!("        label=\"{}\";", prefix));
+            for wire in bus {
+                match self.gates.iter().find(|x| x.output == wire) {
+                    Some(g) => dot.push(format!("        {} [label=\"{}\\n{}\", fillcolor={}];", wire, g.gate_type.to_string(), wire, g.gate_type.colour())),
+                    None => dot.push(format!("        {} [shape=box, fillcolor=white];", wire)),
+                }
+            }
+            dot.push("    }".to_string());
+        }
+        for g in self.gates.iter().filter(|x| bit_of(&x.output).is_none()) {
+            dot.push(format!("    {} [label=\"{}\\n{}\", fillcolor={}];", g.output, g.gate_type.to_string(), g.output, g.gate_type.colour()));
+        }
+        for g in self.gates.iter().sorted_by_key(|x| x.output.clone()) {
+            for input in g.inputs.iter() {
+                dot.push(format!("    {} -> {};", input, g.output));
+            }
+        }
+        dot.push("}".to_string());
+        return dot.join("\n") + "\n";
+    }
 
-    temp_output = gates[swap_indices[7]].output.clone();
-    gates[swap_indices[7]].output = gates[swap_indices[6]].output.clone();
-    gates[swap_indices[6]].output = temp_output;
+    pub fn write_dot(&self, path: &Path) -> Result<(), AocError> {
+        return fs::write(path, self.to_dot()).map_err(|e| AocError::Write { path: path.display().to_string(), source: e });
+    }
 
+    pub fn write_netlist(&self, path: &Path) -> Result<(), AocError> {
+        return fs::write(path, self.to_netlist().join("\n") + "\n").map_err(|e| AocError::Write { path: path.display().to_string(), source: e });
+    }
 }
 
-fn find_gate(all_gates: &Vec<Gate>, inputs: Vec<String>) -> Option<Gate> {
-    let mut found_gate: Option<Gate> = None;
-    for g in all_gates {
-        if (g.inputs[0] == inputs[0] && g.inputs[1] == inputs[1] || g.inputs[1] == inputs[0] && g.inputs[0] == inputs[1]) {
-            found_gate = Some(g.clone());
+// Walk back from a gate that never became ready through drivers that never did either, until a gate repeats.
+// Returns the output wires round the loop in the order the signal flows, with the first repeated at the end.
+fn find_cycle(gates: &[Gate], drivers: &HashMap<String, usize>, waiting: &[usize]) -> Vec<String> {
+    let mut path: Vec<usize> = vec![(0..gates.len()).find(|x| waiting[*x] > 0).unwrap()];
+    loop {
+        let current: usize = *path.last().unwrap();
+        let previous: usize = gates[current].inputs.iter()
+            .filter_map(|x| drivers.get(x))
+            .copied()
+            .find(|x| waiting[*x] > 0)
+            .unwrap();
+        if let Some(start) = path.iter().position(|x| *x == previous) {
+            let mut cycle: Vec<String> = path[start..].iter().rev().map(|x| gates[*x].output.clone()).collect::<Vec<String>>();
+            cycle.push(cycle[0].clone());
+            return cycle;
         }
+        path.push(previous);
     }
-    return found_gate
 }
 
-fn gate_is_xy_xor(gate: &Gate) -> bool {
-    return (gate.gate_type == GateType::XOR && (
-        (gate.inputs[0].starts_with("x") && gate.inputs[1].starts_with("y")) ||
-        (gate.inputs[1].starts_with("x") && gate.inputs[0].starts_with("y"))
-    ));
-}
+// The initial wire values and the circuit, either side of the blank line.
+pub fn parse_lines(input_lines: &[String]) -> Result<(HashMap<String, usize>, Circuit), AocError> {
+    let blank_line_index: usize = input_lines.iter().position(|x| x.is_empty()).ok_or(AocError::parse("expected a blank line between the wires and the gates"))?;
 
-fn get_z_number_dfs(bits: usize, z_gates: &Vec<&Gate>, gates_map: &HashMap<String, Gate>, initial_wires: &HashMap<String, usize>, cheats: &HashMap<usize, usize>) -> usize {
-    let mut number: usize = 0;
-    let mut count: usize = 0;
-    for z in z_gates[..(bits+1)].iter() {
-        number += z.calc_dfs(gates_map, initial_wires, cheats, 0) << count;
-        count += 1;
+    let mut all_wires: HashMap<String, usize> = HashMap::new();
+    for (i, wire_line) in input_lines[..blank_line_index].iter().enumerate() {
+        let (wire_name, wire_value) = wire_line.split_once(": ").ok_or(AocError::parse(format!("expected \"wire: value\", got {:?}", wire_line)).at_line(i + 1))?;
+        let wire_value: usize = match wire_value {
+            "0" => 0,
+            "1" => 1,
+            _ => return Err(AocError::parse(format!("bad wire value {:?}", wire_value)).at_line(i + 1)),
+        };
+        all_wires.insert(wire_name.to_string(), wire_value);
     }
 
-    return number;
+    let mut all_gates: Vec<Gate> = Vec::new();
+    for (i, gate_line) in input_lines.iter().enumerate().skip(blank_line_index + 1) {
+        all_gates.push(Gate::parse(gate_line).map_err(|e| e.at_line(i + 1))?);
+    }
+    return Ok((all_wires, Circuit::new(all_gates)?));
 }
 
-fn trace_gates(gates_map: &HashMap<String, Gate>, start_gate: Gate) -> (String, String) {
-    let mut gates_to_follow: Vec<Gate> = Vec::new();
-    gates_to_follow.push(start_gate);
-
-    let mut x_input: String = String::new();
-    let mut y_input: String = String::new();
-
-    while gates_to_follow.len() > 0 {
-        let target_gate = gates_to_follow.remove(0);
-        for input_wire in target_gate.inputs {
-            if input_wire.starts_with('x') {
-                x_input = input_wire;
-            } else if input_wire.starts_with('y') {
-                y_input = input_wire;
-            } else {
-                gates_to_follow.push(gates_map.get(&input_wire).unwrap().clone());
-            }
-        }
-        if x_input.len() != 0 && y_input.len() != 0 {
-            break;
-        }
+// Write the input's circuit as a Graphviz graph and/or a netlist, for `aoc circuit`. Returns the files written.
+pub fn export_circuit(input_lines: &[String], dot: Option<&Path>, netlist: Option<&Path>) -> Result<Vec<PathBuf>, AocError> {
+    let (_, circuit) = parse_lines(input_lines)?;
+    let mut written: Vec<PathBuf> = Vec::new();
+    if let Some(path) = dot {
+        circuit.write_dot(path)?;
+        written.push(path.to_path_buf());
+    }
+    if let Some(path) = netlist {
+        circuit.write_netlist(path)?;
+        written.push(path.to_path_buf());
     }
+    return Ok(written);
+}
 
-    return (x_input, y_input);
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<String, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
 }
 
-fn process_gates(all_gates: &Vec<Gate>, all_wires: &HashMap<String, usize>) -> HashMap<String, usize> {
-    let mut gates: Vec<Gate> = all_gates.clone();
-    let mut wires: HashMap<String, usize> = all_wires.clone();
-    let mut count: usize = 0;
-    let mut previous_gate_len: usize = all_gates.len();
-    let mut bad_count: usize = 0;
-    let mut gate_count: usize = all_gates.len() * 2;
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<String, AocError> {
+    let (all_wires, circuit) = parse_lines(&input_lines)?;
+    return run_circuit(&all_wires, &circuit, part_2);
+}
 
-    while gates.len() > 0 && bad_count < gate_count {
-        // println!("Gates = {:?}", gates);
-        let target_gate: Gate = gates.remove(0);
-        if wires.contains_key(&target_gate.inputs[0]) && wires.contains_key(&target_gate.inputs[1]) {
-            target_gate.calc(&mut wires);
-        } else {
-            gates.push(target_gate);
-        }
-        count += 1;
+fn run_circuit(all_wires: &HashMap<String, usize>, circuit: &Circuit, part_2: bool) -> Result<String, AocError> {
+    debug!("{:?}", all_wires);
+    for line in circuit.to_netlist() {
+        trace!("{}", line);
+    }
 
-        if previous_gate_len == gates.len() {
-            // println!("No change to gate len! Exiting early soon.");
-            bad_count += 1;
-        } else {
-            bad_count = 0;
+    if !part_2 {
+        return Ok(get_number(&circuit.evaluate(all_wires)?, 'z').to_string());
+    } else {
+        for violation in circuit.adder_violations() {
+            debug!("{:?}", violation);
         }
+        let swaps: Vec<(String, String)> = circuit.find_adder_swaps()?;
+        debug!("Swaps = {:?}", swaps);
+        return Ok(swaps.iter().flat_map(|x| [x.0.clone(), x.1.clone()]).sorted().join(","));
+    }
+}
 
-        previous_gate_len = gates.len();
+// The bit a wire like x07 or z12 carries.
+fn bit_of(wire: &str) -> Option<usize> {
+    if !wire.starts_with(['x', 'y', 'z']) {
+        return None;
     }
-    // println!("Count = {}", count);
+    return wire[1..].parse::<usize>().ok();
+}
 
-    return wires;
-} 
+fn gate_is_xy_xor(gate: &Gate) -> bool {
+    return (gate.gate_type == GateType::XOR && (
+        (gate.inputs[0].starts_with("x") && gate.inputs[1].starts_with("y")) ||
+        (gate.inputs[1].starts_with("x") && gate.inputs[0].starts_with("y"))
+    ));
+}
 
 fn get_number(all_wires: &HashMap<String, usize>, target_char: char) -> usize {
     let mut number: usize = 0;
@@ -498,23 +489,46 @@ fn get_number(all_wires: &HashMap<String, usize>, target_char: char) -> usize {
     return number;
 }
 
-fn all_pairs(input_vec: &Vec<usize>) -> Vec<Vec<usize>> {
-    let mut return_vec: Vec<Vec<usize>> = Vec::new();
-    for i in 0..input_vec.len() {
-        for j in (i+1)..input_vec.len() {
-            return_vec.push(vec![input_vec[i], input_vec[j]]);
-        }
+// The inverse of get_number, for the named wires least significant bit first.
+fn set_number(all_wires: &mut HashMap<String, usize>, wire_names: &[String], number: usize) {
+    for (i, wire_name) in wire_names.iter().enumerate() {
+        all_wires.insert(wire_name.clone(), number.checked_shr(i as u32).unwrap_or(0) & 1);
     }
+}
 
-    return return_vec;
+#[derive(Default)]
+pub struct Day24 {
+    all_wires: HashMap<String, usize>,
+    circuit: Option<Circuit>,
+}
+
+impl Solution for Day24 {
+    fn day(&self) -> usize {
+        return 24;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        let (all_wires, circuit) = parse_lines(&input_lines)?;
+        self.all_wires = all_wires;
+        self.circuit = Some(circuit);
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(run_circuit(&self.all_wires, solution::parsed(&self.circuit, DAY)?, false)?));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(run_circuit(&self.all_wires, solution::parsed(&self.circuit, DAY)?, true)?));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use generic::append_to_file;
-
     use super::*;
+    use crate::answers;
+    use std::env;
 
     #[test]
     fn quick_test() {
@@ -533,54 +547,143 @@ mod tests {
         // println!("Len = {}", permutations.len());
     }
 
+    fn gates(lines: &[&str]) -> Vec<Gate> {
+        return lines.iter().map(|x| Gate::parse(x).unwrap()).collect::<Vec<Gate>>();
+    }
+
+    // A two bit ripple-carry adder.
+    fn adder() -> Circuit {
+        return Circuit::new(gates(&[
+            "x00 XOR y00 -> z00", "x00 AND y00 -> c00",
+            "x01 XOR y01 -> s01", "s01 XOR c00 -> z01", "s01 AND c00 -> d01", "x01 AND y01 -> e01", "d01 OR e01 -> z02",
+        ])).unwrap();
+    }
+
     #[test]
-    fn quicker_test() {
-        let original = vec![1,2,3,4,5,6,7,8];
-        for p in get_pairs(original) {
-            // println!("{:?}", p);
-            append_to_file("inputs\\day_24\\quick_output.txt".to_string(), format!("{:?}", p));
-            
+    fn circuit() {
+        let lines: Vec<String> = ["x00: 1", "x01: 1", "x02: 1", "y00: 0", "y01: 1", "y02: 0", "", "x00 AND y00 -> z00", "x01 XOR y01 -> z01", "x02 OR y02 -> z02"]
+            .iter().map(|x| x.to_string()).collect();
+        assert!(solve_lines(lines, false).unwrap() == "4");
+
+        let circuit: Circuit = adder();
+        assert!(circuit.inputs() == ["x00", "x01", "y00", "y01"]);
+        assert!(circuit.bus('z') == vec!["z00", "z01", "z02"]);
+        for x in 0..4 {
+            for y in 0..4 {
+                assert!(circuit.simulate(x, y).unwrap() == x + y);
+            }
         }
-        // println!("{}", pairs.len());
+        assert!(circuit.evaluate(&HashMap::from([("x00".to_string(), 1)])).is_err());
+
+        // s01 would feed d01, which would now drive s01.
+        let error: String = circuit.with_swap("s01", "z02").unwrap_err().to_string();
+        assert!(error.contains("cycle") && (error.contains("s01 -> d01 -> s01") || error.contains("d01 -> s01 -> d01")));
+        assert!(circuit.with_swap("z00", "z01").unwrap().simulate(1, 0).unwrap() == 2);
+        assert!(circuit.with_swap("z00", "nope").is_err());
+        assert!(Circuit::new(gates(&["a AND b -> c", "a OR b -> c"])).is_err());
     }
 
-    #[test]
-    fn quickerer_test() {
-        let original = vec![1,2,3,4,5,6,7,8];
-        let pairs = all_pairs(&original);
-        for p in pairs.iter().combinations(4) {
-            // println!("{:?}", p);
-            append_to_file("inputs\\day_24\\quick_output2.txt".to_string(), format!("{:?}", p));
-            
+    // A ripple-carry adder with the same layout as the puzzle's, s, d, e and c being the x XOR y, carry AND,
+    // x AND y and carry out wires.
+    fn ripple_adder(width: usize) -> Circuit {
+        let mut lines: Vec<String> = vec!["x00 XOR y00 -> z00".to_string(), "x00 AND y00 -> c00".to_string()];
+        for i in 1..width {
+            let carry_out: String = if i + 1 == width { format!("z{:02}", width) } else { format!("c{:02}", i) };
+            lines.push(format!("x{0:02} XOR y{0:02} -> s{0:02}", i));
+            lines.push(format!("s{0:02} XOR c{1:02} -> z{0:02}", i, i - 1));
+            lines.push(format!("c{1:02} AND s{0:02} -> d{0:02}", i, i - 1));
+            lines.push(format!("y{0:02} AND x{0:02} -> e{0:02}", i));
+            lines.push(format!("d{0:02} OR e{0:02} -> {1}", i, carry_out));
         }
-        // println!("{}", pairs.len());
+        return Circuit::new(lines.iter().map(|x| Gate::parse(x).unwrap()).collect::<Vec<Gate>>()).unwrap();
     }
 
     #[test]
-    fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "2024");
+    fn adder_swaps() {
+        let circuit: Circuit = ripple_adder(8);
+        assert!(circuit.adds_correctly());
+        assert!(circuit.adder_violations().is_empty());
+        assert!(circuit.find_adder_swaps().unwrap().is_empty());
+
+        let swaps: Vec<(String, String)> = [("s03", "e03"), ("z05", "d05"), ("z07", "e07")].iter().map(|x| (x.0.to_string(), x.1.to_string())).collect();
+        let broken: Circuit = circuit.with_swaps(&swaps).unwrap();
+        assert!(!broken.adds_correctly());
+        let wrong: Vec<String> = broken.adder_violations().into_iter().map(|x| x.output).collect::<Vec<String>>();
+        assert!(wrong == vec!["d05", "e03", "e07", "s03", "z05", "z07"]);
+        let found: Vec<(String, String)> = broken.find_adder_swaps().unwrap();
+        assert!(found.len() == 3);
+        assert!(broken.with_swaps(&found).unwrap().adds_correctly());
+        assert!(found.iter().flat_map(|x| [x.0.clone(), x.1.clone()]).sorted().join(",") == "d05,e03,e07,s03,z05,z07");
+
+        // Too many wires to try every way of pairing them.
+        let swaps: Vec<(String, String)> = (1..8).map(|i| (format!("s{:02}", i), format!("e{:02}", i))).collect::<Vec<(String, String)>>();
+        let error: String = circuit.with_swaps(&swaps).unwrap().find_adder_swaps().unwrap_err().to_string();
+        assert!(error == "14 wires break the adder pattern, more than the 12 that can be swapped around");
+
+        // A spare x AND y gate reads nothing, so it breaks the pattern but not the sums, and the fewest swaps leave it be.
+        let mut gates: Vec<Gate> = broken.gates().to_vec();
+        gates.push(Gate::parse("x04 AND y04 -> q04").unwrap());
+        let spare: Circuit = Circuit::new(gates).unwrap();
+        assert!(spare.adder_violations().iter().any(|x| x.output == "q04"));
+        let found: Vec<(String, String)> = spare.find_adder_swaps().unwrap();
+        assert!(found.iter().flat_map(|x| [x.0.clone(), x.1.clone()]).sorted().join(",") == "d05,e03,e07,s03,z05,z07");
+
+        // Swapping a carry with the z bit it feeds loops it back on itself.
+        assert!(circuit.with_swap("c03", "z04").is_err());
+        assert!(circuit.with_swaps(&[("z01".to_string(), "z02".to_string()), ("z02".to_string(), "z03".to_string())]).is_err());
     }
 
     #[test]
-    fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "49574189473968");
+    fn export() {
+        let circuit: Circuit = ripple_adder(2);
+        assert!(circuit.outputs() == vec!["z00", "z01", "z02"]);
+        let netlist: Vec<String> = circuit.to_netlist();
+        assert!(netlist[0] == "INPUT x00 x01 y00 y01" && netlist[1] == "OUTPUT z00 z01 z02");
+        assert!(netlist.len() == 2 + circuit.gates().len());
+        assert!(netlist.contains(&"z01 = XOR(c00, s01)".to_string()));
+        // Every wire is defined before it's used.
+        let defined: Vec<&str> = netlist[2..].iter().map(|x| x.split(" = ").next().unwrap()).collect::<Vec<&str>>();
+        assert!(defined.iter().position(|x| *x == "c00") < defined.iter().position(|x| *x == "d01"));
+
+        let dot: String = circuit.to_dot();
+        assert!(dot.starts_with("digraph circuit {\n") && dot.ends_with("}\n"));
+        assert!(dot.contains("    subgraph cluster_x {\n        label=\"x\";\n        x00 [shape=box, fillcolor=white];\n        x01 [shape=box, fillcolor=white];\n    }"));
+        assert!(dot.contains("        z02 [label=\"OR\\nz02\", fillcolor=palegreen];"));
+        assert!(dot.contains("    c00 [label=\"AND\\nc00\", fillcolor=lightblue];"));
+        assert!(dot.contains("    s01 -> z01;"));
+        assert!(dot.matches(" -> ").count() == 2 * circuit.gates().len());
+
+        let path: PathBuf = env::temp_dir().join(format!("aoc_day_24_{}.dot", std::process::id()));
+        circuit.write_dot(&path).unwrap();
+        assert!(fs::read_to_string(&path).unwrap() == dot);
+        circuit.write_netlist(&path).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().lines().collect::<Vec<&str>>() == netlist);
+        fs::remove_file(&path).unwrap();
+
+        // The same files from puzzle input, as `aoc circuit` writes them.
+        let input_lines: Vec<String> = ["x00: 1", "y00: 1", "", "x00 XOR y00 -> z00", "x00 AND y00 -> z01"].iter().map(|x| x.to_string()).collect();
+        let netlist_path: PathBuf = env::temp_dir().join(format!("aoc_day_24_{}.txt", std::process::id()));
+        let written: Vec<PathBuf> = export_circuit(&input_lines, Some(&path), Some(&netlist_path)).unwrap();
+        assert!(written == vec![path.clone(), netlist_path.clone()]);
+        assert!(fs::read_to_string(&path).unwrap().contains("    x00 -> z01;"));
+        assert!(fs::read_to_string(&netlist_path).unwrap() == "INPUT x00 y00\nOUTPUT z00 z01\nz00 = XOR(x00, y00)\nz01 = AND(x00, y00)\n");
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&netlist_path).unwrap();
+        assert!(export_circuit(&input_lines[3..], Some(&path), None).is_err());
     }
 
     #[test]
-    fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "30");
+    fn example_1() {
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
+    }
+
+    #[test]
+    fn part_1() {
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == "7185540");
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
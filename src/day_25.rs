@@ -1,8 +1,11 @@
-const INPUTS_FOLDER: &str = "inputs/day_25";
+const DAY: usize = 25;
 
 use itertools::all;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::info;
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, PartialEq, Eq)]
 enum TumblerType {
@@ -16,35 +19,59 @@ struct Tumbler {
     columns: Vec<usize>,
 }
 
-impl From<Vec<String>> for Tumbler {
-    fn from(value: Vec<String>) -> Self {
+impl Tumbler {
+    fn new(value: &[String], first_line: usize) -> Result<Self, AocError> {
+        if value.len() != 7 {
+            return Err(AocError::parse(format!("expected 7 rows, got {}", value.len())).at_line(first_line));
+        }
+        for (i, row) in value.iter().enumerate() {
+            if row.len() != value[0].len() {
+                return Err(AocError::parse(format!("expected {} columns, got {}", value[0].len(), row.len())).at_line(first_line + i));
+            }
+            if let Some(j) = row.chars().position(|x| x != '#' && x != '.') {
+                return Err(AocError::parse(format!("bad tumbler character {:?}", row.chars().nth(j).unwrap())).at_column(j + 1).at_line(first_line + i));
+            }
+        }
+
         let tumbler_type: TumblerType = match value[0].as_str() {
             "#####" => TumblerType::Lock,
             _ => TumblerType::Key,
         };
 
-        let columns: Vec<usize> = (0..value[0].len()).map(|i| value.iter().map(|row| row.chars().nth(i).unwrap()).filter(|x| *x == '#').collect::<Vec<char>>().len() - 1).collect::<Vec<usize>>();
+        let columns: Vec<usize> = (0..value[0].len()).map(|i| value.iter().map(|row| row.chars().nth(i).unwrap()).filter(|x| *x == '#').collect::<Vec<char>>().len().saturating_sub(1)).collect::<Vec<usize>>();
 
-        return Self { tumbler_type: tumbler_type, columns: columns };
+        return Ok(Self { tumbler_type, columns });
     }
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, _part_2: bool) -> Result<usize, AocError> {
+    return Ok(count_fits(&parse_tumblers(&input_lines)?));
+}
+
+// Locks and keys are 7 rows each, with a blank line between them.
+fn parse_tumblers(input_lines: &[String]) -> Result<Vec<Tumbler>, AocError> {
     let mut tumblers: Vec<Tumbler> = Vec::new();
 
     let mut i: usize = 0;
     while i < input_lines.len() {
-        tumblers.push(Tumbler::from(input_lines[i..(i+7)].to_vec()));
+        tumblers.push(Tumbler::new(&input_lines[i..(i+7).min(input_lines.len())], i + 1)?);
         i += 8;
     }
+    return Ok(tumblers);
+}
 
+fn count_fits(tumblers: &[Tumbler]) -> usize {
     let keys: Vec<&Tumbler> = tumblers.iter().filter(|x| x.tumbler_type == TumblerType::Key).collect::<Vec<&Tumbler>>();
     let locks: Vec<&Tumbler> = tumblers.iter().filter(|x| x.tumbler_type == TumblerType::Lock).collect::<Vec<&Tumbler>>();
 
-    println!("There are {} keys", keys.len());
-    println!("There are {} locks", locks.len());
+    info!("There are {} keys", keys.len());
+    info!("There are {} locks", locks.len());
 
     let mut count_unique_combinations: usize = 0;
     for k in keys.iter() {
@@ -69,9 +96,35 @@ fn key_fits_lock(key: &Tumbler, lock: &Tumbler) -> bool {
     return all_fit;
 }
 
+#[derive(Default)]
+pub struct Day25 {
+    tumblers: Vec<Tumbler>,
+}
+
+impl Solution for Day25 {
+    fn day(&self) -> usize {
+        return 25;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.tumblers = parse_tumblers(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_fits(&self.tumblers)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_fits(&self.tumblers)));
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -80,29 +133,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 3);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2993);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 30);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 7185540);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
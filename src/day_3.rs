@@ -1,8 +1,12 @@
-const INPUTS_FOLDER: &str = "inputs/day_3";
+const DAY: usize = 3;
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::debug;
+use crate::solution::{Answer, Solution};
 use regex::Regex;
 use itertools::Itertools;
 
@@ -20,18 +24,16 @@ struct Mul {
     string_index: usize,
 }
 
-impl From<String> for Mul {
-    fn from(value: String) -> Self {
-        let mut just_numbers: String = value[4..].to_string();
-        just_numbers = just_numbers[..just_numbers.len()-1].to_string();
-        let split_numbers: Vec<i32> = just_numbers.split(",").map(|x| x.parse::<i32>().expect("Bad number given in input!")).collect::<Vec<i32>>();
-        return Self{x: split_numbers[0], y: split_numbers[1], string_index: 0};
-    }
-}
+impl FromStr for Mul {
+    type Err = AocError;
 
-impl From<&str> for Mul {
-    fn from(value: &str) -> Self{
-        return Self::from(value.to_string());
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let just_numbers: &str = value.strip_prefix("mul(").and_then(|x| x.strip_suffix(")")).ok_or(AocError::parse(format!("bad mul {:?}", value)))?;
+        let split_numbers: Vec<&str> = just_numbers.split(",").collect::<Vec<&str>>();
+        if split_numbers.len() != 2 {
+            return Err(AocError::parse(format!("expected two numbers in {:?}", value)));
+        }
+        return Ok(Self{x: error::parse_number::<i32>(split_numbers[0])?, y: error::parse_number::<i32>(split_numbers[1])?, string_index: 0});
     }
 }
 
@@ -46,54 +48,100 @@ impl Mul {
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> i32 {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let full_input: String = input_lines.join("");
-    let re_mul = Regex::new(r"mul\([0-9]{1,3}.[0-9]{1,3}\)").unwrap();
-    let re_do = Regex::new(r"do\(\)").unwrap();
-    let re_dont = Regex::new(r"don't\(\)").unwrap();
-    let mut all_muls: Vec<Mul> = Vec::new();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<i32, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
-    let mut all_instructions: HashMap<usize, Instruction> = HashMap::new();
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<i32, AocError> {
+    let memory: Memory = Memory::new(&input_lines)?;
+    return Ok(memory.sum_products(part_2));
+}
 
-    for x in re_mul.find_iter(&full_input) {
-        let mut new_mul = Mul::from(x.as_str());
-        new_mul.set_string_index(x.start());
-        all_muls.push(new_mul);
-        all_instructions.insert(x.start(), Instruction::Mul);
-    }
+// Every mul, do and don't in the corrupted memory, with the instructions keyed by where they start.
+#[derive(Debug, Default)]
+struct Memory {
+    all_muls: Vec<Mul>,
+    all_instructions: HashMap<usize, Instruction>,
+}
 
-    for x in re_do.find_iter(&full_input) {
-        all_instructions.insert(x.start(), Instruction::Do);
-    }
+impl Memory {
+    fn new(input_lines: &[String]) -> Result<Self, AocError> {
+        let full_input: String = input_lines.join("");
+        let re_mul = Regex::new(r"mul\([0-9]{1,3}.[0-9]{1,3}\)").unwrap();
+        let re_do = Regex::new(r"do\(\)").unwrap();
+        let re_dont = Regex::new(r"don't\(\)").unwrap();
+        let mut all_muls: Vec<Mul> = Vec::new();
+
+        let mut all_instructions: HashMap<usize, Instruction> = HashMap::new();
+
+        for x in re_mul.find_iter(&full_input) {
+            // The lines are joined, so the column is an offset into the whole input.
+            let mut new_mul = x.as_str().parse::<Mul>().map_err(|e| e.at_column(x.start() + 1))?;
+            new_mul.set_string_index(x.start());
+            all_muls.push(new_mul);
+            all_instructions.insert(x.start(), Instruction::Mul);
+        }
+
+        for x in re_do.find_iter(&full_input) {
+            all_instructions.insert(x.start(), Instruction::Do);
+        }
+
+        for x in re_dont.find_iter(&full_input) {
+            all_instructions.insert(x.start(), Instruction::Dont);
+        }
 
-    for x in re_dont.find_iter(&full_input) {
-        all_instructions.insert(x.start(), Instruction::Dont);
+        debug!("{:?}", all_instructions);
+
+        return Ok(Self { all_muls, all_instructions });
     }
-    
-    println!("{:?}", all_instructions);
-    
-
-    if !part_2 {
-        return all_muls.iter().map(|x| x.multiply()).sum();
-    } else {
-        let mut do_is_on = true;
-        let mut mul_index = 0;
-        let mut full_sum = 0;
-        for start_index in all_instructions.keys().sorted() {
-
-            match all_instructions[start_index] {
-                Instruction::Do => do_is_on = true,
-                Instruction::Dont => do_is_on = false,
-                Instruction::Mul => {
-                    if do_is_on {
-                        full_sum += all_muls[mul_index].multiply();
+
+    fn sum_products(&self, part_2: bool) -> i32 {
+        if !part_2 {
+            return self.all_muls.iter().map(|x| x.multiply()).sum();
+        } else {
+            let mut do_is_on = true;
+            let mut mul_index = 0;
+            let mut full_sum = 0;
+            for start_index in self.all_instructions.keys().sorted() {
+
+                match self.all_instructions[start_index] {
+                    Instruction::Do => do_is_on = true,
+                    Instruction::Dont => do_is_on = false,
+                    Instruction::Mul => {
+                        if do_is_on {
+                            full_sum += self.all_muls[mul_index].multiply();
+                        }
+                        mul_index += 1;
                     }
-                    mul_index += 1;
                 }
             }
+            return full_sum;
         }
-        return full_sum
+    }
+}
+
+#[derive(Default)]
+pub struct Day3 {
+    memory: Memory,
+}
+
+impl Solution for Day3 {
+    fn day(&self) -> usize {
+        return 3;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.memory = Memory::new(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(self.memory.sum_products(false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(self.memory.sum_products(true)));
     }
 }
 
@@ -101,6 +149,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -109,29 +158,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 161);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 175615763);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 48);
+        answers::check(DAY, 2, "example_2", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 74361272);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
-const INPUTS_FOLDER: &str = "inputs/day_5";
+const DAY: usize = 5;
 
-use std::{cmp::Ordering, collections::{HashMap, HashSet}};
+use std::{cmp::Ordering, collections::{HashMap, HashSet}, str::FromStr};
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, PartialEq)]
 enum OrderingRuleResult {
@@ -17,10 +20,15 @@ struct OrderingRule {
     Y: usize,
 }
 
-impl From<String> for OrderingRule {
-    fn from(value: String) -> Self {
-        let values: Vec<usize> = value.split("|").map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>();
-        return Self{X: values[0], Y: values[1]};
+impl FromStr for OrderingRule {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let values: Vec<usize> = value.split("|").map(error::parse_number::<usize>).collect::<Result<Vec<usize>, AocError>>()?;
+        if values.len() != 2 {
+            return Err(AocError::parse(format!("expected a rule X|Y, got {:?}", value)));
+        }
+        return Ok(Self{X: values[0], Y: values[1]});
     }
 }
 
@@ -29,9 +37,11 @@ struct PageProduction {
     pages: Vec<usize>,
 }
 
-impl From<String> for PageProduction {
-    fn from(value: String) -> Self {
-        return Self{pages: value.split(",").map(|x| x.parse::<usize>().unwrap()).collect::<Vec<usize>>()};
+impl FromStr for PageProduction {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        return Ok(Self{pages: value.split(",").map(error::parse_number::<usize>).collect::<Result<Vec<usize>, AocError>>()?});
     }
 }
 
@@ -56,13 +66,25 @@ impl PageProduction {
     }
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let line_break_index: usize = input_lines.iter().position(|x| x == "").unwrap();
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
 
-    let rules: Vec<OrderingRule> = input_lines[..line_break_index].iter().map(|x| OrderingRule::from(x.clone())).collect::<Vec<OrderingRule>>();
-    let page_productions: Vec<PageProduction> = input_lines[(line_break_index + 1)..].iter().map(|x| PageProduction::from(x.clone())).collect::<Vec<PageProduction>>();
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let (rules, page_productions) = parse_input(&input_lines)?;
+    return Ok(sum_middle_pages(&rules, &page_productions, part_2));
+}
+
+fn parse_input(input_lines: &[String]) -> Result<(Vec<OrderingRule>, Vec<PageProduction>), AocError> {
+    let line_break_index: usize = input_lines.iter().position(|x| x.is_empty()).ok_or(AocError::parse("expected a blank line between rules and pages"))?;
 
+    let rules: Vec<OrderingRule> = error::parse_lines(&input_lines[..line_break_index], |x| x.parse::<OrderingRule>())?;
+    let page_productions: Vec<PageProduction> = error::parse_lines_from(&input_lines[(line_break_index + 1)..], line_break_index + 2, |x| x.parse::<PageProduction>())?;
+    return Ok((rules, page_productions));
+}
+
+fn sum_middle_pages(rules: &[OrderingRule], page_productions: &[PageProduction], part_2: bool) -> usize {
     let mut good_productions_sum = 0;
     let mut good_productions_mid_sum = 0;
 
@@ -71,7 +93,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
 
     for p in page_productions {
         let mut not_satisfied = false;
-        println!("Checking {:?}", p);
+        trace!("Checking {:?}", p);
         for r in rules.iter() {
             let rule_result: OrderingRuleResult = p.check_rule(r);
             // println!("Rule {:?} = {:?}", r, rule_result);
@@ -90,13 +112,13 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     if part_2 {
         good_productions_mid_sum = 0;
         let mut rules_set: HashMap<usize, Vec<usize>> = HashMap::new();
-        for r in rules {
+        for r in rules.iter() {
             rules_set.entry(r.Y).or_insert(vec![]).push(r.X);
         }
-        println!("{:?}", rules_set);
+        debug!("{:?}", rules_set);
 
         for p in bad_productions.iter_mut() {
-            println!("\tOld: {:?}", p.pages);
+            trace!("\tOld: {:?}", p.pages);
             p.pages.sort_by(|a, b| {
                 if rules_set.contains_key(a) {
                     if rules_set.get(a).unwrap().contains(b) {
@@ -108,7 +130,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
                     return Ordering::Equal;
                 }
             });
-            println!("\tNew: {:?}", p.pages);
+            trace!("\tNew: {:?}", p.pages);
             good_productions_mid_sum += p.get_middle_page();
         }
     }
@@ -116,10 +138,36 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     return good_productions_mid_sum;
 }
 
+#[derive(Default)]
+pub struct Day5 {
+    rules: Vec<OrderingRule>,
+    page_productions: Vec<PageProduction>,
+}
+
+impl Solution for Day5 {
+    fn day(&self) -> usize {
+        return 5;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        (self.rules, self.page_productions) = parse_input(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_middle_pages(&self.rules, &self.page_productions, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(sum_middle_pages(&self.rules, &self.page_productions, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -142,29 +190,31 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 143);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 4959);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 123);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 4655);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
+    }
+
+    #[test]
+    fn bad_input() {
+        let input_lines: Vec<String> = ["47|53", "97|13", "", "75,47,61", "75,x,61"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let error = solve_lines(input_lines, false).unwrap_err();
+        assert!(error.to_string() == "line 5: bad number \"x\"");
+
+        let input_lines: Vec<String> = ["47|53", "75,47,61"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        assert!(solve_lines(input_lines, false).is_err());
     }
-}
\ No newline at end of file
+}
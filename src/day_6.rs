@@ -1,9 +1,12 @@
-const INPUTS_FOLDER: &str = "inputs/day_6";
+const DAY: usize = 6;
 
-use core::fmt;
 use std::collections::HashMap;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::generic::{Direction, Grid, Position};
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{self, Answer, Solution};
 
 #[derive(Debug, PartialEq, Clone)]
 enum Tile {
@@ -13,47 +16,15 @@ enum Tile {
     Walked,
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Self::Empty,
-            '#' => Self::Obstacle,
-            '^' | '>' | 'V' | '<' => Self::Guard,
-            _ => panic!("bad char input for Tile"),
-        }
-    }
-}
-
-#[derive(Clone, PartialEq, Hash, Eq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
+impl TryFrom<char> for Tile {
+    type Error = AocError;
 
-impl fmt::Debug for Position {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "(r: {}, c: {})", self.row, self.col)
-    }
-}
-
-impl Position {
-    fn walk(&self, steps: usize, direction: Direction) -> Self {
-        let mut new_position = self.clone();
-        match direction {
-            Direction::Up => new_position.row -= steps,
-            Direction::Down => new_position.row += steps,
-            Direction::Left => new_position.col -= steps,
-            Direction::Right => new_position.col += steps,
-        }
-        return new_position
-    }
-
-    fn from_obstacle(row: usize, col: usize, direction: &Direction) -> Self {
-        match direction {
-            Direction::Up => Self{row: row + 1, col: col},
-            Direction::Down => Self{row: row - 1, col: col},
-            Direction::Left => Self{row: row, col: col + 1},
-            Direction::Right => Self{row: row, col: col - 1},
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Empty),
+            '#' => Ok(Self::Obstacle),
+            '^' | '>' | 'v' | 'V' | '<' => Ok(Self::Guard),
+            _ => Err(AocError::parse(format!("bad map tile {:?}", value))),
         }
     }
 }
@@ -64,80 +35,24 @@ struct DetailedTile {
     approach_direction: Option<Direction>,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
-        match value {
-            '^' => Self::Up,
-            '>' => Self::Right,
-            'V' => Self::Down,
-            '<' => Self::Left,
-            _ => panic!("Bad char given for direction!"),
-        }
-    }
-}
-
-impl Direction {
-    fn iter() -> impl Iterator<Item = Direction> {
-        return [
-            Self::Up,
-            Self::Down,
-            Self::Left,
-            Self::Right,
-        ].iter().copied();
-    }
-
-    fn rotate_90_CW(&self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-            Self::Right => Self::Down,
-        }
-    }
-
-    fn rotate_90_CCW(&self) -> Self {
-        match self {
-            Self::Up => Self::Left,
-            Self::Down => Self::Right,
-            Self::Left => Self::Down,
-            Self::Right => Self::Up,
-        }
-    }
-
-    fn reverse(&self) -> Self {
-        match self {
-            Self::Up => Self::Down,
-            Self::Down => Self::Up,
-            Self::Left => Self::Right,
-            Self::Right => Self::Left,
-        }
-    }
+fn position_from_obstacle(row: usize, col: usize, direction: &Direction) -> Position {
+    return Position{row, col}.walk(1, direction.reverse());
 }
 
 #[derive(Debug, Clone, PartialEq)]
 struct TileMap {
-    tiles: Vec<Vec<Tile>>,
-}
-
-impl From<&Vec<String>> for TileMap {
-    fn from(value: &Vec<String>) -> Self {
-        return Self{tiles: value.iter().map(|x| x.chars().map(|y| Tile::from(y)).collect::<Vec<Tile>>()).collect::<Vec<Vec<Tile>>>()};
-    }
+    tiles: Grid<Tile>,
+    guard_start: Position,
+    guard_start_direction: Direction,
 }
 
 impl TileMap {
-    fn get_guard_start(&self) -> (usize, usize) {
-        let row: usize = self.tiles.iter().position(|x| x.contains(&Tile::Guard)).expect("Cant find guard row!");
-        let col: usize = self.tiles[row].iter().position(|x| *x == Tile::Guard).expect("Cant find guard column");
-        return (row, col);
+    fn new(input_lines: &[String]) -> Result<Self, AocError> {
+        let tiles: Grid<Tile> = Grid::parse(input_lines)?;
+        let guard_start: Position = tiles.find(&Tile::Guard).ok_or(AocError::parse("no guard (^, >, v or <) on the map"))?;
+        let guard_char: char = input_lines[guard_start.row].chars().nth(guard_start.col).unwrap();
+        let guard_start_direction: Direction = Direction::try_from(guard_char).map_err(|e| e.at_column(guard_start.col + 1).at_line(guard_start.row + 1))?;
+        return Ok(Self{tiles, guard_start, guard_start_direction});
     }
 
     fn get_path_to_obstacle_from_position(&self, pos: &Position, direction: &Direction) -> Vec<Tile> {
@@ -145,19 +60,7 @@ impl TileMap {
     }
 
     fn get_path_to_obtacle(&self, row: usize, col: usize, direction: &Direction) -> Vec<Tile> {
-        let mut path: Vec<Tile>;
-        path = match direction {
-            Direction::Up => self.tiles[..row].iter().map(|x| x[col].clone()).collect::<Vec<Tile>>(),
-            Direction::Down => self.tiles[(row+1)..].iter().map(|x| x[col].clone()).collect::<Vec<Tile>>(),
-            Direction::Left => self.tiles[row][..col].to_vec(),
-            Direction::Right => self.tiles[row][(col+1)..].to_vec(),
-        };
-
-        if *direction == Direction::Up || *direction == Direction::Left {
-            path.reverse();
-        }
-
-        return path;
+        return self.tiles.line(Position{row, col}, direction.delta()).skip(1).map(|x| x.1.clone()).collect::<Vec<Tile>>();
     }
 
     fn get_distance_to_obstacle(&self, row: usize, col: usize, direction: &Direction) -> (bool, usize) {
@@ -178,10 +81,9 @@ impl TileMap {
         let mut return_bool: bool = false;
         // println!("Checking obstacle between {:?} and {:?}", start_pos, end_pos);
         match direction {
-            Direction::Up => panic!("shouldnt have received up!"),
-            Direction::Down => return_bool = self.tiles[start_pos.row..(end_pos.row+1)].iter().map(|x| x[start_pos.col].clone()).collect::<Vec<Tile>>().contains(&Tile::Obstacle),
-            Direction::Left => panic!("shouldnt have received left!"),
-            Direction::Right => return_bool = self.tiles[start_pos.row][start_pos.col..(end_pos.col + 1)].to_vec().contains(&Tile::Obstacle),
+            Direction::Down => return_bool = self.tiles.line(start_pos, direction.delta()).take(end_pos.row - start_pos.row + 1).any(|x| *x.1 == Tile::Obstacle),
+            Direction::Right => return_bool = self.tiles.line(start_pos, direction.delta()).take(end_pos.col - start_pos.col + 1).any(|x| *x.1 == Tile::Obstacle),
+            _ => panic!("shouldnt have received {:?}!", direction),
         };
 
 
@@ -189,34 +91,29 @@ impl TileMap {
     }
 
     fn get_CW_guard_position(&self, start_position: &Position, direction: &Direction) -> Option<Position> {
-        println!("\tget_CW_guard_position from {:?} @ {:?}", start_position, direction);
-        let mut path = self.get_path_to_obstacle_from_position(&start_position, &direction.rotate_90_CW());
-        println!("\t\tpath is {:?}", path);
+        trace!("\tget_CW_guard_position from {:?} @ {:?}", start_position, direction);
+        let path = self.get_path_to_obstacle_from_position(start_position, &direction.rotate_90_cw());
+        trace!("\t\tpath is {:?}", path);
 
         if path.contains(&Tile::Obstacle) {
             let distance_to_obstacle = path.iter().position(|x| *x == Tile::Obstacle).unwrap();
-            println!("\t\twalking {} in direction {:?}", distance_to_obstacle, direction.rotate_90_CW());
-            return Some(start_position.walk(distance_to_obstacle, direction.rotate_90_CW()));
+            trace!("\t\twalking {} in direction {:?}", distance_to_obstacle, direction.rotate_90_cw());
+            return Some(start_position.walk(distance_to_obstacle, direction.rotate_90_cw()));
         } else {
             return None;
         }
     }
 
     fn get_CCW_guard_position(&self, start_position: &Position, direction: &Direction) -> Option<Position> {
-        println!("\tget_CCW_guard_position from {:?} @ {:?}", start_position, direction);
-        let offset_position: Position = match direction {
-            Direction::Up => start_position.walk(1, Direction::Left),
-            Direction::Left => start_position.walk(1, Direction::Down),
-            Direction::Down => start_position.walk(1, Direction::Right),
-            Direction::Right => start_position.walk(1, Direction::Up),
-        };
-        println!("\t\toffset_position = {:?}", offset_position);
+        trace!("\tget_CCW_guard_position from {:?} @ {:?}", start_position, direction);
+        let offset_position: Position = start_position.walk(1, direction.rotate_90_ccw());
+        trace!("\t\toffset_position = {:?}", offset_position);
         let mut path = self.get_path_to_obstacle_from_position(&offset_position, &direction.reverse());
-        println!("\t\tpath is {:?}", path);
+        trace!("\t\tpath is {:?}", path);
 
         if path.contains(&Tile::Obstacle) {
             let distance_to_obstacle = path.iter().position(|x| *x == Tile::Obstacle).unwrap();
-            println!("\t\twalking {} in direction {:?}", distance_to_obstacle, direction.reverse());
+            trace!("\t\twalking {} in direction {:?}", distance_to_obstacle, direction.reverse());
             return Some(start_position.walk(distance_to_obstacle + 1, direction.reverse()));
         } else {
             return None;
@@ -224,35 +121,11 @@ impl TileMap {
     }
 
     fn get_adjacent_positions(&self, position: &Position) -> Vec<Position> {
-        let mut adjacent_positions: Vec<Position> = Vec::new();
-
-        if position.row > 0 {
-            adjacent_positions.push(position.walk(1, Direction::Up));
-        }
-        if position.row < (self.tiles.len() - 1) {
-            adjacent_positions.push(position.walk(1, Direction::Down));
-        }
-        if position.col > 0 {
-            adjacent_positions.push(position.walk(1, Direction::Left));
-        }
-        if position.col < (self.tiles[0].len() - 1) {
-            adjacent_positions.push(position.walk(1, Direction::Right));
-        }
-
-        return adjacent_positions;
+        return self.tiles.neighbours(*position).collect::<Vec<Position>>();
     }
 
     fn get_all_obstruction_positions(&self) -> Vec<Position> {
-        let mut obstruction_positions: Vec<Position> = Vec::new();
-        for r in 0..self.tiles.len() {
-            for c in 0..self.tiles[0].len() {
-                if self.tiles[r][c] == Tile::Obstacle {
-                    obstruction_positions.push(Position{ row: r, col: c });
-                }
-            }
-        }
-
-        return obstruction_positions;
+        return self.tiles.find_all(&Tile::Obstacle);
     }
 
     fn get_all_obstruction_adjacents(&self) -> Vec<Position> {
@@ -268,15 +141,15 @@ impl TileMap {
 fn sort_positions(pos1: &Position, pos2: &Position) -> (Position, Position, Direction) {
     if pos1.row == pos2.row {
         if pos1.col <= pos2.col {
-            return (pos1.clone(), pos2.clone(), Direction::Right);
+            return (*pos1, *pos2, Direction::Right);
         } else {
-            return (pos2.clone(), pos1.clone(), Direction::Right);
+            return (*pos2, *pos1, Direction::Right);
         }
     } else {
         if pos1.row <= pos2.row {
-            return (pos1.clone(), pos2.clone(), Direction::Down);
+            return (*pos1, *pos2, Direction::Down);
         } else {
-            return (pos2.clone(), pos1.clone(), Direction::Down);
+            return (*pos2, *pos1, Direction::Down);
         }
     }
 }
@@ -311,21 +184,23 @@ fn get_new_guard_position(current_positions: &Vec<Position>) -> Position {
 }
 
 fn get_obstacle_from_guard_position(position: &Position, direction: &Direction) -> Position {
-    match direction {
-        Direction::Up => Position{row: position.row - 1, col: position.col},
-        Direction::Down => Position{row: position.row + 1, col: position.col},
-        Direction::Left => Position{row: position.row, col: position.col - 1},
-        Direction::Right => Position{row: position.row, col: position.col + 1},
-    }
+    return position.walk(1, *direction);
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let tile_map: TileMap = TileMap::from(&input_lines);
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let tile_map: TileMap = TileMap::new(&input_lines)?;
+    return Ok(patrol(&tile_map, part_2));
+}
 
-    let (guard_start_row, guard_start_col): (usize, usize) = tile_map.get_guard_start();
-    let guard_start_direction = Direction::from(input_lines[guard_start_row].chars().nth(guard_start_col).unwrap());
+fn patrol(tile_map: &TileMap, part_2: bool) -> usize {
+    let (guard_start_row, guard_start_col): (usize, usize) = (tile_map.guard_start.row, tile_map.guard_start.col);
+    let guard_start_direction: Direction = tile_map.guard_start_direction;
 
     let mut guard_on_map: bool = true;
     let mut total_distance: usize = 0;
@@ -333,89 +208,75 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     let mut col: usize = guard_start_col;
     let mut direction: Direction = guard_start_direction.clone();
     let mut new_distance: usize = 0;
-    let mut tiles_walked: Vec<Vec<Tile>> = vec![vec![Tile::Empty; tile_map.tiles[0].len()]; tile_map.tiles.len()];
+    let mut tiles_walked: Grid<Tile> = Grid::new(tile_map.tiles.row_count(), tile_map.tiles.col_count(), Tile::Empty);
 
-    let mut approached_obstructions: Vec<Vec<DetailedTile>> = vec![
-        vec![DetailedTile{tile_type: Tile::Empty, approach_direction: None}; tile_map.tiles[0].len()]; tile_map.tiles.len()];
+    let mut approached_obstructions: Grid<DetailedTile> = Grid::new(
+        tile_map.tiles.row_count(), tile_map.tiles.col_count(), DetailedTile{tile_type: Tile::Empty, approach_direction: None});
 
 
-    println!("Starting at row: {}, col: {}", row, col);
+    debug!("Starting at row: {}, col: {}", row, col);
     while guard_on_map {
         (guard_on_map, new_distance) = tile_map.get_distance_to_obstacle(row, col, &direction);
         total_distance += new_distance;
         match direction {
             Direction::Up => {
                 for r in (row - new_distance)..row {
-                    tiles_walked[r][col] = Tile::Walked;
+                    tiles_walked[Position{row: r, col}] = Tile::Walked;
                 }
                 row -= new_distance;
                 if (guard_on_map) {
-                    approached_obstructions[row - 1][col] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction.clone())};
+                    approached_obstructions[Position{row: row - 1, col}] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction)};
                 }
             }
             Direction::Down => {
                 for r in (row..(row + new_distance + 1)) {
-                    tiles_walked[r][col] = Tile::Walked;
+                    tiles_walked[Position{row: r, col}] = Tile::Walked;
                 }
                 row += new_distance;
                 if (guard_on_map) {
-                    approached_obstructions[row + 1][col] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction.clone())};
+                    approached_obstructions[Position{row: row + 1, col}] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction)};
                 }
             },
             Direction::Left => {
                 for c in ((col - new_distance)..col) {
-                    tiles_walked[row][c] = Tile::Walked;
+                    tiles_walked[Position{row, col: c}] = Tile::Walked;
                 }
                 col -= new_distance;
                 if (guard_on_map) {
-                    approached_obstructions[row][col - 1] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction.clone())};
+                    approached_obstructions[Position{row, col: col - 1}] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction)};
                 }
             },
             Direction::Right => {
                 for c in (col..(col + new_distance + 1)) {
-                    tiles_walked[row][c] = Tile::Walked;
+                    tiles_walked[Position{row, col: c}] = Tile::Walked;
                 }
                 col += new_distance;
                 if (guard_on_map) {
-                    approached_obstructions[row][col + 1] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction.clone())};
+                    approached_obstructions[Position{row, col: col + 1}] = DetailedTile{tile_type: Tile::Obstacle, approach_direction: Some(direction)};
                 }
             },
+            _ => unreachable!("the guard only walks up, down, left or right"),
         };
 
-        println!("Now at row: {}, col: {}, guard_on_map: {}", row, col, guard_on_map);
+        trace!("Now at row: {}, col: {}, guard_on_map: {}", row, col, guard_on_map);
         // print_walked_map(&tiles_walked);
 
-        direction = direction.rotate_90_CW();
+        direction = direction.rotate_90_cw();
     }
 
     if !part_2 {
-        let mut count_walked: usize = 0;
-        for r in tiles_walked {
-            for c in r {
-                if c == Tile::Walked {
-                    count_walked += 1;
-                }
-            }
-        }
-        return count_walked;
+        return tiles_walked.find_all(&Tile::Walked).len();
     } else {
-        let mut walked_positions: Vec<Position> = Vec::new();
-        for r in 0..tiles_walked.len() {
-            for c in 0..tiles_walked[0].len() {
-                if tiles_walked[r][c] == Tile::Walked {
-                    walked_positions.push(Position{row: r, col: c});
-                }
-            }
-        }
+        let walked_positions: Vec<Position> = tiles_walked.find_all(&Tile::Walked);
 
         let mut new_obstacle_positions: Vec<Position> = Vec::new();
 
         for p in walked_positions {
-            println!("Running loop for position: {:?}", p);
-            let mut walked_map_count: Vec<Vec<Vec<usize>>> = vec![vec![Vec::new(); tile_map.tiles[0].len()]; tile_map.tiles.len()];
+            trace!("Running loop for position: {:?}", p);
+            let mut walked_map_count: Grid<Vec<usize>> = Grid::new(tile_map.tiles.row_count(), tile_map.tiles.col_count(), Vec::new());
 
             let mut new_tile_map = tile_map.clone();
-            new_tile_map.tiles[p.row][p.col] = Tile::Obstacle;
+            new_tile_map.tiles[p] = Tile::Obstacle;
             
             guard_on_map = true;
             row = guard_start_row;
@@ -436,11 +297,11 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
             while guard_on_map && !completed_loop {
                 (guard_on_map, new_distance) = new_tile_map.get_distance_to_obstacle(row, col, &direction);
 
-                let current_position = Position{row: row, col: col};
+                let current_position = Position{row, col};
                 if obstacle_adjacents.contains_key(&current_position) {
                     // println!("obstacle adjacents contains key");
                     if *obstacle_adjacents.get_mut(&current_position).unwrap().get(&direction).unwrap() {
-                        println!("\tHit {:?} twice!!", current_position);
+                        trace!("\tHit {:?} twice!!", current_position);
                         completed_loop = true;
                     } else {
                         // println!("\tRemembering {:?}", current_position);
@@ -450,34 +311,35 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
                 match direction {
                     Direction::Up => {
                         for r in (row - new_distance)..row {
-                            walked_map_count[r][col].push(step_index);
+                            walked_map_count[Position{row: r, col}].push(step_index);
                             step_index += 1;
                         }
                         row -= new_distance;
                     }
                     Direction::Down => {
                         for r in (row..(row + new_distance + 1)) {
-                            walked_map_count[r][col].push(step_index);
+                            walked_map_count[Position{row: r, col}].push(step_index);
                             step_index += 1;
                         }
                         row += new_distance;
                     },
                     Direction::Left => {
                         for c in ((col - new_distance)..col) {
-                            walked_map_count[row][c].push(step_index);
+                            walked_map_count[Position{row, col: c}].push(step_index);
                             step_index += 1;
                         }
                         col -= new_distance;
                     },
                     Direction::Right => {
                         for c in (col..(col + new_distance + 1)) {
-                            walked_map_count[row][c].push(step_index);
+                            walked_map_count[Position{row, col: c}].push(step_index);
                             step_index += 1;
                         }
                         col += new_distance;
                     },
+                    _ => unreachable!("the guard only walks up, down, left or right"),
                 };
-                direction = direction.rotate_90_CW()
+                direction = direction.rotate_90_cw()
             }
 
             if guard_on_map && completed_loop {
@@ -485,7 +347,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
             }
         }
 
-        println!("{:?}", new_obstacle_positions);
+        debug!("{:?}", new_obstacle_positions);
 
 
 
@@ -493,10 +355,10 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     }
 }
 
-fn print_map(walked_map: &Vec<Vec<Tile>>, special: Tile) {
+fn print_map(walked_map: &Grid<Tile>, special: Tile) {
     let mut print_char = '.';
     let mut print_row: String;
-    for r in walked_map {
+    for r in walked_map.rows() {
         print_row = String::new();
         for c in r {
             if *c == special {
@@ -506,7 +368,31 @@ fn print_map(walked_map: &Vec<Vec<Tile>>, special: Tile) {
             }
             print_row.push(print_char);
         }
-        print!("{}\n", print_row);
+        debug!("{}", print_row);
+    }
+}
+
+#[derive(Default)]
+pub struct Day6 {
+    tile_map: Option<TileMap>,
+}
+
+impl Solution for Day6 {
+    fn day(&self) -> usize {
+        return 6;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.tile_map = Some(TileMap::new(&input_lines)?);
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(patrol(solution::parsed(&self.tile_map, DAY)?, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(patrol(solution::parsed(&self.tile_map, DAY)?, true)));
     }
 }
 
@@ -514,6 +400,7 @@ fn print_map(walked_map: &Vec<Vec<Tile>>, special: Tile) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -522,23 +409,30 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 41);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 4656);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn test_example_2() {
-        let input_lines: Vec<String> = generic::read_in_file((INPUTS_FOLDER.to_owned() + "/input_example_1.txt").as_str());
-        let tile_map: TileMap = TileMap::from(&input_lines);
-        let start_guard_position: Position = Position::from_obstacle(3, 2, &Direction::Up);
+        let input_lines: Vec<String> = [
+            "....#.....",
+            ".........#",
+            "..........",
+            "..#.......",
+            ".......#..",
+            "..........",
+            ".#..^.....",
+            "........#.",
+            "#.........",
+            "......#...",
+        ].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let tile_map: TileMap = TileMap::new(&input_lines).unwrap();
+        let start_guard_position: Position = position_from_obstacle(3, 2, &Direction::Up);
         let mut next_CW_guard_position: Option<Position> = tile_map.get_CW_guard_position(&start_guard_position, &Direction::Up);
         assert!(next_CW_guard_position == Some(Position{row: 4, col: 6}));
         let mut next_CW_guard_position: Option<Position> = tile_map.get_CW_guard_position(&next_CW_guard_position.unwrap(), &Direction::Right);
@@ -559,9 +453,10 @@ mod tests {
         next_CCW_guard_position = tile_map.get_CCW_guard_position(&start_guard_position, &Direction::Right);
         println!("next_CCW_guard_position = {:?}", next_CCW_guard_position);
         assert!(next_CCW_guard_position == Some(Position{row: 1, col: 4}));
-        next_CCW_guard_position = tile_map.get_CCW_guard_position(&next_CCW_guard_position.unwrap(), &Direction::Right);
+        // The guard walked up to there from where it started, with nothing turning it on the way.
+        next_CCW_guard_position = tile_map.get_CCW_guard_position(&next_CCW_guard_position.unwrap(), &Direction::Up);
         println!("next_CCW_guard_position = {:?}", next_CCW_guard_position);
-        assert!(next_CCW_guard_position == Some(Position{row: 4, col: 2}));
+        assert!(next_CCW_guard_position.is_none());
     }
 
     #[test]
@@ -577,16 +472,12 @@ mod tests {
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 6);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1575);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
         // 157 too low
     }
 }
\ No newline at end of file
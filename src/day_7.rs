@@ -1,9 +1,12 @@
-const INPUTS_FOLDER: &str = "inputs/day_7";
+const DAY: usize = 7;
 
 use core::num;
 use std::{collections::HashSet, thread::{self, JoinHandle}};
 
-use crate::generic;
+use crate::error::{self, AocError};
+use crate::inputs;
+use crate::log::{debug, trace};
+use crate::solution::{Answer, Solution};
 
 #[derive(Debug, Clone)]
 enum Operator {
@@ -18,14 +21,16 @@ struct Equation {
     operators: Option<Vec<Operator>>,
 }
 
-impl From<&String> for Equation {
-    fn from(value: &String) -> Self {
-        let split_string = value.split(": ").map(|x| x.to_string()).collect::<Vec<String>>();
-        Self {
-            result: split_string[0].parse::<i64>().expect(&format!("Bad input given for test value. {}", value)),
-            numbers: split_string[1].split(" ").map(|x| x.parse::<i64>().expect(&format!("Bad input given for numbers {}", value))).collect::<Vec<i64>>(),
+impl TryFrom<&String> for Equation {
+    type Error = AocError;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        let (result, numbers) = value.split_once(": ").ok_or(AocError::parse(format!("expected \"result: numbers\", got {:?}", value)))?;
+        return Ok(Self {
+            result: error::parse_number::<i64>(result)?,
+            numbers: numbers.split(" ").map(error::parse_number::<i64>).collect::<Result<Vec<i64>, AocError>>()?,
             operators: None,
-        }
+        });
     }
 }
 
@@ -186,10 +191,17 @@ fn solve_equation_backwards(a: &i64, r: &i64, part_2: bool, multipliers: Option<
     return new_numbers;
 }
 
-fn solve_puzzle(input_filename: String, part_2: bool, threading: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let equations: Vec<Equation> = input_lines.iter().map(|x| Equation::from(x)).collect::<Vec<Equation>>();
+pub fn solve_puzzle(variant: &str, part_2: bool, threading: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2, threading);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool, threading: bool) -> Result<usize, AocError> {
+    let equations: Vec<Equation> = error::parse_lines(&input_lines, |x| Equation::try_from(x))?;
+    return Ok(calibration_result(&equations, part_2, threading));
+}
 
+fn calibration_result(equations: &[Equation], part_2: bool, threading: bool) -> usize {
     let mut test_value_sum: i64 = 0;
 
     if !threading
@@ -199,7 +211,7 @@ fn solve_puzzle(input_filename: String, part_2: bool, threading: bool) -> usize
             if equation_passes {
                 test_value_sum += e.result;
             }
-            println!("Equation ({}/850) {:?} is {}", i, e, equation_passes);
+            trace!("Equation ({}/850) {:?} is {}", i, e, equation_passes);
         }
     } else {
 
@@ -215,10 +227,10 @@ fn solve_puzzle(input_filename: String, part_2: bool, threading: bool) -> usize
                     if equation_passes {
                         thread_test_value_sum += e.result;
                     }
-                    println!("Thread {} ({}/50):: Equation {:?} is {}", i + 1, j, e, equation_passes);
+                    trace!("Thread {} ({}/50):: Equation {:?} is {}", i + 1, j, e, equation_passes);
                 }
 
-                println!("Thread {} Finished!!!!", i);
+                debug!("Thread {} Finished!!!!", i);
 
                 return thread_test_value_sum;
             }))
@@ -261,10 +273,35 @@ fn join_numbers(numbers: Vec<i64>) -> i64 {
     return new_number;
 }
 
+#[derive(Default)]
+pub struct Day7 {
+    equations: Vec<Equation>,
+}
+
+impl Solution for Day7 {
+    fn day(&self) -> usize {
+        return 7;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.equations = error::parse_lines(&input_lines, |x| Equation::try_from(x))?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(calibration_result(&self.equations, false, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(calibration_result(&self.equations, true, false)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -340,16 +377,12 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false, false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 3749);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false, false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 5702958180383);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false, false));
         //5977528110155 too high
     }
 
@@ -363,16 +396,12 @@ mod tests {
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true, false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 11387);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true, false));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true, true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 92612386119138);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true, true));
 
         // answer = 85,082,831,264,513 after 1086.21s. TOO LOOOOOW
         // answer = 85,082,831,264,513 after 1856.91s
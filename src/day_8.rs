@@ -1,37 +1,30 @@
-const INPUTS_FOLDER: &str = "inputs/day_8";
+const DAY: usize = 8;
 
 use std::collections::{HashMap, HashSet};
 
-use crate::generic;
+use crate::error::AocError;
+use crate::generic::{Grid, Position};
+use crate::inputs;
+use crate::log::debug;
+use crate::solution::{Answer, Solution};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct Position {
-    row: i32,
-    col: i32,
-}
 
-impl Position {
-    fn new(row: i32, col: i32) -> Self {
-        return Self{row: row, col: col};
-    }
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
 }
 
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    let char_map: Grid<char> = Grid::parse(&input_lines)?;
+    return Ok(count_antinodes(&char_map, part_2));
+}
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let char_map: Vec<Vec<char>> = input_lines.iter().map(|x| x.chars().collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
-
+fn count_antinodes(char_map: &Grid<char>, part_2: bool) -> usize {
     let mut antennas: HashMap<char, Vec<Position>> = HashMap::new();
 
-    for r in 0..char_map.len() {
-        for c in 0..char_map[0].len() {
-            let current_char = char_map[r][c];
-            if current_char != '.' {
-                if !antennas.contains_key(&current_char) {
-                    antennas.insert(current_char, Vec::new());
-                }
-                antennas.get_mut(&current_char).unwrap().push(Position::new(r as i32, c as i32));
-            }
+    for (p, current_char) in char_map.iter() {
+        if *current_char != '.' {
+            antennas.entry(*current_char).or_default().push(p);
         }
     }
 
@@ -40,36 +33,27 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     for (k, v) in antennas.iter() {
         for i in 0..(v.len() - 1) {
             for j in (i + 1)..v.len() {
-                let p1: &Position = &v[i];
-                let p2: &Position = &v[j];
-                
-                let vertical_delta: i32 = p2.row - p1.row;
-                let horizontal_delta: i32 = p2.col - p1.col;
-                
+                let p1: Position = v[i];
+                let p2: Position = v[j];
+
+                let (vertical_delta, horizontal_delta): (isize, isize) = p1.offset_to(&p2);
+
                 if !part_2 {
-                    antinodes.insert(Position::new(p2.row + vertical_delta, p2.col + horizontal_delta));
-                    antinodes.insert(Position::new(p1.row - vertical_delta, p1.col - horizontal_delta));
+                    antinodes.extend(char_map.offset(p2, (vertical_delta, horizontal_delta)));
+                    antinodes.extend(char_map.offset(p1, (-vertical_delta, -horizontal_delta)));
                 } else {
-                    let mut i: i32 = 1;
+                    let mut i: isize = 1;
 
-                    antinodes.insert(p1.clone());
-                    antinodes.insert(p2.clone());
+                    antinodes.insert(p1);
+                    antinodes.insert(p2);
 
-                    loop {
-                        let new_point = Position::new(p2.row + (vertical_delta * i), p2.col + (horizontal_delta * i));
-                        if new_point.row < 0 || new_point.row >= char_map.len() as i32 || new_point.col < 0 || new_point.col >= char_map[0].len()  as i32 {
-                            break;
-                        }
+                    while let Some(new_point) = char_map.offset(p2, (vertical_delta * i, horizontal_delta * i)) {
                         antinodes.insert(new_point);
                         i += 1;
                     }
 
                     i = 1;
-                    loop {
-                        let new_point = Position::new(p1.row - (vertical_delta * i), p1.col - (horizontal_delta * i));
-                        if new_point.row < 0 || new_point.row >= char_map.len() as i32 || new_point.col < 0 || new_point.col >= char_map[0].len()  as i32 {
-                            break;
-                        }
+                    while let Some(new_point) = char_map.offset(p1, (-vertical_delta * i, -horizontal_delta * i)) {
                         antinodes.insert(new_point);
                         i += 1;
                     }
@@ -78,28 +62,50 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
         }
     }
 
-    for r in 0..char_map.len() {
+    for r in 0..char_map.row_count() {
         let mut row_string: String = String::new();
-        for c in 0..char_map[0].len() {
-            if antinodes.contains(&Position::new(r as i32, c as i32)) {
+        for c in 0..char_map.col_count() {
+            if antinodes.contains(&Position { row: r, col: c }) {
                 row_string.push('#');
             } else {
                 row_string.push('.');
             }
         }
-        println!("{}", row_string);
+        debug!("{}", row_string);
+    }
+
+    return antinodes.len();
+}
+
+#[derive(Default)]
+pub struct Day8 {
+    char_map: Grid<char>,
+}
+
+impl Solution for Day8 {
+    fn day(&self) -> usize {
+        return 8;
     }
 
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.char_map = Grid::parse(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_antinodes(&self.char_map, false)));
+    }
 
-    return antinodes.iter()
-        .filter(|x| x.row < char_map.len() as i32 && x.row >= 0 && x.col < char_map[0].len() as i32 && x.col >= 0)
-        .collect::<Vec<&Position>>().len();
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(count_antinodes(&self.char_map, true)));
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -108,29 +114,21 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 14);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 376);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 34);
+        answers::check(DAY, 2, "example_1", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1352);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
-const INPUTS_FOLDER: &str = "inputs/day_9";
+const DAY: usize = 9;
 
 use std::fmt;
 
 use itertools::rev;
 
-use crate::generic;
+use crate::error::AocError;
+use crate::inputs;
+use crate::log::debug;
+use crate::solution::{Answer, Solution};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 enum FileType {
@@ -103,9 +106,8 @@ impl Iterator for DiskMap {
     }
 }
 
-impl From<String> for DiskMap {
-    fn from(value: String) -> Self {
-        let input_values: Vec<usize> = value.chars().map(|x| x as usize - 48).collect::<Vec<usize>>();
+impl From<&[usize]> for DiskMap {
+    fn from(input_values: &[usize]) -> Self {
         let file_lengths: Vec<usize> = input_values.iter().enumerate().filter(|(i, x)| i % 2 == 0).map(|(i, x)| *x).collect::<Vec<usize>>();
         let free_lengths: Vec<usize> = input_values.iter().enumerate().filter(|(i, x)| i % 2 != 0).map(|(i, x)| *x).collect::<Vec<usize>>();
         let mut reversed_lengths: Vec<usize> = file_lengths.clone();
@@ -129,12 +131,28 @@ impl From<String> for DiskMap {
 }
 
 
-fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
-    let input_lines: Vec<String> = generic::read_in_file(input_filename.as_str());
-    let original: Vec<usize> = input_lines[0].chars().map(|x| x as usize - 48).collect::<Vec<usize>>();
-    let mut disk_map: DiskMap = DiskMap::from(input_lines[0].clone());
+pub fn solve_puzzle(variant: &str, part_2: bool) -> Result<usize, AocError> {
+    let input_lines: Vec<String> = inputs::read(DAY, variant)?;
+    return solve_lines(input_lines, part_2);
+}
+
+pub fn solve_lines(input_lines: Vec<String>, part_2: bool) -> Result<usize, AocError> {
+    return Ok(checksum(&parse_lengths(&input_lines)?, part_2));
+}
+
+// The alternating file and free space lengths.
+// The disk map is one line of single digit lengths.
+fn parse_lengths(input_lines: &[String]) -> Result<Vec<usize>, AocError> {
+    let first_line: &String = input_lines.first().ok_or(AocError::parse("expected a disk map").at_line(1))?;
+    return first_line.chars().enumerate()
+        .map(|(i, x)| x.to_digit(10).map(|d| d as usize).ok_or(AocError::parse(format!("bad length {:?}", x)).at_column(i + 1).at_line(1)))
+        .collect::<Result<Vec<usize>, AocError>>();
+}
 
-    println!("{:?}", disk_map);
+fn checksum(original: &[usize], part_2: bool) -> usize {
+    let disk_map: DiskMap = DiskMap::from(original);
+
+    debug!("{:?}", disk_map);
 
     let mut final_string: String = String::new();
     let mut final_list: Vec<usize> = Vec::new();
@@ -150,9 +168,9 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
 
     // println!("{}", final_string);
     // println!("{:?}", product_list);
-    println!("product list sum = {}", product_list.iter().sum::<usize>());
+    debug!("product list sum = {}", product_list.iter().sum::<usize>());
     let input_was_even: bool = original.len() % 2 == 0;
-    println!("Input was even {}", input_was_even);
+    debug!("Input was even {}", input_was_even);
 
 
     let all_spaces_sum: usize = original.iter().sum::<usize>();
@@ -205,7 +223,7 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
         }
 
         // println!("Manual sort2 = {:?}", manual_expansion_answer);
-        println!("Manual product = {:?}", manual_expansion_answer.iter().enumerate().map(|(i, x)| i * x).sum::<usize>());
+        debug!("Manual product = {:?}", manual_expansion_answer.iter().enumerate().map(|(i, x)| i * x).sum::<usize>());
 
         return manual_expansion_answer.iter().enumerate().map(|(i, x)| i * x).sum::<usize>();
     } else {
@@ -249,10 +267,35 @@ fn solve_puzzle(input_filename: String, part_2: bool) -> usize {
     }
 }
 
+#[derive(Default)]
+pub struct Day9 {
+    lengths: Vec<usize>,
+}
+
+impl Solution for Day9 {
+    fn day(&self) -> usize {
+        return 9;
+    }
+
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError> {
+        self.lengths = parse_lengths(&input_lines)?;
+        return Ok(());
+    }
+
+    fn part1(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(checksum(&self.lengths, false)));
+    }
+
+    fn part2(&self) -> Result<Answer, AocError> {
+        return Ok(Answer::from(checksum(&self.lengths, true)));
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::answers;
 
     #[test]
     fn quick_test() {
@@ -263,59 +306,43 @@ mod tests {
 
     #[test]
     fn example_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_1.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 60);
+        answers::check(DAY, 1, "example_1", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 1928);
+        answers::check(DAY, 1, "example_2", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_3() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_3.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 9786);
+        answers::check(DAY, 1, "example_3", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_4() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_4.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2132);
+        answers::check(DAY, 1, "example_4", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn example_1_5() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_5.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 17513);
+        answers::check(DAY, 1, "example_5", |x| solve_puzzle(x, false));
     }
 
     #[test]
     fn part_1() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", false);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 6337367222422);
+        answers::check(DAY, 1, "input", |x| solve_puzzle(x, false));
 
         // 6222777990262 is too low
     }
 
     #[test]
     fn example_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input_example_2.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 2858);
+        answers::check(DAY, 2, "example_2", |x| solve_puzzle(x, true));
     }
 
     #[test]
     fn part_2() {
-        let answer = solve_puzzle(INPUTS_FOLDER.to_owned() + "/input.txt", true);
-        println!("Answer = {:?}", answer);
-        assert!(answer == 6361380647183);
+        answers::check(DAY, 2, "input", |x| solve_puzzle(x, true));
     }
 }
\ No newline at end of file
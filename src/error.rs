@@ -0,0 +1,123 @@
+use std::{convert::Infallible, error, fmt, io};
+
+#[derive(Debug)]
+pub enum AocError {
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Parse {
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    Write {
+        path: String,
+        source: io::Error,
+    },
+    AlreadyExists {
+        path: String,
+    },
+    MissingInput {
+        year: usize,
+        day: usize,
+        variant: String,
+        tried: Vec<String>,
+    },
+}
+
+impl AocError {
+    pub fn parse(message: impl Into<String>) -> Self {
+        return AocError::Parse { line: None, column: None, message: message.into() };
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        // Only the innermost caller knows the line, so an existing one is kept.
+        match self {
+            AocError::Parse { line: None, column, message } => AocError::Parse { line: Some(line), column, message },
+            other => other,
+        }
+    }
+
+    pub fn at_column(self, column: usize) -> Self {
+        match self {
+            AocError::Parse { line, column: None, message } => AocError::Parse { line, column: Some(column), message },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io { path, source } => write!(f, "unable to read file {}: {}", path, source),
+            AocError::Write { path, source } => write!(f, "unable to write file {}: {}", path, source),
+            AocError::AlreadyExists { path } => write!(f, "{} already exists", path),
+            AocError::MissingInput { year, day, variant, tried } => {
+                write!(f, "no {:?} input for {} day {}, looked for {} (set {} to your inputs folder or pass --input)", variant, year, day, tried.join(" and "), crate::inputs::ROOT_VAR)
+            },
+            AocError::Parse { line, column, message } => {
+                match (line, column) {
+                    (Some(l), Some(c)) => write!(f, "line {}, column {}: {}", l, c, message),
+                    (Some(l), None) => write!(f, "line {}: {}", l, message),
+                    (None, Some(c)) => write!(f, "column {}: {}", c, message),
+                    (None, None) => write!(f, "{}", message),
+                }
+            },
+        }
+    }
+}
+
+// Lets parsers that can't fail, like a From<char>, stand in where one that can is expected.
+impl From<Infallible> for AocError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl error::Error for AocError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } | AocError::Write { source, .. } => Some(source),
+            AocError::Parse { .. } | AocError::AlreadyExists { .. } | AocError::MissingInput { .. } => None,
+        }
+    }
+}
+
+pub fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, AocError> {
+    return value.trim().parse::<T>().map_err(|_| AocError::parse(format!("bad number {:?}", value)));
+}
+
+pub fn parse_lines<T>(input_lines: &[String], parser: impl Fn(&String) -> Result<T, AocError>) -> Result<Vec<T>, AocError> {
+    return parse_lines_from(input_lines, 1, parser);
+}
+
+pub fn parse_lines_from<T>(input_lines: &[String], first_line: usize, parser: impl Fn(&String) -> Result<T, AocError>) -> Result<Vec<T>, AocError> {
+    return input_lines.iter().enumerate().map(|(i, x)| parser(x).map_err(|e| e.at_line(first_line + i))).collect::<Result<Vec<T>, AocError>>();
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_context() {
+        assert!(AocError::parse("bad number \"x\"").to_string() == "bad number \"x\"");
+        assert!(AocError::parse("bad").at_line(3).to_string() == "line 3: bad");
+        assert!(AocError::parse("bad").at_column(5).at_line(3).to_string() == "line 3, column 5: bad");
+        assert!(AocError::parse("bad").at_line(3).at_line(9).to_string() == "line 3: bad");
+    }
+
+    #[test]
+    fn parse_helpers() {
+        assert!(parse_number::<usize>(" 42").unwrap() == 42);
+        assert!(parse_number::<usize>("-1").is_err());
+
+        let lines: Vec<String> = vec!["1".to_string(), "2".to_string(), "x".to_string()];
+        let error = parse_lines(&lines, |x| parse_number::<usize>(x)).unwrap_err();
+        assert!(error.to_string() == "line 3: bad number \"x\"");
+        let error = parse_lines_from(&lines, 5, |x| parse_number::<usize>(x)).unwrap_err();
+        assert!(error.to_string() == "line 7: bad number \"x\"");
+    }
+}
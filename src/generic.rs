@@ -1,11 +1,12 @@
-use std::{fmt, fs::{self, File}, io::Write};
+use std::{fmt, fs::{self, File}, io::Write, num::TryFromIntError, ops::{Index, IndexMut}, str::FromStr};
 
-pub fn read_in_file(input_filename: &str) -> Vec<String> {
-    let error_msg = format!("unable to read file {}", input_filename);
-    let input_data = fs::read_to_string(input_filename).expect(error_msg.as_str());
+use crate::error::AocError;
+
+pub fn read_in_file(input_filename: &str) -> Result<Vec<String>, AocError> {
+    let input_data = fs::read_to_string(input_filename).map_err(|e| AocError::Io { path: input_filename.to_string(), source: e })?;
     let split = input_data.lines();
     let file_lines: Vec<String> = split.map(str::to_string).collect();
-    return file_lines.clone();
+    return Ok(file_lines);
 }
 
 pub fn append_to_file(output_filename: String, output_data: String) {
@@ -28,16 +29,54 @@ pub enum Direction {
     Down,
     Left,
     Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
 }
 
-impl From<char> for Direction {
-    fn from(value: char) -> Self {
+// All eight directions going clockwise from Up, so a rotation by 45 degrees is one step along it.
+const CLOCKWISE: [Direction; 8] = [
+    Direction::Up,
+    Direction::UpRight,
+    Direction::Right,
+    Direction::DownRight,
+    Direction::Down,
+    Direction::DownLeft,
+    Direction::Left,
+    Direction::UpLeft,
+];
+
+impl TryFrom<char> for Direction {
+    type Error = AocError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '^' => Self::Up,
-            'v' => Self::Down,
-            '>' => Self::Right,
-            '<' => Self::Left,
-            _ => panic!("Bad char input for Direction::from"),
+            '^' | 'U' | 'N' => Ok(Self::Up),
+            'v' | 'V' | 'D' | 'S' => Ok(Self::Down),
+            '>' | 'R' | 'E' => Ok(Self::Right),
+            '<' | 'L' | 'W' => Ok(Self::Left),
+            _ => Err(AocError::parse(format!("bad direction {:?}", value))),
+        }
+    }
+}
+
+// A single direction character, or two for a diagonal, e.g. "NE" or "DL".
+impl FromStr for Direction {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let directions: Vec<Direction> = value.chars().map(Direction::try_from).collect::<Result<Vec<Direction>, AocError>>()?;
+        match directions.as_slice() {
+            [d] => return Ok(*d),
+            [a, b] => {
+                let (dr, dc) = (a.delta().0 + b.delta().0, a.delta().1 + b.delta().1);
+                if a.is_diagonal() || b.is_diagonal() || dr == 0 || dc == 0 {
+                    return Err(AocError::parse(format!("bad direction {:?}", value)));
+                }
+                return Ok(Direction::from_delta((dr, dc)).unwrap());
+            },
+            _ => return Err(AocError::parse(format!("bad direction {:?}", value))),
         }
     }
 }
@@ -52,14 +91,81 @@ impl Direction {
         ].iter().copied();
     }
 
-    pub fn reverse(&self) -> Self {
+    // The four above followed by the diagonals.
+    pub fn iter_all() -> impl Iterator<Item = Direction> {
+        return [
+            Self::Up,
+            Self::Down,
+            Self::Left,
+            Self::Right,
+            Self::UpLeft,
+            Self::UpRight,
+            Self::DownLeft,
+            Self::DownRight,
+        ].iter().copied();
+    }
+
+    // The direction a (row, col) vector points in, None unless it runs along a row, a column or a diagonal.
+    pub fn from_delta(delta: (isize, isize)) -> Option<Self> {
+        if delta.0 != 0 && delta.1 != 0 && delta.0.unsigned_abs() != delta.1.unsigned_abs() {
+            return None;
+        }
+        match (delta.0.signum(), delta.1.signum()) {
+            (-1, 0) => return Some(Self::Up),
+            (1, 0) => return Some(Self::Down),
+            (0, -1) => return Some(Self::Left),
+            (0, 1) => return Some(Self::Right),
+            (-1, -1) => return Some(Self::UpLeft),
+            (-1, 1) => return Some(Self::UpRight),
+            (1, -1) => return Some(Self::DownLeft),
+            (1, 1) => return Some(Self::DownRight),
+            _ => return None,
+        }
+    }
+
+    // (row, col) change of one step, rows counting down the map.
+    pub fn delta(&self) -> (isize, isize) {
         match self {
-            Self::Up => Self::Down,
-            Self::Down => Self::Up,
-            Self::Left => Self::Right,
-            Self::Right => Self::Left,
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (-1, 1),
+            Self::DownLeft => (1, -1),
+            Self::DownRight => (1, 1),
         }
     }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (dr, dc) = self.delta();
+        return dr != 0 && dc != 0;
+    }
+
+    fn rotate(&self, eighths: usize) -> Self {
+        let index: usize = CLOCKWISE.iter().position(|x| x == self).unwrap();
+        return CLOCKWISE[(index + eighths) % CLOCKWISE.len()];
+    }
+
+    pub fn rotate_45_cw(&self) -> Self {
+        return self.rotate(1);
+    }
+
+    pub fn rotate_45_ccw(&self) -> Self {
+        return self.rotate(7);
+    }
+
+    pub fn rotate_90_cw(&self) -> Self {
+        return self.rotate(2);
+    }
+
+    pub fn rotate_90_ccw(&self) -> Self {
+        return self.rotate(6);
+    }
+
+    pub fn reverse(&self) -> Self {
+        return self.rotate(4);
+    }
 }
 
 #[derive(Clone, PartialEq, Hash, Eq, Ord, Copy)]
@@ -85,73 +191,92 @@ impl PartialOrd for Position {
 }
 
 impl Position {
+    // Panics when the walk goes past row or column 0, see checked_walk.
     pub fn walk(&self, steps: usize, direction: Direction) -> Self {
-        let mut new_position = self.clone();
-        match direction {
-            Direction::Up => new_position.row -= steps,
-            Direction::Down => new_position.row += steps,
-            Direction::Left => new_position.col -= steps,
-            Direction::Right => new_position.col += steps,
-        }
-        return new_position
+        return self.checked_walk(steps, direction).unwrap_or_else(|| panic!("walked {} {:?} off the map from {:?}", steps, direction, self));
+    }
+
+    // None when the walk goes past row or column 0.
+    pub fn checked_walk(&self, steps: usize, direction: Direction) -> Option<Self> {
+        let (dr, dc) = direction.delta();
+        return self.checked_offset((dr * steps as isize, dc * steps as isize));
+    }
+
+    // Walks on a map whose edges join up, so leaving one side comes back in on the other.
+    pub fn wrapping_walk(&self, steps: usize, direction: Direction, row_count: usize, col_count: usize) -> Self {
+        let (dr, dc) = direction.delta();
+        return self.wrapping_offset((dr * steps as isize, dc * steps as isize), row_count, col_count);
+    }
+
+    pub fn checked_offset(&self, delta: (isize, isize)) -> Option<Self> {
+        let row: usize = self.row.checked_add_signed(delta.0)?;
+        let col: usize = self.col.checked_add_signed(delta.1)?;
+        return Some(Position { row, col });
+    }
+
+    pub fn wrapping_offset(&self, delta: (isize, isize), row_count: usize, col_count: usize) -> Self {
+        let row: isize = (self.row as isize + delta.0).rem_euclid(row_count as isize);
+        let col: isize = (self.col as isize + delta.1).rem_euclid(col_count as isize);
+        return Position { row: row as usize, col: col as usize };
+    }
+
+    // The (dr, dc) that takes this position to the target.
+    pub fn offset_to(&self, target: &Self) -> (isize, isize) {
+        return (target.row as isize - self.row as isize, target.col as isize - self.col as isize);
     }
 
     pub fn direction(&self, next_position: Position) -> Direction {
-        if self.row > next_position.row {
-            return Direction::Up;
-        } else if self.row < next_position.row {
-            return Direction::Down;
-        } else if self.col > next_position.col {
-            return Direction::Left;
-        } else if self.col < next_position.col {
-            return Direction::Right;
-        } else {
-            panic!("Points are the same!");
-        }
+        return Direction::from_delta(self.offset_to(&next_position)).expect("Points are the same or not in a line!");
     }
 
     pub fn get_neighbours(&self, max_row: usize, max_col: usize) -> Vec<Position> {
-        let mut row: i32 = self.row as i32;
-        let mut col: i32 = self.col as i32;
-        let mut neighbours: Vec<Position> = Vec::new();
-
-        for d in Direction::iter() {
-            match d {
-                Direction::Up => {
-                    if self.row > 0 {
-                        neighbours.push(self.walk(1, d));
-                    }
-                },
-                Direction::Down => {
-                    if self.row < max_row - 1 {
-                        neighbours.push(self.walk(1, d));
-                    }
-                },
-                Direction::Left => {
-                    if self.col > 0 {
-                        neighbours.push(self.walk(1, d));
-                    }
-                },
-                Direction::Right => {
-                    if self.col < max_col - 1 {
-                        neighbours.push(self.walk(1, d));
-                    }
-                },
-            };
-        }
-        return neighbours;
+        return Direction::iter()
+            .filter_map(|d| self.checked_walk(1, d))
+            .filter(|p| p.row < max_row && p.col < max_col)
+            .collect::<Vec<Position>>();
     }
 
     pub fn manhattan_distance(&self, target: &Self) -> usize {
         return self.row.abs_diff(target.row) + self.col.abs_diff(target.col);
     }
+}
+
+// Point and Point64 use x for the column and y for the row.
+impl From<Position> for Point {
+    fn from(value: Position) -> Self {
+        return Point::new(value.col as i32, value.row as i32);
+    }
+}
+
+impl From<Position> for Point64 {
+    fn from(value: Position) -> Self {
+        return Point64::new(value.col as i64, value.row as i64);
+    }
+}
 
-    pub fn delta(&self, target: &Self) -> (i32, i32) {
-        return (self.row as i32 - target.row as i32, self.col as i32 - target.col as i32);
+impl From<Point> for Point64 {
+    fn from(value: Point) -> Self {
+        return Point64::new(value.x as i64, value.y as i64);
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+impl TryFrom<Point> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        return Ok(Position { row: usize::try_from(value.y)?, col: usize::try_from(value.x)? });
+    }
+}
+
+impl TryFrom<Point64> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Point64) -> Result<Self, Self::Error> {
+        return Ok(Position { row: usize::try_from(value.y)?, col: usize::try_from(value.x)? });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub struct Point {
     pub x: i32,
     pub y: i32,
@@ -159,7 +284,7 @@ pub struct Point {
 
 impl Point {
     pub fn new(x: i32, y: i32) -> Self {
-        return Self{x: x as i32, y: y as i32};
+        return Self{x, y};
     }
 
     pub fn walk(&self, x: i32, y: i32) -> Self {
@@ -167,7 +292,7 @@ impl Point {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub struct Point64 {
     pub x: i64,
     pub y: i64,
@@ -185,4 +310,271 @@ impl Point64 {
     pub fn walk32(&self, x: i32, y: i32) -> Self {
         return Self{ x: self.x + x as i64, y: self.y + y as i64 };
     }
-}
\ No newline at end of file
+}
+
+// A rectangular map stored row by row, indexed by Position. Reading outside the map with `get` gives None,
+// indexing outside it panics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    row_count: usize,
+    col_count: usize,
+}
+
+// An empty map, with no rows or columns.
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        return Grid { cells: Vec::new(), row_count: 0, col_count: 0 };
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> where AocError: From<T::Error> {
+    // One cell per character. A character that isn't a cell is reported at its line and column.
+    pub fn parse(input_lines: &[String]) -> Result<Self, AocError> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (i, line) in input_lines.iter().enumerate() {
+            rows.push(line.chars().enumerate()
+                .map(|(j, x)| T::try_from(x).map_err(|e| AocError::from(e).at_column(j + 1).at_line(i + 1)))
+                .collect::<Result<Vec<T>, AocError>>()?);
+        }
+        return Grid::from_rows(rows);
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(row_count: usize, col_count: usize, value: T) -> Self {
+        return Grid { cells: vec![value; row_count * col_count], row_count, col_count };
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AocError> {
+        let col_count: usize = rows.first().map(|x| x.len()).unwrap_or(0);
+        if let Some(i) = rows.iter().position(|x| x.len() != col_count) {
+            return Err(AocError::parse(format!("expected {} columns, got {}", col_count, rows[i].len())).at_line(i + 1));
+        }
+        let row_count: usize = rows.len();
+        return Ok(Grid { cells: rows.into_iter().flatten().collect::<Vec<T>>(), row_count, col_count });
+    }
+
+    pub fn row_count(&self) -> usize {
+        return self.row_count;
+    }
+
+    pub fn col_count(&self) -> usize {
+        return self.col_count;
+    }
+
+    pub fn contains(&self, p: Position) -> bool {
+        return p.row < self.row_count && p.col < self.col_count;
+    }
+
+    pub fn get(&self, p: Position) -> Option<&T> {
+        if !self.contains(p) {
+            return None;
+        }
+        return self.cells.get(p.row * self.col_count + p.col);
+    }
+
+    pub fn get_mut(&mut self, p: Position) -> Option<&mut T> {
+        if !self.contains(p) {
+            return None;
+        }
+        return self.cells.get_mut(p.row * self.col_count + p.col);
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let col_count: usize = self.col_count;
+        return (0..self.cells.len()).map(move |i| Position { row: i / col_count, col: i % col_count });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        return self.positions().zip(self.cells.iter());
+    }
+
+    // The position one (dr, dc) step away, if it is still on the map.
+    pub fn offset(&self, p: Position, delta: (isize, isize)) -> Option<Position> {
+        let next: Position = p.checked_offset(delta)?;
+        if self.contains(next) {
+            return Some(next);
+        }
+        return None;
+    }
+
+    // Up, down, left, right.
+    pub fn neighbours(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        return [(-1, 0), (1, 0), (0, -1), (0, 1)].into_iter().filter_map(move |x| self.offset(p, x));
+    }
+
+    // Up, down, left, right, then the diagonals up-left, up-right, down-left, down-right.
+    pub fn neighbours_8(&self, p: Position) -> impl Iterator<Item = Position> + '_ {
+        return [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (-1, 1), (1, -1), (1, 1)].into_iter().filter_map(move |x| self.offset(p, x));
+    }
+
+    // Every cell from `start` (included) stepping by (dr, dc) until the edge of the map.
+    pub fn line(&self, start: Position, delta: (isize, isize)) -> impl Iterator<Item = (Position, &T)> {
+        let first: Option<Position> = Some(start).filter(|x| self.contains(*x));
+        return std::iter::successors(first, move |x| self.offset(*x, delta)).map(|x| (x, &self[x]));
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        return &self.cells[(row * self.col_count)..((row + 1) * self.col_count)];
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &T> {
+        return self.cells.iter().skip(col).step_by(self.col_count.max(1)).take(self.row_count);
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return (0..self.row_count).map(|x| self.row(x));
+    }
+
+    // Down and to the right from `start`.
+    pub fn diagonal(&self, start: Position) -> impl Iterator<Item = (Position, &T)> {
+        return self.line(start, (1, 1));
+    }
+
+    // Down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: Position) -> impl Iterator<Item = (Position, &T)> {
+        return self.line(start, (1, -1));
+    }
+
+    pub fn find(&self, value: &T) -> Option<Position> where T: PartialEq {
+        return self.iter().find(|(_, x)| *x == value).map(|(p, _)| p);
+    }
+
+    pub fn find_all(&self, value: &T) -> Vec<Position> where T: PartialEq {
+        return self.iter().filter(|(_, x)| *x == value).map(|(p, _)| p).collect::<Vec<Position>>();
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        return Grid { cells: self.cells.iter().map(f).collect::<Vec<U>>(), row_count: self.row_count, col_count: self.col_count };
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Position) -> &Self::Output {
+        let (row_count, col_count) = (self.row_count, self.col_count);
+        return self.get(p).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", p, row_count, col_count));
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, p: Position) -> &mut Self::Output {
+        let (row_count, col_count) = (self.row_count, self.col_count);
+        return self.get_mut(p).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", p, row_count, col_count));
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(value: &str) -> Vec<String> {
+        return value.lines().map(str::to_string).collect::<Vec<String>>();
+    }
+
+    #[test]
+    fn grid_parse_and_index() {
+        let mut grid: Grid<char> = Grid::parse(&to_lines("#.S\n.#.\nE..")).unwrap();
+        assert!(grid.row_count() == 3 && grid.col_count() == 3);
+        assert!(grid[Position { row: 0, col: 2 }] == 'S');
+        assert!(grid.get(Position { row: 3, col: 0 }).is_none());
+        assert!(grid.find(&'E') == Some(Position { row: 2, col: 0 }));
+        assert!(grid.find_all(&'#') == vec![Position { row: 0, col: 0 }, Position { row: 1, col: 1 }]);
+
+        grid[Position { row: 1, col: 1 }] = '.';
+        assert!(grid.to_string() == "#.S\n...\nE..");
+        assert!(grid.map(|x| *x == '.').find_all(&true).len() == 6);
+
+        assert!(Grid::<char>::parse(&to_lines("..\n...")).unwrap_err().to_string() == "line 2: expected 2 columns, got 3");
+        assert!(Grid::<char>::parse(&[]).unwrap().positions().count() == 0);
+        assert!(Grid::<Direction>::parse(&to_lines("^>\n<x")).unwrap_err().to_string() == "line 2, column 2: bad direction 'x'");
+    }
+
+    #[test]
+    fn grid_neighbours_and_lines() {
+        let grid: Grid<char> = Grid::parse(&to_lines("abc\ndef\nghi")).unwrap();
+        let corner: Vec<char> = grid.neighbours(Position { row: 0, col: 0 }).map(|x| grid[x]).collect::<Vec<char>>();
+        assert!(corner == vec!['d', 'b']);
+        let middle: String = grid.neighbours_8(Position { row: 1, col: 1 }).map(|x| grid[x]).collect::<String>();
+        assert!(middle == "bhdfacgi");
+
+        assert!(grid.row(1) == ['d', 'e', 'f']);
+        assert!(grid.col(2).collect::<String>() == "cfi");
+        assert!(grid.rows().count() == 3);
+        assert!(grid.diagonal(Position { row: 0, col: 0 }).map(|x| x.1).collect::<String>() == "aei");
+        assert!(grid.anti_diagonal(Position { row: 0, col: 2 }).map(|x| x.1).collect::<String>() == "ceg");
+        assert!(grid.line(Position { row: 2, col: 1 }, (-1, 0)).map(|x| x.1).collect::<String>() == "heb");
+        assert!(grid.line(Position { row: 3, col: 0 }, (-1, 0)).count() == 0);
+    }
+
+    #[test]
+    fn directions() {
+        assert!(Direction::Up.rotate_45_cw() == Direction::UpRight);
+        assert!(Direction::Up.rotate_45_ccw() == Direction::UpLeft);
+        assert!(Direction::Left.rotate_90_cw() == Direction::Up);
+        assert!(Direction::Left.rotate_90_ccw() == Direction::Down);
+        assert!(Direction::DownRight.reverse() == Direction::UpLeft);
+        assert!(Direction::iter_all().all(|d| d.rotate_90_cw().rotate_90_ccw() == d && d.reverse().reverse() == d));
+        assert!(Direction::iter_all().all(|d| Direction::from_delta(d.delta()) == Some(d)));
+        assert!(Direction::from_delta((-7, 3)).is_none());
+        assert!(Direction::from_delta((-7, 7)) == Some(Direction::UpRight));
+        assert!(Direction::from_delta((0, -4)) == Some(Direction::Left));
+        assert!(Direction::from_delta((0, 0)).is_none());
+
+        assert!("^v<>".chars().map(|x| Direction::try_from(x).unwrap()).collect::<Vec<Direction>>() == vec![Direction::Up, Direction::Down, Direction::Left, Direction::Right]);
+        assert!("NSWE".chars().map(|x| Direction::try_from(x).unwrap()).eq("UDLR".chars().map(|x| Direction::try_from(x).unwrap())));
+        assert!("NE".parse::<Direction>().unwrap() == Direction::UpRight);
+        assert!("DL".parse::<Direction>().unwrap() == Direction::DownLeft);
+        assert!("NS".parse::<Direction>().is_err());
+        assert!("x".parse::<Direction>().unwrap_err().to_string() == "bad direction 'x'");
+
+        let p: Position = Position { row: 2, col: 2 };
+        assert!(p.walk(2, Direction::UpLeft) == Position { row: 0, col: 0 });
+        assert!(p.direction(Position { row: 3, col: 1 }) == Direction::DownLeft);
+        assert!(p.direction(Position { row: 0, col: 2 }) == Direction::Up);
+    }
+
+    #[test]
+    fn position_arithmetic() {
+        let p: Position = Position { row: 1, col: 2 };
+        assert!(p.checked_walk(1, Direction::Up) == Some(Position { row: 0, col: 2 }));
+        assert!(p.checked_walk(2, Direction::Up).is_none());
+        assert!(p.checked_walk(3, Direction::Left).is_none());
+        assert!(p.checked_walk(2, Direction::DownLeft) == Some(Position { row: 3, col: 0 }));
+        assert!(p.checked_offset((-1, -2)) == Some(Position { row: 0, col: 0 }));
+        assert!(p.offset_to(&Position { row: 0, col: 5 }) == (-1, 3));
+
+        assert!(p.wrapping_walk(2, Direction::Up, 4, 5) == Position { row: 3, col: 2 });
+        assert!(p.wrapping_walk(4, Direction::Right, 4, 5) == Position { row: 1, col: 1 });
+        assert!(p.wrapping_offset((-9, 13), 4, 5) == Position { row: 0, col: 0 });
+
+        assert!(Position { row: 0, col: 0 }.get_neighbours(0, 0).is_empty());
+        assert!(Position { row: 0, col: 0 }.get_neighbours(1, 1).is_empty());
+        assert!(Position { row: 0, col: 0 }.get_neighbours(2, 2) == vec![Position { row: 1, col: 0 }, Position { row: 0, col: 1 }]);
+
+        assert!(Point::from(p) == Point::new(2, 1));
+        assert!(Point64::from(Point::new(-3, 4)) == Point64::new(-3, 4));
+        assert!(Position::try_from(Point::new(2, 1)) == Ok(p));
+        assert!(Position::try_from(Point::new(-1, 1)).is_err());
+        assert!(Position::try_from(Point64::from(p)) == Ok(p));
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+// An undirected graph with named nodes. Names are interned, so the graph itself works on ids, which are handed out
+// in the order the names are first seen.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacent: Vec<HashSet<usize>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut graph: Graph = Graph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        return graph;
+    }
+
+    // The id for the name, adding a node if it's new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id: usize = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacent.push(HashSet::new());
+        return id;
+    }
+
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let a: usize = self.intern(a);
+        let b: usize = self.intern(b);
+        if a != b {
+            self.adjacent[a].insert(b);
+            self.adjacent[b].insert(a);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.names.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.names.is_empty();
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        return self.ids.get(name).copied();
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        return &self.names[id];
+    }
+
+    pub fn neighbours(&self, id: usize) -> &HashSet<usize> {
+        return &self.adjacent[id];
+    }
+
+    pub fn is_edge(&self, a: usize, b: usize) -> bool {
+        return self.adjacent[a].contains(&b);
+    }
+
+    // Every set of three nodes all joined to each other, each listed once with its ids in order.
+    pub fn triangles(&self) -> Vec<Vec<usize>> {
+        let mut triangles: Vec<Vec<usize>> = Vec::new();
+        for a in 0..self.len() {
+            for b in self.adjacent[a].iter().filter(|x| **x > a).sorted() {
+                for c in self.adjacent[*b].iter().filter(|x| *x > b && self.is_edge(a, **x)).sorted() {
+                    triangles.push(vec![a, *b, *c]);
+                }
+            }
+        }
+        return triangles;
+    }
+
+    // Every clique that can't be grown by adding another node, each with its ids in order.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut cliques: Vec<Vec<usize>> = Vec::new();
+        self.bron_kerbosch(&mut Vec::new(), (0..self.len()).collect(), HashSet::new(), 0, &mut |clique| {
+            cliques.push(clique.iter().copied().sorted().collect::<Vec<usize>>());
+            return 0;
+        });
+        return cliques;
+    }
+
+    // The largest clique, with its ids in order. Branches that can't beat the best so far are skipped rather
+    // than listing every maximal clique.
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        let mut best: Vec<usize> = Vec::new();
+        self.bron_kerbosch(&mut Vec::new(), (0..self.len()).collect(), HashSet::new(), 0, &mut |clique| {
+            if clique.len() > best.len() {
+                best = clique.iter().copied().sorted().collect::<Vec<usize>>();
+            }
+            return best.len() + 1;
+        });
+        return best;
+    }
+
+    // Bron-Kerbosch with pivoting. `clique` is the clique so far, `candidates` the nodes that could extend it
+    // and `excluded` those already tried. `found` gets each maximal clique and returns the smallest size still
+    // worth looking for, and branches that can't reach `wanted` nodes are cut.
+    fn bron_kerbosch(&self, clique: &mut Vec<usize>, mut candidates: HashSet<usize>, mut excluded: HashSet<usize>, mut wanted: usize, found: &mut impl FnMut(&[usize]) -> usize) -> usize {
+        if candidates.is_empty() && excluded.is_empty() {
+            return found(clique);
+        }
+        // Any maximal clique contains the pivot or one of its non-neighbours, so only those need trying.
+        let pivot: usize = *candidates.union(&excluded).max_by_key(|x| self.adjacent[**x].intersection(&candidates).count()).unwrap();
+        let to_try: Vec<usize> = candidates.difference(&self.adjacent[pivot]).copied().collect::<Vec<usize>>();
+        for v in to_try {
+            if clique.len() + candidates.len() < wanted {
+                break;
+            }
+            clique.push(v);
+            let next_candidates: HashSet<usize> = candidates.intersection(&self.adjacent[v]).copied().collect::<HashSet<usize>>();
+            let next_excluded: HashSet<usize> = excluded.intersection(&self.adjacent[v]).copied().collect::<HashSet<usize>>();
+            wanted = wanted.max(self.bron_kerbosch(clique, next_candidates, next_excluded, wanted, found));
+            clique.pop();
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+        return wanted;
+    }
+
+    // The node sets with at least one node whose name starts with the prefix.
+    pub fn with_name_prefix(&self, node_sets: impl IntoIterator<Item = Vec<usize>>, prefix: &str) -> Vec<Vec<usize>> {
+        return node_sets.into_iter().filter(|x| x.iter().any(|n| self.names[*n].starts_with(prefix))).collect::<Vec<Vec<usize>>>();
+    }
+
+    // The names in alphabetical order, joined with commas.
+    pub fn password(&self, nodes: &[usize]) -> String {
+        return nodes.iter().map(|x| self.name(*x)).sorted().join(",");
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph {
+        let edges: &str = "kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq tb-ka wh-tc yn-cg kh-ub ta-co de-co tc-td tb-wq wh-td \
+            ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq wq-vc wh-yn ka-de kh-ta co-tc wh-qp tb-vc td-yn";
+        return Graph::from_edges(edges.split(" ").map(|x| x.split_once("-").unwrap()));
+    }
+
+    #[test]
+    fn cliques() {
+        let graph: Graph = example();
+        assert!(graph.len() == 16);
+        assert!(graph.id("kh") == Some(0) && graph.name(1) == "tc");
+        assert!(graph.is_edge(graph.id("kh").unwrap(), graph.id("tc").unwrap()));
+
+        let triangles: Vec<Vec<usize>> = graph.triangles();
+        assert!(triangles.len() == 12);
+        assert!(triangles.iter().map(|x| graph.password(x)).contains(&"co,de,ta".to_string()));
+        assert!(graph.with_name_prefix(triangles, "t").len() == 7);
+
+        let maximum: Vec<usize> = graph.maximum_clique();
+        assert!(graph.password(&maximum) == "co,de,ka,ta");
+        let maximal: Vec<Vec<usize>> = graph.maximal_cliques();
+        assert!(maximal.iter().map(|x| x.len()).max() == Some(4));
+        assert!(maximal.iter().filter(|x| x.len() == 4).count() == 1);
+        // Every triangle is inside some maximal clique.
+        assert!(graph.triangles().iter().all(|t| maximal.iter().any(|c| t.iter().all(|x| c.contains(x)))));
+    }
+
+    #[test]
+    fn small_graphs() {
+        assert!(Graph::new().maximum_clique().is_empty());
+        assert!(Graph::new().maximal_cliques().is_empty());
+
+        // Two triangles sharing an edge, and a separate pair.
+        let graph: Graph = Graph::from_edges([("a", "b"), ("b", "c"), ("a", "c"), ("b", "d"), ("c", "d"), ("e", "f"), ("a", "a")]);
+        assert!(!graph.is_edge(0, 0));
+        let mut maximal: Vec<String> = graph.maximal_cliques().iter().map(|x| graph.password(x)).collect::<Vec<String>>();
+        maximal.sort();
+        assert!(maximal == vec!["a,b,c", "b,c,d", "e,f"]);
+        assert!(graph.maximum_clique().len() == 3);
+    }
+}
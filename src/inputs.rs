@@ -0,0 +1,141 @@
+use std::{env, io::{self, Read}, path::{Path, PathBuf}};
+
+use crate::error::AocError;
+use crate::generic;
+
+pub const YEAR: usize = 2024;
+pub const ROOT_VAR: &str = "AOC_INPUTS";
+pub const REAL_INPUT: &str = "input";
+const DEFAULT_ROOT: &str = "inputs";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    File(String),
+    Variant(String),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self, resolver: &InputResolver, day: usize) -> Result<Vec<String>, AocError> {
+        match self {
+            InputSource::File(path) => generic::read_in_file(path),
+            InputSource::Variant(variant) => resolver.read(day, variant),
+            InputSource::Stdin => read_stdin(),
+        }
+    }
+
+    // The variant this input is, taking a file or stdin to be a real input.
+    pub fn variant(&self) -> &str {
+        match self {
+            InputSource::Variant(variant) => return variant,
+            InputSource::File(_) | InputSource::Stdin => return REAL_INPUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputResolver {
+    root: PathBuf,
+    year: usize,
+}
+
+impl InputResolver {
+    pub fn new(root: impl Into<PathBuf>, year: usize) -> Self {
+        return Self { root: root.into(), year };
+    }
+
+    pub fn from_env() -> Self {
+        let root: String = env::var(ROOT_VAR).unwrap_or(DEFAULT_ROOT.to_string());
+        return Self::new(root, YEAR);
+    }
+
+    pub fn root(&self) -> &Path {
+        return self.root.as_path();
+    }
+
+    pub fn day_folders(&self, day: usize) -> Vec<PathBuf> {
+        // A root can hold several years, or just the days of this one.
+        return vec![
+            self.root.join(self.year.to_string()).join(format!("day_{}", day)),
+            self.root.join(format!("day_{}", day)),
+        ];
+    }
+
+    pub fn day_folder(&self, day: usize) -> PathBuf {
+        let folders: Vec<PathBuf> = self.day_folders(day);
+        return folders.iter().find(|x| x.is_dir()).unwrap_or(&folders[folders.len() - 1]).clone();
+    }
+
+    pub fn resolve(&self, day: usize, variant: &str) -> Result<PathBuf, AocError> {
+        let candidates: Vec<PathBuf> = self.day_folders(day).iter().map(|x| x.join(file_name(variant))).collect::<Vec<PathBuf>>();
+        match candidates.iter().find(|x| x.is_file()) {
+            Some(x) => return Ok(x.clone()),
+            None => return Err(AocError::MissingInput {
+                year: self.year,
+                day,
+                variant: variant.to_string(),
+                tried: candidates.iter().map(|x| x.display().to_string()).collect::<Vec<String>>(),
+            }),
+        }
+    }
+
+    pub fn read(&self, day: usize, variant: &str) -> Result<Vec<String>, AocError> {
+        let path: PathBuf = self.resolve(day, variant)?;
+        return generic::read_in_file(path.to_string_lossy().as_ref());
+    }
+}
+
+pub fn file_name(variant: &str) -> String {
+    if variant == REAL_INPUT {
+        return format!("{}.txt", REAL_INPUT);
+    } else {
+        return format!("input_{}.txt", variant);
+    }
+}
+
+pub fn read(day: usize, variant: &str) -> Result<Vec<String>, AocError> {
+    return InputResolver::from_env().read(day, variant);
+}
+
+pub fn read_stdin() -> Result<Vec<String>, AocError> {
+    let mut input_data: String = String::new();
+    io::stdin().read_to_string(&mut input_data).map_err(|e| AocError::Io { path: "<stdin>".to_string(), source: e })?;
+    return Ok(input_data.lines().map(str::to_string).collect::<Vec<String>>());
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn file_names() {
+        assert!(file_name("input") == "input.txt");
+        assert!(file_name("example_1") == "input_example_1.txt");
+        assert!(file_name("example_1_2") == "input_example_1_2.txt");
+
+        assert!(InputSource::Variant("example_2".to_string()).variant() == "example_2");
+        assert!(InputSource::File("my_input.txt".to_string()).variant() == REAL_INPUT);
+    }
+
+    #[test]
+    fn resolve_from_root() {
+        let root: PathBuf = env::temp_dir().join(format!("aoc_inputs_{}", std::process::id()));
+        fs::create_dir_all(root.join("2024").join("day_3")).unwrap();
+        fs::create_dir_all(root.join("day_4")).unwrap();
+        fs::write(root.join("2024").join("day_3").join("input.txt"), "abc\ndef\n").unwrap();
+        fs::write(root.join("day_4").join("input_example_1.txt"), "XMAS\n").unwrap();
+
+        let resolver: InputResolver = InputResolver::new(&root, 2024);
+        assert!(resolver.read(3, "input").unwrap() == vec!["abc".to_string(), "def".to_string()]);
+        assert!(resolver.read(4, "example_1").unwrap() == vec!["XMAS".to_string()]);
+        assert!(resolver.day_folder(3) == root.join("2024").join("day_3"));
+
+        let error = resolver.read(4, "input").unwrap_err();
+        assert!(matches!(error, AocError::MissingInput { day: 4, .. }));
+        assert!(error.to_string().contains(&root.join("day_4").join("input.txt").display().to_string()));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
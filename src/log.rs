@@ -0,0 +1,148 @@
+use std::{env, fmt, str::FromStr, sync::{Mutex, Once, atomic::{AtomicU8, Ordering}}};
+
+use crate::error::AocError;
+
+pub const LOG_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Ok(Level::Off),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(AocError::parse(format!("bad log level {:?}, expected off, info, debug or trace", value))),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+// A spec is a comma separated list of a default level and per day levels, e.g. "info,5=trace,day_7=off".
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSpec {
+    pub default: Level,
+    pub days: Vec<(usize, Level)>,
+}
+
+impl FromStr for LogSpec {
+    type Err = AocError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut spec: LogSpec = LogSpec { default: Level::Off, days: Vec::new() };
+        for (i, item) in value.split(",").map(str::trim).filter(|x| !x.is_empty()).enumerate() {
+            match item.split_once("=") {
+                Some((day, level)) => {
+                    let day: &str = day.trim();
+                    let day: usize = crate::error::parse_number::<usize>(day.strip_prefix("day_").unwrap_or(day)).map_err(|e| e.at_column(i + 1))?;
+                    spec.days.retain(|x| x.0 != day);
+                    spec.days.push((day, level.parse::<Level>().map_err(|e| e.at_column(i + 1))?));
+                },
+                None => spec.default = item.parse::<Level>().map_err(|e| e.at_column(i + 1))?,
+            }
+        }
+        return Ok(spec);
+    }
+}
+
+impl LogSpec {
+    pub fn level(&self, day: usize) -> Level {
+        return self.days.iter().find(|x| x.0 == day).map(|x| x.1).unwrap_or(self.default);
+    }
+
+    fn max_level(&self) -> Level {
+        return self.days.iter().map(|x| x.1).fold(self.default, |a, b| a.max(b));
+    }
+}
+
+static SPEC: Mutex<LogSpec> = Mutex::new(LogSpec { default: Level::Off, days: Vec::new() });
+// Highest level any day logs at, so quiet runs never take the lock.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static FROM_ENV: Once = Once::new();
+
+pub fn set_spec(spec: LogSpec) {
+    // A spec set by a run replaces whatever the environment asked for.
+    FROM_ENV.call_once(|| ());
+    MAX_LEVEL.store(spec.max_level() as u8, Ordering::Relaxed);
+    *SPEC.lock().unwrap() = spec;
+}
+
+pub fn enabled(day: usize, level: Level) -> bool {
+    FROM_ENV.call_once(|| {
+        if let Some(spec) = env::var(LOG_VAR).ok().and_then(|x| x.parse::<LogSpec>().ok()) {
+            MAX_LEVEL.store(spec.max_level() as u8, Ordering::Relaxed);
+            *SPEC.lock().unwrap() = spec;
+        }
+    });
+    if level == Level::Off || level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    return level <= SPEC.lock().unwrap().level(day);
+}
+
+// The logging macros use the `DAY` constant of the module they are called from, and only evaluate their
+// arguments when that day logs at the given level. Logs go to stderr so answers on stdout stay clean.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled(DAY, $crate::log::Level::Info) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled(DAY, $crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled(DAY, $crate::log::Level::Trace) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use {debug, info, trace};
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_spec() {
+        let spec: LogSpec = "debug, 5=trace, day_7=off".parse::<LogSpec>().unwrap();
+        assert!(spec.level(5) == Level::Trace);
+        assert!(spec.level(7) == Level::Off);
+        assert!(spec.level(16) == Level::Debug);
+        assert!(spec.max_level() == Level::Trace);
+
+        assert!("".parse::<LogSpec>().unwrap().level(1) == Level::Off);
+        assert!("4=trace,4=info".parse::<LogSpec>().unwrap().days == vec![(4, Level::Info)]);
+        assert!("info,loud".parse::<LogSpec>().unwrap_err().to_string().starts_with("column 2: bad log level"));
+        assert!("x=info".parse::<LogSpec>().is_err());
+    }
+}
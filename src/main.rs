@@ -1,5 +1,15 @@
 #![allow(dead_code)]
+pub mod answers;
+pub mod bench;
+pub mod error;
 pub mod generic;
+pub mod graph;
+pub mod inputs;
+pub mod log;
+pub mod scaffold;
+pub mod search;
+pub mod verify;
+pub mod solution;
 pub mod day_1;
 pub mod day_2;
 pub mod day_3;
@@ -26,17 +36,514 @@ pub mod day_23;
 pub mod day_24;
 pub mod day_25;
 
+use std::{env, path::{Path, PathBuf}, process, time::Instant};
+
+use answers::Answers;
+use inputs::{InputResolver, InputSource};
+use log::LogSpec;
+use solution::{Answer, Solution};
+
+const USAGE: &str = "Usage:
+    aoc run <day> <part> [--input <path> | --input - | --variant <name>] [--root <dir>] [--log <spec>]
+    aoc new <day>
+    aoc verify [<day>] [--root <dir>] [--answers <path>] [--log <spec>]
+    aoc bench [<day>] [--iterations <n>] [--variant <name>] [--root <dir>] [--baseline <path>] [--save <path>] [--threshold <percent>]
+    aoc circuit [--dot <path>] [--netlist <path>] [--input <path> | --input - | --variant <name>] [--root <dir>]
+
+A log spec is a level (off, info, debug, trace) and/or per day levels, e.g. --log info,16=trace";
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day: usize,
+    part: usize,
+    source: InputSource,
+    root: Option<String>,
+    log: Option<LogSpec>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    if args.len() < 2 {
+        return Err("Expected a day and a part".to_string());
+    }
+
+    let day: usize = args[0].parse::<usize>().map_err(|_| format!("Bad day given: {}", args[0]))?;
+    if solution::get_day(day).is_none() {
+        return Err(format!("No solution for day {}", day));
+    }
+
+    let part: usize = args[1].parse::<usize>().map_err(|_| format!("Bad part given: {}", args[1]))?;
+    if part != 1 && part != 2 {
+        return Err(format!("Part must be 1 or 2, got {}", part));
+    }
+
+    let mut source: InputSource = InputSource::Variant(inputs::REAL_INPUT.to_string());
+    let mut root: Option<String> = None;
+    let mut log: Option<LogSpec> = None;
+    let mut i: usize = 2;
+    while i < args.len() {
+        if i + 1 >= args.len() {
+            return Err(format!("{} needs a value", args[i]));
+        }
+        let value: String = args[i + 1].clone();
+        match args[i].as_str() {
+            "--input" | "-i" => {
+                if value == "-" {
+                    source = InputSource::Stdin;
+                } else {
+                    source = InputSource::File(value);
+                }
+            },
+            "--variant" => source = InputSource::Variant(value),
+            "--root" => root = Some(value),
+            "--log" => log = Some(value.parse::<LogSpec>().map_err(|e| format!("Bad log spec: {}", e))?),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+        i += 2;
+    }
+
+    return Ok(RunArgs { day, part, source, root, log });
+}
+
+#[derive(Debug, PartialEq)]
+struct VerifyArgs {
+    day: Option<usize>,
+    root: Option<String>,
+    answers: Option<String>,
+    log: Option<LogSpec>,
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut verify_args: VerifyArgs = VerifyArgs { day: None, root: None, answers: None, log: None };
+    let mut i: usize = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--root" | "--answers" | "--log" => {
+                if i + 1 >= args.len() {
+                    return Err(format!("{} needs a value", args[i]));
+                }
+                match args[i].as_str() {
+                    "--root" => verify_args.root = Some(args[i + 1].clone()),
+                    "--answers" => verify_args.answers = Some(args[i + 1].clone()),
+                    _ => verify_args.log = Some(args[i + 1].parse::<LogSpec>().map_err(|e| format!("Bad log spec: {}", e))?),
+                }
+                i += 2;
+            },
+            other => {
+                let day: usize = other.parse::<usize>().map_err(|_| format!("Unknown argument: {}", other))?;
+                if solution::get_day(day).is_none() {
+                    return Err(format!("No solution for day {}", day));
+                }
+                verify_args.day = Some(day);
+                i += 1;
+            },
+        }
+    }
+
+    return Ok(verify_args);
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchArgs {
+    day: Option<usize>,
+    iterations: usize,
+    variant: String,
+    root: Option<String>,
+    baseline: Option<String>,
+    save: Option<String>,
+    threshold: f64,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut bench_args: BenchArgs = BenchArgs { day: None, iterations: 10, variant: inputs::REAL_INPUT.to_string(), root: None, baseline: None, save: None, threshold: 0.1 };
+    let mut i: usize = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--iterations" | "--variant" | "--root" | "--baseline" | "--save" | "--threshold" => {
+                if i + 1 >= args.len() {
+                    return Err(format!("{} needs a value", args[i]));
+                }
+                let value: String = args[i + 1].clone();
+                match args[i].as_str() {
+                    "--iterations" => {
+                        bench_args.iterations = value.parse::<usize>().map_err(|_| format!("Bad iterations given: {}", value))?;
+                        if bench_args.iterations == 0 {
+                            return Err("Iterations must be at least 1".to_string());
+                        }
+                    },
+                    "--variant" => bench_args.variant = value,
+                    "--root" => bench_args.root = Some(value),
+                    "--baseline" => bench_args.baseline = Some(value),
+                    "--save" => bench_args.save = Some(value),
+                    _ => {
+                        let percent: f64 = value.trim_end_matches("%").parse::<f64>().map_err(|_| format!("Bad threshold given: {}", value))?;
+                        if percent < 0.0 {
+                            return Err(format!("Threshold must not be negative, got {}", value));
+                        }
+                        bench_args.threshold = percent / 100.0;
+                    },
+                }
+                i += 2;
+            },
+            other => {
+                let day: usize = other.parse::<usize>().map_err(|_| format!("Unknown argument: {}", other))?;
+                if solution::get_day(day).is_none() {
+                    return Err(format!("No solution for day {}", day));
+                }
+                bench_args.day = Some(day);
+                i += 1;
+            },
+        }
+    }
+
+    return Ok(bench_args);
+}
+
+// Where to write the day 24 circuit, at least one of a Graphviz graph and a netlist.
+#[derive(Debug, PartialEq)]
+struct CircuitArgs {
+    source: InputSource,
+    root: Option<String>,
+    dot: Option<String>,
+    netlist: Option<String>,
+}
+
+fn parse_circuit_args(args: &[String]) -> Result<CircuitArgs, String> {
+    let mut circuit_args: CircuitArgs = CircuitArgs { source: InputSource::Variant(inputs::REAL_INPUT.to_string()), root: None, dot: None, netlist: None };
+    let mut i: usize = 0;
+    while i < args.len() {
+        if i + 1 >= args.len() {
+            return Err(format!("{} needs a value", args[i]));
+        }
+        let value: String = args[i + 1].clone();
+        match args[i].as_str() {
+            "--input" | "-i" => {
+                if value == "-" {
+                    circuit_args.source = InputSource::Stdin;
+                } else {
+                    circuit_args.source = InputSource::File(value);
+                }
+            },
+            "--variant" => circuit_args.source = InputSource::Variant(value),
+            "--root" => circuit_args.root = Some(value),
+            "--dot" => circuit_args.dot = Some(value),
+            "--netlist" => circuit_args.netlist = Some(value),
+            other => return Err(format!("Unknown argument: {}", other)),
+        }
+        i += 2;
+    }
+
+    if circuit_args.dot.is_none() && circuit_args.netlist.is_none() {
+        return Err("Expected --dot and/or --netlist".to_string());
+    }
+    return Ok(circuit_args);
+}
+
+fn parse_new_args(args: &[String]) -> Result<usize, String> {
+    if args.len() != 1 {
+        return Err("Expected a day".to_string());
+    }
+
+    let day: usize = args[0].parse::<usize>().map_err(|_| format!("Bad day given: {}", args[0]))?;
+    if day == 0 {
+        return Err("Day must be at least 1".to_string());
+    }
+    return Ok(day);
+}
 
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|x| x.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("circuit") => circuit(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+fn run(args: &[String]) {
+    let run_args: RunArgs = match parse_run_args(args) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Some(spec) = run_args.log.clone() {
+        log::set_spec(spec);
+    }
+
+    let mut day: Box<dyn Solution> = solution::get_day(run_args.day).unwrap();
+    let resolver: InputResolver = match &run_args.root {
+        Some(root) => InputResolver::new(root, inputs::YEAR),
+        None => InputResolver::from_env(),
+    };
+    let input_lines: Vec<String> = match run_args.source.read(&resolver, run_args.day) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    day.set_variant(run_args.source.variant());
+    let start_time = Instant::now();
+    let answer: Answer = match day.parse(input_lines).and_then(|_| day.solve(run_args.part == 2)) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("Error in day {} part {}: {}", run_args.day, run_args.part, e);
+            process::exit(1);
+        }
+    };
+    let elapsed = start_time.elapsed();
+
+    println!("Day {} part {}: {}", run_args.day, run_args.part, answer);
+    println!("Elapsed: {:?}", elapsed);
 }
 
+fn verify(args: &[String]) {
+    let verify_args: VerifyArgs = match parse_verify_args(args) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Some(spec) = verify_args.log.clone() {
+        log::set_spec(spec);
+    }
+
+    let resolver: InputResolver = match &verify_args.root {
+        Some(root) => InputResolver::new(root, inputs::YEAR),
+        None => InputResolver::from_env(),
+    };
+    let answers: Result<Answers, error::AocError> = match &verify_args.answers {
+        Some(path) => Answers::load(Path::new(path)),
+        None => Answers::from_resolver(&resolver),
+    };
+    let answers: Answers = match answers {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Box<dyn Solution>> = solution::all_days().into_iter().filter(|x| verify_args.day.is_none() || verify_args.day == Some(x.day())).collect();
+    let checks: Vec<verify::Check> = verify::run_checks(days, &resolver, &answers);
+    println!("{}", verify::format_table(&checks));
+
+    if checks.iter().any(|x| x.status() == verify::Status::Fail) {
+        process::exit(1);
+    }
+}
+
+fn bench(args: &[String]) {
+    let bench_args: BenchArgs = match parse_bench_args(args) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    // Load the baseline first, so a bad path fails before minutes of timing.
+    let baseline: Vec<bench::Timing> = match &bench_args.baseline {
+        Some(path) => match bench::load_baseline(Path::new(path)) {
+            Ok(x) => x,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let resolver: InputResolver = match &bench_args.root {
+        Some(root) => InputResolver::new(root, inputs::YEAR),
+        None => InputResolver::from_env(),
+    };
+    let mut timings: Vec<bench::Timing> = Vec::new();
+    for mut day in solution::all_days().into_iter().filter(|x| bench_args.day.is_none() || bench_args.day == Some(x.day())) {
+        day.set_variant(&bench_args.variant);
+        let day_timings: Result<Vec<bench::Timing>, error::AocError> = resolver.read(day.day(), &bench_args.variant)
+            .and_then(|input_lines| bench::bench_day(day.as_mut(), &input_lines, bench_args.iterations));
+        match day_timings {
+            Ok(x) => timings.extend(x),
+            Err(error::AocError::MissingInput { .. }) => eprintln!("Skipping day {}, no input for variant {:?}", day.day(), bench_args.variant),
+            Err(e) => eprintln!("Error in day {}: {}", day.day(), e),
+        }
+    }
+
+    println!("{}", bench::format_table(&timings, &baseline, bench_args.threshold));
+
+    if let Some(path) = &bench_args.save {
+        if let Err(e) = bench::save_baseline(Path::new(path), &timings, bench_args.iterations) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        println!("Saved timings to {}", path);
+    }
+
+    let regressions: usize = timings.iter()
+        .filter(|t| baseline.iter().any(|b| b.day == t.day && b.phase == t.phase && t.regressed(b, bench_args.threshold)))
+        .count();
+    if regressions > 0 {
+        eprintln!("{} timings regressed by more than {}%", regressions, bench_args.threshold * 100.0);
+        process::exit(1);
+    }
+}
+
+fn circuit(args: &[String]) {
+    let circuit_args: CircuitArgs = match parse_circuit_args(args) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let resolver: InputResolver = match &circuit_args.root {
+        Some(root) => InputResolver::new(root, inputs::YEAR),
+        None => InputResolver::from_env(),
+    };
+    let written: Result<Vec<PathBuf>, error::AocError> = circuit_args.source.read(&resolver, 24)
+        .and_then(|input_lines| day_24::export_circuit(&input_lines, circuit_args.dot.as_deref().map(Path::new), circuit_args.netlist.as_deref().map(Path::new)));
+    match written {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+        },
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn new(args: &[String]) {
+    let day: usize = match parse_new_args(args) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let created: Vec<PathBuf> = match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), &InputResolver::from_env(), day) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    for path in created {
+        println!("Created {}", path.display());
+    }
+    println!("Registered day_{} in src/main.rs and src/solution.rs", day);
+}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        return args.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    }
 
     #[test]
-    fn first_example() {
+    fn parse_default_input() {
+        let run_args = parse_run_args(&to_args(&["16", "2"])).unwrap();
+        assert!(run_args == RunArgs { day: 16, part: 2, source: InputSource::Variant("input".to_string()), root: None, log: None });
+    }
+
+    #[test]
+    fn parse_given_input() {
+        let run_args = parse_run_args(&to_args(&["16", "2", "--input", "my.txt"])).unwrap();
+        assert!(run_args.source == InputSource::File("my.txt".to_string()));
+
+        let run_args = parse_run_args(&to_args(&["16", "2", "-i", "-"])).unwrap();
+        assert!(run_args.source == InputSource::Stdin);
 
+        let run_args = parse_run_args(&to_args(&["16", "2", "--variant", "example_2", "--root", "/tmp/aoc"])).unwrap();
+        assert!(run_args.source == InputSource::Variant("example_2".to_string()));
+        assert!(run_args.root == Some("/tmp/aoc".to_string()));
+
+        let run_args = parse_run_args(&to_args(&["16", "2", "--log", "info,16=trace"])).unwrap();
+        assert!(run_args.log.unwrap().level(16) == log::Level::Trace);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_bad_args() {
+        assert!(parse_run_args(&to_args(&["16"])).is_err());
+        assert!(parse_run_args(&to_args(&["26", "1"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "3"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "1", "--input"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "1", "--verbose"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "1", "--root"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "1", "--log", "loud"])).is_err());
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert!(parse_verify_args(&to_args(&[])).unwrap() == VerifyArgs { day: None, root: None, answers: None, log: None });
+        let verify_args = parse_verify_args(&to_args(&["16", "--answers", "mine.toml"])).unwrap();
+        assert!(verify_args == VerifyArgs { day: Some(16), root: None, answers: Some("mine.toml".to_string()), log: None });
+        assert!(parse_verify_args(&to_args(&["--log", "debug"])).unwrap().log.unwrap().default == log::Level::Debug);
+        assert!(parse_verify_args(&to_args(&["26"])).is_err());
+        assert!(parse_verify_args(&to_args(&["--root"])).is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        let bench_args = parse_bench_args(&to_args(&[])).unwrap();
+        assert!(bench_args == BenchArgs { day: None, iterations: 10, variant: "input".to_string(), root: None, baseline: None, save: None, threshold: 0.1 });
+
+        let bench_args = parse_bench_args(&to_args(&["16", "--iterations", "5", "--baseline", "old.json", "--save", "new.json", "--threshold", "25%"])).unwrap();
+        assert!(bench_args == BenchArgs { day: Some(16), iterations: 5, variant: "input".to_string(), root: None, baseline: Some("old.json".to_string()), save: Some("new.json".to_string()), threshold: 0.25 });
+
+        assert!(parse_bench_args(&to_args(&["--iterations", "0"])).is_err());
+        assert!(parse_bench_args(&to_args(&["--threshold", "-5"])).is_err());
+        assert!(parse_bench_args(&to_args(&["--save"])).is_err());
+        assert!(parse_bench_args(&to_args(&["26"])).is_err());
+    }
+
+    #[test]
+    fn parse_circuit() {
+        let circuit_args = parse_circuit_args(&to_args(&["--dot", "adder.dot"])).unwrap();
+        assert!(circuit_args == CircuitArgs { source: InputSource::Variant("input".to_string()), root: None, dot: Some("adder.dot".to_string()), netlist: None });
+
+        let circuit_args = parse_circuit_args(&to_args(&["--netlist", "adder.txt", "--input", "-", "--dot", "adder.dot"])).unwrap();
+        assert!(circuit_args.source == InputSource::Stdin);
+        assert!(circuit_args.dot == Some("adder.dot".to_string()) && circuit_args.netlist == Some("adder.txt".to_string()));
+
+        assert!(parse_circuit_args(&to_args(&[])).is_err());
+        assert!(parse_circuit_args(&to_args(&["--variant", "example_1"])).is_err());
+        assert!(parse_circuit_args(&to_args(&["--dot"])).is_err());
+        assert!(parse_circuit_args(&to_args(&["--dot", "adder.dot", "--svg", "adder.svg"])).is_err());
+    }
+
+    #[test]
+    fn parse_new() {
+        assert!(parse_new_args(&to_args(&["26"])).unwrap() == 26);
+        assert!(parse_new_args(&to_args(&[])).is_err());
+        assert!(parse_new_args(&to_args(&["0"])).is_err());
+        assert!(parse_new_args(&to_args(&["x"])).is_err());
+        assert!(parse_new_args(&to_args(&["26", "27"])).is_err());
+        assert!(parse_run_args(&to_args(&["16", "1", "--verbose", "2"])).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::error::AocError;
+use crate::inputs::{self, InputResolver};
+
+const TEMPLATE: &str = include_str!("day_x.rs");
+const PLACEHOLDER_VARIANTS: [&str; 2] = ["example_1", inputs::REAL_INPUT];
+
+pub fn render_day(day: usize) -> String {
+    return TEMPLATE
+        .replacen("const DAY: usize = 0;", format!("const DAY: usize = {};", day).as_str(), 1)
+        .replace("DayX", format!("Day{}", day).as_str());
+}
+
+pub fn register_module(main_source: &str, day: usize) -> Result<String, AocError> {
+    let mut lines: Vec<String> = main_source.lines().map(str::to_string).collect::<Vec<String>>();
+    let day_numbers: Vec<(usize, usize)> = lines.iter().enumerate()
+        .filter_map(|(i, x)| x.strip_prefix("pub mod day_").and_then(|x| x.strip_suffix(";")).and_then(|x| x.parse::<usize>().ok()).map(|x| (i, x)))
+        .collect::<Vec<(usize, usize)>>();
+    if day_numbers.is_empty() {
+        return Err(AocError::parse("no day modules found in main.rs"));
+    }
+
+    // Keep the modules in day order.
+    let insert_index: usize = match day_numbers.iter().rfind(|(_, x)| *x < day) {
+        Some((i, _)) => i + 1,
+        None => day_numbers[0].0,
+    };
+    lines.insert(insert_index, format!("pub mod day_{};", day));
+    return Ok(lines.join("\n") + "\n");
+}
+
+pub fn register_solution(solution_source: &str, day: usize) -> Result<String, AocError> {
+    let use_start: usize = solution_source.find("use crate::{day_").ok_or(AocError::parse("no day imports found in solution.rs"))?;
+    let use_end: usize = use_start + solution_source[use_start..].find("};").ok_or(AocError::parse("unterminated day imports in solution.rs"))?;
+    let new_source: String = format!("{}, day_{}{}", &solution_source[..use_end], day, &solution_source[use_end..]);
+
+    let mut lines: Vec<String> = new_source.lines().map(str::to_string).collect::<Vec<String>>();
+    let day_numbers: Vec<(usize, usize)> = lines.iter().enumerate()
+        .filter_map(|(i, x)| x.trim().strip_prefix("Box::new(day_").and_then(|x| x.split_once("::")).and_then(|x| x.0.parse::<usize>().ok()).map(|x| (i, x)))
+        .collect::<Vec<(usize, usize)>>();
+    if day_numbers.is_empty() {
+        return Err(AocError::parse("no days found in all_days"));
+    }
+
+    // Keep all_days in day order, the same as the modules.
+    let insert_index: usize = match day_numbers.iter().rfind(|(_, x)| *x < day) {
+        Some((i, _)) => i + 1,
+        None => day_numbers[0].0,
+    };
+    lines.insert(insert_index, format!("        Box::new(day_{}::Day{}::default()),", day, day));
+    return Ok(lines.join("\n") + "\n");
+}
+
+pub fn new_day(crate_root: &Path, resolver: &InputResolver, day: usize) -> Result<Vec<PathBuf>, AocError> {
+    let day_path: PathBuf = crate_root.join("src").join(format!("day_{}.rs", day));
+    let main_path: PathBuf = crate_root.join("src").join("main.rs");
+    let solution_path: PathBuf = crate_root.join("src").join("solution.rs");
+
+    if day_path.exists() {
+        return Err(AocError::AlreadyExists { path: day_path.display().to_string() });
+    }
+    let main_source: String = read_source(&main_path)?;
+    if main_source.lines().any(|x| x == format!("pub mod day_{};", day)) {
+        return Err(AocError::AlreadyExists { path: format!("pub mod day_{} in {}", day, main_path.display()) });
+    }
+    let new_main_source: String = register_module(&main_source, day)?;
+    let new_solution_source: String = register_solution(&read_source(&solution_path)?, day)?;
+
+    let mut created: Vec<PathBuf> = Vec::new();
+    write_source(&day_path, &render_day(day))?;
+    created.push(day_path);
+    write_source(&main_path, &new_main_source)?;
+    write_source(&solution_path, &new_solution_source)?;
+
+    // Inputs may already be downloaded, so only missing ones are created.
+    let day_folder: PathBuf = resolver.day_folder(day);
+    fs::create_dir_all(&day_folder).map_err(|e| AocError::Write { path: day_folder.display().to_string(), source: e })?;
+    for variant in PLACEHOLDER_VARIANTS {
+        let input_path: PathBuf = day_folder.join(inputs::file_name(variant));
+        if !input_path.exists() {
+            write_source(&input_path, "")?;
+            created.push(input_path);
+        }
+    }
+
+    return Ok(created);
+}
+
+fn read_source(path: &Path) -> Result<String, AocError> {
+    return fs::read_to_string(path).map_err(|e| AocError::Io { path: path.display().to_string(), source: e });
+}
+
+fn write_source(path: &Path, contents: &str) -> Result<(), AocError> {
+    return fs::write(path, contents).map_err(|e| AocError::Write { path: path.display().to_string(), source: e });
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const MAIN_SOURCE: &str = "pub mod generic;\npub mod day_1;\npub mod day_2;\n\nfn main() {}\n";
+    const SOLUTION_SOURCE: &str = "use crate::{day_1,\n    day_2};\n\npub fn all_days() -> Vec<Box<dyn Solution>> {\n    return vec![\n        Box::new(day_1::Day1::default()),\n        Box::new(day_2::Day2::default()),\n    ];\n}\n";
+
+    #[test]
+    fn render_template() {
+        let source: String = render_day(26);
+        assert!(source.starts_with("const DAY: usize = 26;"));
+        assert!(source.contains("pub struct Day26 {"));
+        assert!(source.contains("impl Solution for Day26 {"));
+        assert!(!source.contains("DayX"));
+    }
+
+    #[test]
+    fn register_in_order() {
+        assert!(register_module(MAIN_SOURCE, 3).unwrap() == "pub mod generic;\npub mod day_1;\npub mod day_2;\npub mod day_3;\n\nfn main() {}\n");
+        assert!(register_module("pub mod day_2;\n", 1).unwrap() == "pub mod day_1;\npub mod day_2;\n");
+        assert!(register_module("fn main() {}\n", 1).is_err());
+
+        let solution_source: String = register_solution(SOLUTION_SOURCE, 3).unwrap();
+        assert!(solution_source.contains("    day_2, day_3};"));
+        assert!(solution_source.contains("        Box::new(day_2::Day2::default()),\n        Box::new(day_3::Day3::default()),\n    ];"));
+        // A day between two others goes between them, and one before them all goes first.
+        let solution_source: String = register_solution(&SOLUTION_SOURCE.replace("day_2", "day_4").replace("Day2", "Day4"), 3).unwrap();
+        assert!(solution_source.contains("        Box::new(day_1::Day1::default()),\n        Box::new(day_3::Day3::default()),\n        Box::new(day_4::Day4::default()),\n"));
+        let solution_source: String = register_solution(SOLUTION_SOURCE, 0).unwrap();
+        assert!(solution_source.contains("vec![\n        Box::new(day_0::Day0::default()),\n        Box::new(day_1::Day1::default()),"));
+        assert!(solution_source.ends_with("];\n}\n"));
+    }
+
+    #[test]
+    fn new_day_files() {
+        let root: PathBuf = env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src").join("main.rs"), MAIN_SOURCE).unwrap();
+        fs::write(root.join("src").join("solution.rs"), SOLUTION_SOURCE).unwrap();
+        let resolver: InputResolver = InputResolver::new(root.join("inputs"), inputs::YEAR);
+
+        let created: Vec<PathBuf> = new_day(&root, &resolver, 3).unwrap();
+        assert!(created.len() == 3);
+        assert!(fs::read_to_string(root.join("src").join("day_3.rs")).unwrap() == render_day(3));
+        assert!(fs::read_to_string(root.join("src").join("main.rs")).unwrap().contains("pub mod day_3;"));
+        assert!(root.join("inputs").join("day_3").join("input_example_1.txt").is_file());
+        assert!(root.join("inputs").join("day_3").join("input.txt").is_file());
+
+        let main_source: String = fs::read_to_string(root.join("src").join("main.rs")).unwrap();
+        assert!(matches!(new_day(&root, &resolver, 3), Err(AocError::AlreadyExists { .. })));
+        assert!(fs::read_to_string(root.join("src").join("main.rs")).unwrap() == main_source);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Shortest distances from the start states, along with every state that reaches each one at its best distance.
+// Following the first predecessor back gives one shortest path, following all of them gives every shortest path.
+// Zero cost moves can make the predecessors loop, since states joined by free moves are all as close as each other.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    distances: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> SearchResult<S> {
+    fn new() -> Self {
+        return Self { distances: HashMap::new(), predecessors: HashMap::new() };
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        return self.distances.get(state).copied();
+    }
+
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        return &self.distances;
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        return self.distances.contains_key(state);
+    }
+
+    // The states one step before this one on its shortest paths, empty for the start states.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        return self.predecessors.get(state).map(|x| x.as_slice()).unwrap_or(&[]);
+    }
+
+    // One shortest path, from a start state to the target.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        if !self.contains(target) {
+            return None;
+        }
+        let mut path: Vec<S> = vec![target.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        return Some(path);
+    }
+
+    // Every shortest path to the target that doesn't go through a state twice. There can be a lot of them, see
+    // on_shortest_paths for just the states.
+    pub fn all_paths_to(&self, target: &S) -> Vec<Vec<S>> {
+        let mut paths: Vec<Vec<S>> = Vec::new();
+        if self.contains(target) {
+            self.extend_paths(&mut vec![target.clone()], &mut paths);
+        }
+        return paths;
+    }
+
+    // Walks back from the end of a reversed path, keeping each one that gets to a start.
+    fn extend_paths(&self, path: &mut Vec<S>, paths: &mut Vec<Vec<S>>) {
+        let previous: &[S] = self.predecessors(path.last().unwrap());
+        if previous.is_empty() {
+            paths.push(path.iter().rev().cloned().collect::<Vec<S>>());
+            return;
+        }
+        for p in previous {
+            if !path.contains(p) {
+                path.push(p.clone());
+                self.extend_paths(path, paths);
+                path.pop();
+            }
+        }
+    }
+
+    // All the states on any shortest path to any of the targets.
+    pub fn on_shortest_paths(&self, targets: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen: HashSet<S> = HashSet::new();
+        let mut to_do: Vec<S> = targets.into_iter().filter(|x| self.contains(x)).collect::<Vec<S>>();
+        while let Some(state) = to_do.pop() {
+            if seen.insert(state.clone()) {
+                to_do.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        return seen;
+    }
+
+    fn add_predecessor(&mut self, state: &S, previous: &S) {
+        self.predecessors.entry(state.clone()).or_default().push(previous.clone());
+    }
+}
+
+// Every move costs 1.
+pub fn bfs<S: Clone + Eq + Hash>(starts: impl IntoIterator<Item = S>, mut neighbours: impl FnMut(&S) -> Vec<S>) -> SearchResult<S> {
+    let mut result: SearchResult<S> = SearchResult::new();
+    let mut to_do: VecDeque<S> = VecDeque::new();
+    for s in starts {
+        if !result.contains(&s) {
+            result.distances.insert(s.clone(), 0);
+            to_do.push_back(s);
+        }
+    }
+
+    while let Some(state) = to_do.pop_front() {
+        let distance: usize = result.distances[&state];
+        for n in neighbours(&state) {
+            match result.distance(&n) {
+                None => {
+                    result.distances.insert(n.clone(), distance + 1);
+                    result.add_predecessor(&n, &state);
+                    to_do.push_back(n);
+                },
+                Some(d) if d == distance + 1 => result.add_predecessor(&n, &state),
+                Some(_) => (),
+            }
+        }
+    }
+    return result;
+}
+
+pub fn dijkstra<S: Clone + Eq + Hash>(starts: impl IntoIterator<Item = S>, mut neighbours: impl FnMut(&S) -> Vec<(S, usize)>) -> SearchResult<S> {
+    let mut result: SearchResult<S> = SearchResult::new();
+    let mut done: HashSet<S> = HashSet::new();
+    // The heap holds indexes into queued so the states don't need to be Ord.
+    let mut queued: Vec<S> = Vec::new();
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+    for s in starts {
+        if !result.contains(&s) {
+            result.distances.insert(s.clone(), 0);
+            heap.push(Reverse((0, queued.len())));
+            queued.push(s);
+        }
+    }
+
+    while let Some(Reverse((distance, index))) = heap.pop() {
+        let state: S = queued[index].clone();
+        if done.contains(&state) {
+            continue;
+        }
+        done.insert(state.clone());
+
+        for (n, cost) in neighbours(&state) {
+            // A state that is already done is never further away than this one, so it is never queued again. A zero
+            // cost move can still reach it just as cheaply though, and that is another way in. Start states have no
+            // predecessors, so they stay where every path begins.
+            let new_distance: usize = distance + cost;
+            match result.distance(&n) {
+                Some(d) if d < new_distance => (),
+                Some(d) if d == new_distance => {
+                    if n != state && result.predecessors.contains_key(&n) {
+                        result.add_predecessor(&n, &state);
+                    }
+                },
+                _ => {
+                    result.distances.insert(n.clone(), new_distance);
+                    result.predecessors.insert(n.clone(), vec![state.clone()]);
+                    heap.push(Reverse((new_distance, queued.len())));
+                    queued.push(n);
+                },
+            }
+        }
+    }
+    return result;
+}
+
+// The cost and one path to the nearest goal. Each state is closed the first time it is popped, so the heuristic
+// must be consistent: never more than the cost of a move plus the heuristic from where that move lands.
+pub fn astar<S: Clone + Eq + Hash>(start: S, is_goal: impl Fn(&S) -> bool, mut neighbours: impl FnMut(&S) -> Vec<(S, usize)>, heuristic: impl Fn(&S) -> usize) -> Option<(usize, Vec<S>)> {
+    let mut result: SearchResult<S> = SearchResult::new();
+    let mut done: HashSet<S> = HashSet::new();
+    let mut queued: Vec<S> = vec![start.clone()];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    result.distances.insert(start, 0);
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let state: S = queued[index].clone();
+        if done.contains(&state) {
+            continue;
+        }
+        let distance: usize = result.distances[&state];
+        if is_goal(&state) {
+            return Some((distance, result.path_to(&state).unwrap()));
+        }
+        done.insert(state.clone());
+
+        for (n, cost) in neighbours(&state) {
+            let new_distance: usize = distance + cost;
+            if done.contains(&n) || result.distance(&n).is_some_and(|d| d <= new_distance) {
+                continue;
+            }
+            result.distances.insert(n.clone(), new_distance);
+            result.predecessors.insert(n.clone(), vec![state.clone()]);
+            heap.push(Reverse((new_distance + heuristic(&n), queued.len())));
+            queued.push(n);
+        }
+    }
+    return None;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::{Grid, Position};
+
+    fn maze() -> Grid<char> {
+        let lines: Vec<String> = ["S..#", ".#..", "...E"].iter().map(|x| x.to_string()).collect();
+        return Grid::parse(&lines).unwrap();
+    }
+
+    fn open_neighbours(grid: &Grid<char>, p: &Position) -> Vec<Position> {
+        return grid.neighbours(*p).filter(|x| grid[*x] != '#').collect::<Vec<Position>>();
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let grid: Grid<char> = maze();
+        let start: Position = grid.find(&'S').unwrap();
+        let end: Position = grid.find(&'E').unwrap();
+        let result: SearchResult<Position> = bfs([start], |p| open_neighbours(&grid, p));
+
+        assert!(result.distance(&end) == Some(5));
+        assert!(result.distance(&Position { row: 0, col: 3 }).is_none());
+        assert!(result.path_to(&end).unwrap().len() == 6);
+        assert!(result.path_to(&start) == Some(vec![start]));
+        // Below the wall at (1, 1), or above it and then down either side of (1, 2).
+        assert!(result.all_paths_to(&end).len() == 3);
+        assert!(result.on_shortest_paths([end]).len() == 10);
+    }
+
+    #[test]
+    fn weighted_paths() {
+        // a -> b -> d costs 1 + 4, a -> c -> d costs 3 + 2, a -> d costs 6.
+        let edges: HashMap<char, Vec<(char, usize)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 3), ('d', 6)]),
+            ('b', vec![('d', 4)]),
+            ('c', vec![('d', 2)]),
+            ('d', vec![('e', 0)]),
+            ('e', vec![('d', 0)]),
+        ]);
+        let neighbours = |x: &char| edges.get(x).cloned().unwrap_or_default();
+        let result: SearchResult<char> = dijkstra(['a'], neighbours);
+
+        assert!(result.distance(&'d') == Some(5));
+        assert!(result.distance(&'e') == Some(5));
+        assert!(result.predecessors(&'a').is_empty());
+        assert!(result.predecessors(&'e') == ['d']);
+        let mut paths: Vec<Vec<char>> = result.all_paths_to(&'e');
+        paths.sort();
+        assert!(paths == vec![vec!['a', 'b', 'd', 'e'], vec!['a', 'c', 'd', 'e']]);
+        // d and e are a free move apart, so each is a way into the other.
+        assert!(result.all_paths_to(&'d').len() == 2);
+        assert!(result.on_shortest_paths(['d']) == HashSet::from(['a', 'b', 'c', 'd', 'e']));
+        assert!(result.on_shortest_paths(['b']) == HashSet::from(['a', 'b']));
+
+        assert!(astar('a', |x| *x == 'e', neighbours, |_| 0).map(|x| x.0) == Some(5));
+        assert!(astar('b', |x| *x == 'a', neighbours, |_| 0).is_none());
+    }
+
+    #[test]
+    fn zero_cost_predecessors() {
+        // b is reached from a directly, and just as cheaply through c with a free move.
+        let edges: HashMap<char, Vec<(char, usize)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 1)]),
+            ('c', vec![('b', 0)]),
+        ]);
+        let result: SearchResult<char> = dijkstra(['a'], |x| edges.get(x).cloned().unwrap_or_default());
+
+        assert!(result.distance(&'b') == Some(1));
+        let mut predecessors: Vec<char> = result.predecessors(&'b').to_vec();
+        predecessors.sort();
+        assert!(predecessors == vec!['a', 'c']);
+        let mut paths: Vec<Vec<char>> = result.all_paths_to(&'b');
+        paths.sort();
+        assert!(paths == vec![vec!['a', 'b'], vec!['a', 'c', 'b']]);
+        assert!(result.on_shortest_paths(['b']) == HashSet::from(['a', 'b', 'c']));
+
+        // With free moves both ways b and c are each a way into the other, but a path never goes round the loop.
+        let edges: HashMap<char, Vec<(char, usize)>> = HashMap::from([
+            ('a', vec![('b', 1), ('c', 1)]),
+            ('b', vec![('c', 0), ('d', 1)]),
+            ('c', vec![('b', 0)]),
+        ]);
+        let result: SearchResult<char> = dijkstra(['a'], |x| edges.get(x).cloned().unwrap_or_default());
+        assert!(result.predecessors(&'a').is_empty());
+        let mut paths: Vec<Vec<char>> = result.all_paths_to(&'d');
+        paths.sort();
+        assert!(paths == vec![vec!['a', 'b', 'd'], vec!['a', 'c', 'b', 'd']]);
+        assert!(result.path_to(&'d').unwrap().len() == 3);
+    }
+
+    #[test]
+    fn astar_grid() {
+        let grid: Grid<char> = maze();
+        let start: Position = grid.find(&'S').unwrap();
+        let end: Position = grid.find(&'E').unwrap();
+        let (cost, path) = astar(start, |x| *x == end, |p| open_neighbours(&grid, p).into_iter().map(|x| (x, 1)).collect(), |x| x.manhattan_distance(&end)).unwrap();
+
+        assert!(cost == 5);
+        assert!(path.first() == Some(&start) && path.last() == Some(&end));
+        assert!(path.windows(2).all(|x| x[0].manhattan_distance(&x[1]) == 1));
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use crate::error::AocError;
+use crate::{day_1, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9, day_10, day_11, day_12, day_13,
+    day_14, day_15, day_16, day_17, day_18, day_19, day_20, day_21, day_22, day_23, day_24, day_25};
+
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+// Answers are compared the way they're printed, so a day that builds its number as a string still matches an
+// unquoted number in answers.toml.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Number(x), Answer::Number(y)) => return x == y,
+            _ => return self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(x) => write!(f, "{}", x),
+            Answer::Text(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+// Numbers too big for an i64 are kept as text rather than wrapped.
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        match i64::try_from(value) {
+            Ok(x) => return Answer::Number(x),
+            Err(_) => return Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        return Answer::Number(value as i64);
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        return Answer::Number(value);
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        match i64::try_from(value) {
+            Ok(x) => return Answer::Number(x),
+            Err(_) => return Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        return Answer::Text(value);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        return Answer::Text(value.to_string());
+    }
+}
+
+pub trait Solution {
+    fn day(&self) -> usize;
+    // Called before parse with the name of the input, for days where the examples are a different size to the
+    // real puzzle.
+    fn set_variant(&mut self, _variant: &str) {}
+    fn parse(&mut self, input_lines: Vec<String>) -> Result<(), AocError>;
+    fn part1(&self) -> Result<Answer, AocError>;
+    fn part2(&self) -> Result<Answer, AocError>;
+
+    fn solve(&self, part_2: bool) -> Result<Answer, AocError> {
+        if !part_2 {
+            return self.part1();
+        } else {
+            return self.part2();
+        }
+    }
+}
+
+// The state a day built in parse, for days where there is nothing sensible to start from before then.
+pub fn parsed<T>(state: &Option<T>, day: usize) -> Result<&T, AocError> {
+    return state.as_ref().ok_or(AocError::parse(format!("day {} has not parsed any input", day)));
+}
+
+pub fn all_days() -> Vec<Box<dyn Solution>> {
+    return vec![
+        Box::new(day_1::Day1::default()),
+        Box::new(day_2::Day2::default()),
+        Box::new(day_3::Day3::default()),
+        Box::new(day_4::Day4::default()),
+        Box::new(day_5::Day5::default()),
+        Box::new(day_6::Day6::default()),
+        Box::new(day_7::Day7::default()),
+        Box::new(day_8::Day8::default()),
+        Box::new(day_9::Day9::default()),
+        Box::new(day_10::Day10::default()),
+        Box::new(day_11::Day11::default()),
+        Box::new(day_12::Day12::default()),
+        Box::new(day_13::Day13::default()),
+        Box::new(day_14::Day14::default()),
+        Box::new(day_15::Day15::default()),
+        Box::new(day_16::Day16::default()),
+        Box::new(day_17::Day17::default()),
+        Box::new(day_18::Day18::default()),
+        Box::new(day_19::Day19::default()),
+        Box::new(day_20::Day20::default()),
+        Box::new(day_21::Day21::default()),
+        Box::new(day_22::Day22::default()),
+        Box::new(day_23::Day23::default()),
+        Box::new(day_24::Day24::default()),
+        Box::new(day_25::Day25::default()),
+    ];
+}
+
+pub fn get_day(day: usize) -> Option<Box<dyn Solution>> {
+    return all_days().into_iter().find(|x| x.day() == day);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_has_every_day() {
+        let days: Vec<usize> = all_days().iter().map(|x| x.day()).collect::<Vec<usize>>();
+        assert!(days == (1..26).collect::<Vec<usize>>());
+        assert!(get_day(16).unwrap().day() == 16);
+        assert!(get_day(26).is_none());
+    }
+
+    #[test]
+    fn answer_display() {
+        assert!(Answer::from(2769675usize).to_string() == "2769675");
+        assert!(Answer::from(-3i32) == Answer::Number(-3));
+        assert!(Answer::from("co,de,ka,ta").to_string() == "co,de,ka,ta");
+        assert!(Answer::from(u64::MAX).to_string() == "18446744073709551615");
+        assert!(Answer::from(u64::MAX) != Answer::Number(-1));
+    }
+
+    #[test]
+    fn answers_compare_as_printed() {
+        assert!(Answer::from("22") == Answer::Number(22));
+        assert!(Answer::Number(22) == Answer::from("22"));
+        assert!(Answer::from("022") != Answer::Number(22));
+        assert!(Answer::from("co,de") != Answer::Number(22));
+    }
+
+    #[test]
+    fn solve_from_lines() {
+        let mut day_1: Box<dyn Solution> = get_day(1).unwrap();
+        day_1.parse(["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"].iter().map(|x| x.to_string()).collect::<Vec<String>>()).unwrap();
+        assert!(day_1.part1().unwrap() == Answer::Number(11));
+        assert!(day_1.solve(true).unwrap() == Answer::Number(31));
+    }
+}
@@ -0,0 +1,166 @@
+use std::{fmt, panic::{self, AssertUnwindSafe}, time::{Duration, Instant}};
+
+use crate::answers::Answers;
+use crate::error::AocError;
+use crate::inputs::{self, InputResolver};
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub day: usize,
+    pub part: usize,
+    pub variant: String,
+    pub expected: Option<Answer>,
+    pub actual: Result<Answer, AocError>,
+    pub elapsed: Duration,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (Err(AocError::MissingInput { .. }), _) => Status::Missing,
+            (Err(_), _) => Status::Fail,
+            (Ok(_), None) => Status::Missing,
+            (Ok(actual), Some(expected)) => {
+                if actual == expected {
+                    return Status::Pass;
+                } else {
+                    return Status::Fail;
+                }
+            },
+        }
+    }
+
+    fn actual_text(&self) -> String {
+        match &self.actual {
+            Ok(x) => return x.to_string(),
+            Err(AocError::MissingInput { .. }) => return "no input".to_string(),
+            Err(e) => return format!("error: {}", e),
+        }
+    }
+}
+
+pub fn run_checks(days: Vec<Box<dyn Solution>>, resolver: &InputResolver, answers: &Answers) -> Vec<Check> {
+    let mut checks: Vec<Check> = Vec::new();
+    for mut day in days {
+        for part in 1..3 {
+            // The real input is always checked, even without an answer, so it shows up as missing.
+            let mut variants: Vec<String> = answers.variants(day.day(), part);
+            if !variants.iter().any(|x| x == inputs::REAL_INPUT) {
+                variants.push(inputs::REAL_INPUT.to_string());
+            }
+
+            for variant in variants {
+                day.set_variant(&variant);
+                let start_time = Instant::now();
+                let actual: Result<Answer, AocError> = resolver.read(day.day(), &variant)
+                    .and_then(|input_lines| solve_caught(day.as_mut(), input_lines, part));
+                checks.push(Check {
+                    day: day.day(),
+                    part,
+                    expected: answers.get(day.day(), part, &variant).cloned(),
+                    variant,
+                    actual,
+                    elapsed: start_time.elapsed(),
+                });
+            }
+        }
+    }
+
+    return checks;
+}
+
+fn solve_caught(day: &mut dyn Solution, input_lines: Vec<String>, part: usize) -> Result<Answer, AocError> {
+    // A panicking day is reported as a failure rather than ending the whole run.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| day.parse(input_lines).and_then(|_| day.solve(part == 2))));
+    match result {
+        Ok(x) => return x,
+        Err(e) => {
+            let message: String = match e.downcast_ref::<&str>() {
+                Some(x) => x.to_string(),
+                None => e.downcast_ref::<String>().cloned().unwrap_or("unknown panic".to_string()),
+            };
+            return Err(AocError::parse(format!("panicked: {}", message)));
+        },
+    }
+}
+
+pub fn format_table(checks: &[Check]) -> String {
+    let header: Vec<String> = ["Day", "Part", "Variant", "Expected", "Actual", "Status", "Time"].iter().map(|x| x.to_string()).collect::<Vec<String>>();
+    let mut rows: Vec<Vec<String>> = vec![header];
+    for c in checks {
+        rows.push(vec![
+            c.day.to_string(),
+            c.part.to_string(),
+            c.variant.clone(),
+            c.expected.as_ref().map(|x| x.to_string()).unwrap_or("-".to_string()),
+            c.actual_text(),
+            c.status().to_string(),
+            format!("{:?}", c.elapsed),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len()).map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0)).collect::<Vec<usize>>();
+    let mut lines: Vec<String> = rows.iter()
+        .map(|r| r.iter().zip(widths.iter()).map(|(x, w)| format!("{:<width$}", x, width = *w)).collect::<Vec<String>>().join("  ").trim_end().to_string())
+        .collect::<Vec<String>>();
+    lines.insert(1, widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("  "));
+
+    let count = |status: Status| checks.iter().filter(|x| x.status() == status).count();
+    lines.push(String::new());
+    lines.push(format!("{} passed, {} failed, {} missing", count(Status::Pass), count(Status::Fail), count(Status::Missing)));
+    return lines.join("\n");
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution;
+    use std::{env, fs, path::PathBuf};
+
+    #[test]
+    fn verify_day_1() {
+        let root: PathBuf = env::temp_dir().join(format!("aoc_verify_{}", std::process::id()));
+        fs::create_dir_all(root.join("day_1")).unwrap();
+        fs::write(root.join("day_1").join("input_example_1.txt"), "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        let resolver: InputResolver = InputResolver::new(&root, inputs::YEAR);
+
+        let mut answers: Answers = Answers::default();
+        answers.insert(1, 1, "example_1", Answer::Number(11));
+        answers.insert(1, 2, "example_1", Answer::Number(30));
+
+        let checks: Vec<Check> = run_checks(vec![solution::get_day(1).unwrap()], &resolver, &answers);
+        let statuses: Vec<(usize, String, Status)> = checks.iter().map(|x| (x.part, x.variant.clone(), x.status())).collect::<Vec<(usize, String, Status)>>();
+        assert!(statuses == vec![
+            (1, "example_1".to_string(), Status::Pass),
+            (1, "input".to_string(), Status::Missing),
+            (2, "example_1".to_string(), Status::Fail),
+            (2, "input".to_string(), Status::Missing),
+        ]);
+
+        let table: String = format_table(&checks);
+        assert!(table.lines().next().unwrap().starts_with("Day  Part  Variant    Expected  Actual"));
+        assert!(table.lines().any(|x| x.starts_with("1    2     example_1  30        31        FAIL")));
+        assert!(table.ends_with("1 passed, 1 failed, 2 missing"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}